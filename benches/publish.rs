@@ -0,0 +1,98 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use multi_agent::sync::{Shared, TripleBuffer};
+use std::hint::black_box;
+
+/// Data shaped like the one of the physarum workshop: a trail map and its agents.
+#[derive(Debug, Clone, Default)]
+struct SimulationData {
+    trail_map: Vec<f32>,
+    agents: Vec<[f32; 3]>,
+}
+
+impl SimulationData {
+    fn new(width: usize, height: usize, agents: usize) -> Self {
+        Self {
+            trail_map: vec![0.5; width * height],
+            agents: vec![[1.0, 2.0, 0.25]; agents],
+        }
+    }
+}
+
+/// Sizes of the published data, as `(width, height, agents)`.
+const SIZES: [(usize, usize, usize); 3] =
+    [(100, 100, 1_000), (400, 300, 10_000), (800, 600, 100_000)];
+
+fn publish(c: &mut Criterion) {
+    let mut group = c.benchmark_group("publish");
+
+    for (width, height, agents) in SIZES {
+        let data = SimulationData::new(width, height, agents);
+        let size = format!("{width}x{height}+{agents}");
+
+        group.bench_with_input(
+            BenchmarkId::new("arc_swap_store", &size),
+            &data,
+            |b, data| {
+                let shared = Shared::new(SimulationData::default());
+                b.iter(|| shared.store(black_box(data).clone()));
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("triple_buffer", &size),
+            &data,
+            |b, data| {
+                let mut buffer = TripleBuffer::new(Shared::new(SimulationData::default()));
+                b.iter(|| buffer.publish(black_box(data)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn publish_while_reading(c: &mut Criterion) {
+    let mut group = c.benchmark_group("publish_while_reading");
+    let data = SimulationData::new(800, 600, 100_000);
+
+    group.bench_function("arc_swap_store", |b| {
+        let shared = Shared::new(SimulationData::default());
+        b.iter(|| {
+            let frame = shared.load();
+            shared.store(black_box(&data).clone());
+            black_box(frame.trail_map.len() + frame.agents.len())
+        });
+    });
+
+    group.bench_function("triple_buffer", |b| {
+        let shared = Shared::new(SimulationData::default());
+        let mut buffer = TripleBuffer::new(shared.clone());
+        b.iter(|| {
+            let frame = shared.load();
+            buffer.publish(black_box(&data));
+            black_box(frame.trail_map.len() + frame.agents.len())
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, publish, publish_while_reading);
+
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Flag telling a simulation to give up the work in progress.
+///
+/// The runtime cancels the token passed to `MultiAgentSimulation::update` when
+/// it shuts down. Poll [`is_cancelled`](Self::is_cancelled) inside long loops
+/// and return early once it is set: the data returned then is discarded.
+///
+/// Clones share the same flag.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_core::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let clone = token.clone();
+///
+/// clone.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token and all its clones.
+    #[inline]
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether the token has been cancelled.
+    #[inline]
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
@@ -25,6 +25,51 @@ pub enum Error {
     #[error("Simulation thread panicked: {0}")]
     SimulationPanic(String),
 
+    /// Simulation returned an error and was halted until the application closed.
+    #[error("Simulation failed at tick {tick}: {source}")]
+    SimulationFailed {
+        /// Tick during which the simulation failed.
+        tick: u64,
+        /// The error returned by the simulation.
+        source: Box<Self>,
+    },
+
+    /// Simulation does not implement `MultiAgentSimulation::restore`.
+    #[error("Simulation cannot be restored from its published data")]
+    RestoreUnsupported,
+
+    /// The frame of the given tick is no longer kept in the timeline.
+    #[error("Frame of tick {tick} is not in the timeline")]
+    FrameUnavailable {
+        /// The requested tick.
+        tick: u64,
+    },
+
+    /// A recording could not be saved or loaded.
+    #[error("Recording error: {0}")]
+    Recording(String),
+
+    /// A session could not be saved or opened.
+    #[error("Session error: {0}")]
+    Session(String),
+
+    /// The results of a batch run could not be written.
+    #[error("Batch output error: {0}")]
+    Batch(String),
+
+    /// A remote simulation or GUI could not be reached, or sent an invalid frame.
+    #[error("Remote transport error: {0}")]
+    Remote(String),
+
+    /// The HTTP observation endpoint could not be started.
+    #[error("HTTP endpoint error: {0}")]
+    Http(String),
+
+    /// The runtime is misconfigured, or a configuration file given on the command
+    /// line could not be loaded.
+    #[error("Configuration error: {0}")]
+    Config(String),
+
     /// Simulation thread failed to stop within the specified timeout period.
     #[error("Simulation thread failed to stop within {timeout:?}")]
     ShutdownTimeout {
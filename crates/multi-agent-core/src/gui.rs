@@ -120,6 +120,19 @@ pub trait MultiAgentGui: Debug + Default {
     /// Default: 250.0
     const SIDEBAR_DEFAULT_WIDTH_IN_PIXELS: f32 = 250.0;
 
+    /// Show the transport toolbar (pause, step, speed) above the content.
+    ///
+    /// Default: true
+    const SHOW_TRANSPORT_TOOLBAR: bool = true;
+
+    /// Redraw on every frame of the display instead of only when the simulation
+    /// publishes data, the controls change or the user interacts with the window.
+    ///
+    /// Enable it for content animated independently of the simulation.
+    ///
+    /// Default: false
+    const REPAINT_CONTINUOUSLY: bool = false;
+
     /// Configuration data sent from GUI to simulation.
     ///
     /// This should match the `GuiData` type in your `MultiAgentSimulation` implementation.
@@ -244,4 +257,15 @@ pub trait MultiAgentGui: Debug + Default {
         send_message_to_simulation: F,
     ) where
         F: Fn(Self::MessageToSimulation);
+
+    /// Called before rendering when the GUI data was replaced from outside the
+    /// sidebar, for example by an opened session or a scenario script.
+    ///
+    /// Override it to bring the widgets of the sidebar in line with the new values,
+    /// so that the next change made in the sidebar does not revert them.
+    ///
+    /// # Arguments
+    /// * `gui_data` - The GUI data now shared with the simulation
+    #[inline]
+    fn on_gui_data_changed(&mut self, _gui_data: &Self::GuiData) {}
 }
@@ -0,0 +1,96 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{Error, Result};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Write `value` as JSON to the file at `path`, creating or truncating it.
+///
+/// # Errors
+/// Returns the error built by `error` from the message of the failure if the
+/// file cannot be created or written.
+#[inline]
+pub fn save_json<T, P>(path: P, value: &T, error: fn(String) -> Error) -> Result<()>
+where
+    T: Serialize + ?Sized,
+    P: AsRef<Path>,
+{
+    write_json(path, error, |writer| serde_json::to_writer(writer, value))
+}
+
+/// Write `value` as indented JSON to the file at `path`, creating or truncating it.
+///
+/// # Errors
+/// Returns the error built by `error` from the message of the failure if the
+/// file cannot be created or written.
+#[inline]
+pub fn save_json_pretty<T, P>(path: P, value: &T, error: fn(String) -> Error) -> Result<()>
+where
+    T: Serialize + ?Sized,
+    P: AsRef<Path>,
+{
+    write_json(path, error, |writer| {
+        serde_json::to_writer_pretty(writer, value)
+    })
+}
+
+fn write_json<P: AsRef<Path>>(
+    path: P,
+    error: fn(String) -> Error,
+    serialize: impl FnOnce(&mut BufWriter<File>) -> serde_json::Result<()>,
+) -> Result<()> {
+    let file = File::create(path).map_err(|err| error(err.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    serialize(&mut writer).map_err(|err| error(err.to_string()))?;
+    writer.flush().map_err(|err| error(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_save_json() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-core-test-json-{}.json",
+            std::process::id()
+        ));
+
+        save_json(&path, &[1, 2, 3], Error::Session).unwrap();
+        let compact: String = fs::read_to_string(&path).unwrap();
+        save_json_pretty(&path, &[1, 2, 3], Error::Session).unwrap();
+        let pretty: String = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(compact, "[1,2,3]");
+        assert_eq!(pretty, "[\n  1,\n  2,\n  3\n]");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_save_json_reports_write_errors() {
+        assert!(matches!(
+            save_json("/dev/full", &[1, 2, 3], Error::Recording),
+            Err(Error::Recording(_))
+        ));
+    }
+}
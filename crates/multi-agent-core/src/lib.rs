@@ -14,14 +14,24 @@
  * limitations under the License.
  */
 
+mod cancel;
 mod error;
 mod guard_arc;
 mod gui;
+#[cfg(feature = "serde")]
+mod json;
 mod result;
+mod seed;
+mod session;
 mod simulation;
 
+pub use cancel::CancellationToken;
 pub use error::Error;
 pub use guard_arc::GuardArc;
 pub use gui::MultiAgentGui;
+#[cfg(feature = "serde")]
+pub use json::{save_json, save_json_pretty};
 pub use result::Result;
+pub use seed::SeedFactory;
+pub use session::Session;
 pub use simulation::MultiAgentSimulation;
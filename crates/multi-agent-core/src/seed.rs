@@ -0,0 +1,113 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::{BuildHasher, RandomState},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Source of reproducible seeds handed to a simulation by the runtime.
+///
+/// The runtime owns a master seed, shown in the GUI, and gives the simulation a
+/// factory derived from it. Every independent random stream (one per thread,
+/// one per agent kind, ...) takes its own seed from [`stream`], so that the same
+/// master seed always reproduces the same run, whatever RNG crate is used.
+///
+/// [`stream`]: SeedFactory::stream
+///
+/// # Example
+/// ```rust,ignore
+/// use multi_agent::SeedFactory;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
+///     let mut rng = StdRng::seed_from_u64(seeds.stream(0));
+///     let workers: Vec<fastrand::Rng> = (1..=4)
+///         .map(|index| fastrand::Rng::with_seed(seeds.stream(index)))
+///         .collect();
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SeedFactory {
+    master: u64,
+}
+
+impl SeedFactory {
+    /// Create a factory from a master seed.
+    #[inline]
+    #[must_use]
+    pub const fn new(master: u64) -> Self {
+        Self { master }
+    }
+
+    /// Create a factory from a master seed that differs on every call.
+    #[inline]
+    #[must_use]
+    pub fn random() -> Self {
+        let nanos: u128 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        Self::new(RandomState::new().hash_one(nanos))
+    }
+
+    /// The master seed every stream is derived from.
+    #[inline]
+    #[must_use]
+    pub const fn master(&self) -> u64 {
+        self.master
+    }
+
+    /// Seed of the independent random stream number `index`.
+    ///
+    /// Streams are statistically independent from each other and from the master seed.
+    #[inline]
+    #[must_use]
+    pub const fn stream(&self, index: u64) -> u64 {
+        split_mix_64(self.master ^ split_mix_64(index))
+    }
+}
+
+/// Finalizer of the `SplitMix64` generator, a bijective 64-bit mix.
+const fn split_mix_64(value: u64) -> u64 {
+    let mut z: u64 = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeedFactory;
+
+    #[test]
+    fn test_streams_are_reproducible() {
+        let seeds = SeedFactory::new(42);
+
+        assert_eq!(seeds.master(), 42);
+        assert_eq!(seeds.stream(0), SeedFactory::new(42).stream(0));
+        assert_ne!(seeds.stream(0), seeds.stream(1));
+        assert_ne!(seeds.stream(0), SeedFactory::new(43).stream(0));
+    }
+
+    #[test]
+    fn test_random_seeds_differ() {
+        assert_ne!(SeedFactory::random(), SeedFactory::random());
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use crate::{save_json, Error, Result};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Snapshot of a running simulation, to resume it later or on another machine.
+///
+/// A session holds everything needed to continue a run: the GUI data, the data
+/// published by the last tick, the tick count and the master seed. Simulations
+/// are recreated from it with `MultiAgentSimulation::restore_session`.
+///
+/// With the `serde` feature, sessions can be saved to and loaded from JSON files
+/// tagged with the [`VERSION`](Self::VERSION) of the format.
+///
+/// # Example
+/// ```rust,ignore
+/// use multi_agent::Session;
+///
+/// let session: Session<PhysarumConfig, PhysarumData> = Session::load("spirals.json")?;
+/// println!("Saved at tick {} with seed {}", session.tick, session.seed);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Session<GuiData, SimulationData> {
+    /// Version of the session format, [`Session::VERSION`] for sessions created by this crate.
+    pub version: u32,
+    /// Master seed of the simulation.
+    pub seed: u64,
+    /// Number of ticks executed before the session was taken.
+    pub tick: u64,
+    /// GUI data shared with the simulation.
+    pub gui_data: GuiData,
+    /// Data published by the last tick.
+    pub simulation_data: SimulationData,
+}
+
+impl<GuiData, SimulationData> Session<GuiData, SimulationData> {
+    /// Current version of the session format.
+    pub const VERSION: u32 = 1;
+
+    /// Create a session of the current format version.
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        seed: u64,
+        tick: u64,
+        gui_data: GuiData,
+        simulation_data: SimulationData,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            seed,
+            tick,
+            gui_data,
+            simulation_data,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<GuiData, SimulationData> Session<GuiData, SimulationData>
+where
+    GuiData: Serialize + DeserializeOwned,
+    SimulationData: Serialize + DeserializeOwned,
+{
+    /// Save the session to a JSON file.
+    ///
+    /// # Errors
+    /// Returns `Error::Session` if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save_json(path, self, Error::Session)
+    }
+
+    /// Load a session from a JSON file written by [`save`](Self::save).
+    ///
+    /// # Errors
+    /// Returns `Error::Session` if the file cannot be read or parsed, or if it
+    /// was written with another version of the format.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path).map_err(|err| Error::Session(err.to_string()))?;
+        let value: serde_json::Value = serde_json::from_reader(BufReader::new(file))
+            .map_err(|err| Error::Session(err.to_string()))?;
+
+        let version: Option<u64> = value.get("version").and_then(serde_json::Value::as_u64);
+        if version != Some(u64::from(Self::VERSION)) {
+            return Err(Error::Session(format!(
+                "Unsupported session version {}, expected {}",
+                version.map_or_else(|| String::from("(none)"), |version| version.to_string()),
+                Self::VERSION
+            )));
+        }
+        serde_json::from_value(value).map_err(|err| Error::Session(err.to_string()))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_session_save_and_load() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-core-test-session-{}.json",
+            std::process::id()
+        ));
+        let session: Session<u32, Vec<u8>> = Session::new(7, 120, 3, vec![1, 2, 3]);
+
+        session.save(&path).unwrap();
+        let loaded: Session<u32, Vec<u8>> = Session::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, session);
+    }
+
+    #[test]
+    fn test_session_version_is_checked() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-core-test-session-version-{}.json",
+            std::process::id()
+        ));
+        let mut session: Session<u32, u32> = Session::new(7, 120, 3, 4);
+        session.version = Session::<u32, u32>::VERSION + 1;
+
+        session.save(&path).unwrap();
+        let loaded = Session::<u32, u32>::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(loaded, Err(Error::Session(_))));
+    }
+}
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::Result;
+use crate::{CancellationToken, Error, Result, SeedFactory, Session};
 use std::{fmt::Debug, time::Duration};
 
 /// A multi-agent simulation framework trait.
@@ -26,7 +26,7 @@ use std::{fmt::Debug, time::Duration};
 /// # Example
 ///
 /// ```rust,ignore
-/// use multi_agent::{MultiAgentSimulation, Result};
+/// use multi_agent::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
 /// use std::time::Duration;
 /// # #[derive(Clone, Default, Debug)] struct Agent;
 /// # impl Agent { fn update(&mut self, _dt: f32) {} }
@@ -47,7 +47,7 @@ use std::{fmt::Debug, time::Duration};
 ///     type MessageFromGui = GuiMessage;
 ///     type MessageToGui = SimulationMessage;
 ///
-///     fn new(initial_gui_data: Self::GuiData) -> Result<Self> {
+///     fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
 ///         Ok(Self {
 ///             agents: Vec::new(),
 ///         })
@@ -59,6 +59,7 @@ use std::{fmt::Debug, time::Duration};
 ///         messages: Vec<Self::MessageFromGui>,
 ///         delta_time: Duration,
 ///         send_message_to_gui: F,
+///         cancellation: &CancellationToken,
 ///     ) -> Result<&Self::SimulationData>
 ///     where
 ///         F: Fn(Self::MessageToGui),
@@ -71,6 +72,9 @@ use std::{fmt::Debug, time::Duration};
 ///         // Update simulation state
 ///         let dt = delta_time.as_secs_f32();
 ///         for agent in &mut self.agents {
+///             if cancellation.is_cancelled() {
+///                 break;
+///             }
 ///             agent.update(dt);
 ///         }
 ///
@@ -134,14 +138,16 @@ pub trait MultiAgentSimulation: Debug + Send + 'static {
 
     /// Create a new simulation instance with initial GUI data.
     ///
-    /// This is called once when the simulation starts, before the first `update()` call.
+    /// This is called when the simulation starts, before the first `update()` call, and
+    /// again every time the simulation is restarted.
     ///
     /// # Arguments
     /// * `initial_gui_data` - The initial configuration from the GUI
+    /// * `seeds` - Seeds of the random streams, derived from the master seed of the runtime
     ///
     /// # Errors
     /// Return an error if the simulation cannot be initialized with the given configuration.
-    fn new(initial_gui_data: Self::GuiData) -> Result<Self>
+    fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self>
     where
         Self: Sized;
 
@@ -154,8 +160,11 @@ pub trait MultiAgentSimulation: Debug + Send + 'static {
     /// # Arguments
     /// * `gui_data` - Current configuration from the GUI (cloned each frame)
     /// * `messages` - All messages received from the GUI since the last update
-    /// * `delta_time` - Time elapsed since the last update call
+    /// * `delta_time` - Time elapsed since the last update call, or the constant time step
+    ///   when the runtime uses a fixed schedule
     /// * `send_message_to_gui` - Callback to send messages to the GUI
+    /// * `cancellation` - Cancelled when the runtime shuts down; poll it inside long
+    ///   loops and return early once it is set
     ///
     /// # Returns
     /// A reference to the simulation data that will be shared with the GUI.
@@ -163,7 +172,7 @@ pub trait MultiAgentSimulation: Debug + Send + 'static {
     ///
     /// # Errors
     /// Return an error if the simulation encounters an unrecoverable error.
-    /// This will cause the simulation thread to terminate.
+    /// The runtime halts the simulation and reports the error until it is restarted.
     ///
     /// # Performance Tips
     /// - Message sending is lossy: messages may be dropped if the channel is full
@@ -175,7 +184,99 @@ pub trait MultiAgentSimulation: Debug + Send + 'static {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         send_message_to_gui: F,
+        cancellation: &CancellationToken,
     ) -> Result<&Self::SimulationData>
     where
         F: Fn(Self::MessageToGui);
+
+    /// Whether the simulation has nothing to do until it receives a message or the
+    /// GUI data changes.
+    ///
+    /// While it returns `true`, the runtime stops ticking the simulation on its own
+    /// and sleeps until one of those events, then runs a single tick. Override it to
+    /// save CPU while the simulation is paused by its own logic, like a game of life
+    /// waiting for the user to press "play". Headless runs tick on demand and ignore it.
+    ///
+    /// Default: `false`, the simulation is ticked continuously
+    #[inline]
+    fn is_idle(&self) -> bool {
+        false
+    }
+
+    /// Called on the simulation thread before the first `update()` of an instance,
+    /// whether it was created, restarted or restored.
+    #[inline]
+    fn on_start(&mut self) {}
+
+    /// Called on the simulation thread when the simulation is paused.
+    #[inline]
+    fn on_pause(&mut self) {}
+
+    /// Called on the simulation thread when the simulation is resumed after a pause.
+    #[inline]
+    fn on_resume(&mut self) {}
+
+    /// Called once on the simulation thread when the runtime shuts down, after the last
+    /// `update()`, and when this instance is replaced by a restart, a rollback or an
+    /// opened session. Release external resources here: files, sockets, worker threads.
+    #[inline]
+    fn on_shutdown(&mut self) {}
+
+    /// Called before `update()` when the GUI published new data since the previous tick.
+    ///
+    /// Use it to react to a configuration change once, instead of comparing
+    /// `gui_data` on every tick.
+    ///
+    /// # Arguments
+    /// * `gui_data` - The new configuration from the GUI
+    #[inline]
+    fn on_gui_data_changed(&mut self, _gui_data: &Self::GuiData) {}
+
+    /// Recreate a simulation from data it published earlier.
+    ///
+    /// The runtime calls this to roll back to the last good tick after a panic,
+    /// and to resume the simulation from a frame of the timeline.
+    /// Implement it when `SimulationData` holds enough of the simulation state
+    /// to rebuild it.
+    ///
+    /// # Arguments
+    /// * `gui_data` - Current configuration from the GUI
+    /// * `simulation_data` - Data published by the tick to restore
+    /// * `seeds` - Seeds of the random streams, derived from the master seed of the runtime
+    ///
+    /// # Errors
+    /// The default implementation returns `Error::RestoreUnsupported`.
+    fn restore(
+        _gui_data: Self::GuiData,
+        _simulation_data: Self::SimulationData,
+        _seeds: SeedFactory,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(Error::RestoreUnsupported)
+    }
+
+    /// Recreate a simulation from a session saved to a file, possibly on another machine.
+    ///
+    /// The runtime calls this when a session is opened from the toolbar, then
+    /// continues from the tick of the session. Override it when the session needs
+    /// more than `restore`, like replaying the random draws made before `session.tick`.
+    ///
+    /// # Arguments
+    /// * `session` - GUI data, published data, tick count and master seed of the saved run
+    ///
+    /// # Errors
+    /// The default implementation returns the result of `restore` with the data
+    /// and the master seed of the session.
+    fn restore_session(session: Session<Self::GuiData, Self::SimulationData>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::restore(
+            session.gui_data,
+            session.simulation_data,
+            SeedFactory::new(session.seed),
+        )
+    }
 }
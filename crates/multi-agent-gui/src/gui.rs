@@ -14,15 +14,20 @@
  * limitations under the License.
  */
 
+use crate::{
+    overlay, perf::PerformanceOverlay, scrubber::TimelineBar, toolbar::TransportToolbar,
+    WindowOptions,
+};
 use eframe::{
-    egui::{CentralPanel, Color32, Context, SidePanel, ViewportBuilder, Visuals}, App, Frame,
-    NativeOptions,
+    egui::{CentralPanel, Color32, Context, SidePanel, TopBottomPanel, ViewportBuilder, Visuals},
+    App, Frame, NativeOptions,
 };
 use multi_agent_core::{Error, GuardArc, MultiAgentGui, Result};
 use multi_agent_sync::{
     message::{MessageReceiver, MessageSender},
-    Shared,
+    RuntimeStats, Shared, SimulationControl, Timeline,
 };
+use std::thread::{self, ThreadId};
 
 pub struct AppGui<Interface>
 where
@@ -33,6 +38,11 @@ where
     receiver: MessageReceiver<Interface::MessageFromSimulation>,
     gui_data: Shared<Interface::GuiData>,
     simulation_data: Shared<Interface::SimulationData>,
+    control: SimulationControl,
+    toolbar: TransportToolbar,
+    timeline: Option<TimelineBar<Interface::SimulationData>>,
+    performance: Option<PerformanceOverlay>,
+    gui_generation: Option<u64>,
 }
 
 impl<Interface> AppGui<Interface>
@@ -40,11 +50,13 @@ where
     Interface: MultiAgentGui + Default,
 {
     #[inline]
+    #[must_use]
     pub fn new(
         sender: MessageSender<Interface::MessageToSimulation>,
         receiver: MessageReceiver<Interface::MessageFromSimulation>,
         gui_data: Shared<Interface::GuiData>,
         simulation_data: Shared<Interface::SimulationData>,
+        control: SimulationControl,
     ) -> Self {
         Self {
             inner: Interface::default(),
@@ -52,27 +64,96 @@ where
             receiver,
             gui_data,
             simulation_data,
+            control,
+            toolbar: TransportToolbar::new(),
+            timeline: None,
+            performance: None,
+            gui_generation: None,
         }
     }
 
+    /// Show a timeline bar to scrub through the frames recorded in `timeline`.
     #[inline]
-    pub fn run(self) -> Result<()> {
+    #[must_use]
+    pub fn with_timeline(mut self, timeline: Timeline<Interface::SimulationData>) -> Self {
+        self.timeline = Some(TimelineBar::new(timeline));
+        self
+    }
+
+    /// Offer a performance overlay drawing rolling graphs of `stats`, toggled with `F3`.
+    #[inline]
+    #[must_use]
+    pub fn with_stats(mut self, stats: Shared<RuntimeStats>) -> Self {
+        self.performance = Some(PerformanceOverlay::new(stats));
+        self
+    }
+
+    /// Open the window and run the GUI on the current thread until it is closed.
+    ///
+    /// # Arguments
+    /// * `window` - Options of the native window
+    ///
+    /// # Errors
+    /// Returns `Error::Gui` if the window cannot be created or the GUI framework fails.
+    #[inline]
+    pub fn run(self, window: &WindowOptions) -> Result<()> {
         eframe::run_native(
             Interface::APP_NAME,
             NativeOptions {
                 viewport: ViewportBuilder::default()
-                    .with_min_inner_size(Interface::MIN_WINDOW_SIZE_IN_PIXELS)
-                    .with_inner_size(Interface::WINDOW_SIZE_IN_PIXELS)
-                    .with_maximized(true),
-                centered: true,
+                    .with_min_inner_size(
+                        window
+                            .min_inner_size
+                            .unwrap_or(Interface::MIN_WINDOW_SIZE_IN_PIXELS),
+                    )
+                    .with_inner_size(
+                        window
+                            .inner_size
+                            .unwrap_or(Interface::WINDOW_SIZE_IN_PIXELS),
+                    )
+                    .with_maximized(window.maximized)
+                    .with_fullscreen(window.fullscreen),
+                centered: window.centered,
                 ..NativeOptions::default()
             },
-            Box::new(|_| Ok(Box::new(self))),
+            Box::new(|creation| {
+                repaint_on_change(
+                    &creation.egui_ctx,
+                    &self.simulation_data,
+                    &self.gui_data,
+                    &self.control,
+                );
+                Ok(Box::new(self))
+            }),
         )
         .map_err(|err| Error::Gui(err.to_string()))
     }
 }
 
+/// Wake up the window to redraw it whenever the simulation publishes data, the
+/// GUI data is written by another thread or the controls change.
+///
+/// Messages from the simulation are sent during a tick, so they come with data.
+/// The GUI writes its own data while drawing a frame, so that write must not ask
+/// for another frame, or a sidebar returning `Some` every frame would never idle.
+fn repaint_on_change<SimulationData, GuiData>(
+    ctx: &Context,
+    simulation_data: &Shared<SimulationData>,
+    gui_data: &Shared<GuiData>,
+    control: &SimulationControl,
+) {
+    let repaint = |ctx: Context| move || ctx.request_repaint();
+    let gui_thread: ThreadId = thread::current().id();
+    let repaint_gui_data = repaint(ctx.clone());
+    simulation_data.on_change(repaint(ctx.clone()));
+    gui_data.on_change(move || {
+        if thread::current().id() != gui_thread {
+            repaint_gui_data();
+        }
+    });
+    control.on_change(repaint(ctx.clone()));
+}
+
 impl<Interface> App for AppGui<Interface>
 where
     Interface: MultiAgentGui + Default,
@@ -82,7 +163,35 @@ where
         self.inner
             .received_messages_from_simulation(self.receiver.drain());
 
-        let simulation_data: GuardArc<Interface::SimulationData> = self.simulation_data.load();
+        let generation: u64 = self.gui_data.generation();
+        if self.gui_generation != Some(generation) {
+            self.inner.on_gui_data_changed(&self.gui_data.load());
+            self.gui_generation = Some(generation);
+        }
+
+        if let Some(timeline) = &mut self.timeline {
+            TopBottomPanel::bottom("multi-agent-gui::Gui.update[timeline]")
+                .show(ctx, |ui| timeline.show(ui, &self.control));
+        }
+
+        let simulation_data: GuardArc<Interface::SimulationData> = self
+            .timeline
+            .as_mut()
+            .and_then(TimelineBar::frozen_frame)
+            .map_or_else(|| self.simulation_data.load(), GuardArc::from_inner);
+
+        if Interface::SHOW_TRANSPORT_TOOLBAR {
+            TopBottomPanel::top("multi-agent-gui::Gui.update[toolbar]")
+                .show(ctx, |ui| {
+                    self.toolbar.show(
+                        ui,
+                        &self.control,
+                        self.performance
+                            .as_mut()
+                            .map(PerformanceOverlay::visible_mut),
+                    );
+                });
+        }
 
         let mut gui_data: Option<Interface::GuiData> = None;
         SidePanel::left("multi-agent-gui::Gui.update[sidebar]")
@@ -91,22 +200,33 @@ where
                 gui_data = self
                     .inner
                     .sidebar(&simulation_data, ctx, frame, ui, |message| {
-                        self.sender.send_lossy(message)
+                        self.sender.send_lossy(message);
                     });
             });
 
         CentralPanel::default().show(ctx, |ui| {
             self.inner
                 .content(&simulation_data, ctx, frame, ui, |message| {
-                    self.sender.send_lossy(message)
-                })
+                    self.sender.send_lossy(message);
+                });
         });
 
         if let Some(gui_data) = gui_data {
             self.gui_data.store(gui_data);
+            self.gui_generation = Some(self.gui_data.generation());
         }
 
-        ctx.request_repaint();
+        if let Some(performance) = &mut self.performance {
+            performance.show(ctx);
+        }
+
+        if let Some(failure) = self.control.failure() {
+            overlay::show_failure(ctx, &self.control, &failure);
+        }
+
+        if Interface::REPAINT_CONTINUOUSLY {
+            ctx.request_repaint();
+        }
     }
 
     #[inline]
@@ -115,3 +235,46 @@ where
         Color32::from_rgba_unmultiplied(r, g, b, a).to_normalized_gamma_f32()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::RawInput;
+
+    /// Run passes until the context no longer asks for one.
+    fn settle(ctx: &Context) {
+        for _ in 0..10 {
+            if !ctx.has_requested_repaint() {
+                return;
+            }
+            let _ = ctx.run(RawInput::default(), |_| {});
+        }
+        panic!("the context keeps requesting repaints");
+    }
+
+    #[test]
+    fn test_idle_gui_stops_repainting() {
+        let ctx = Context::default();
+        let simulation_data: Shared<u64> = Shared::new(0);
+        let gui_data: Shared<u64> = Shared::new(0);
+        let control = SimulationControl::new();
+        repaint_on_change(&ctx, &simulation_data, &gui_data, &control);
+        settle(&ctx);
+
+        gui_data.store(1);
+        assert!(!ctx.has_requested_repaint());
+
+        thread::scope(|scope| {
+            scope.spawn(|| gui_data.store(2));
+        });
+        assert!(ctx.has_requested_repaint());
+        settle(&ctx);
+
+        simulation_data.store(1);
+        assert!(ctx.has_requested_repaint());
+        settle(&ctx);
+
+        control.pause();
+        assert!(ctx.has_requested_repaint());
+    }
+}
@@ -15,6 +15,12 @@
  */
 
 mod gui;
+mod overlay;
+mod perf;
+mod scrubber;
+mod toolbar;
+mod window;
 
 pub use eframe::{self, egui};
 pub use gui::AppGui;
+pub use window::WindowOptions;
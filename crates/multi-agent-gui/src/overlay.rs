@@ -0,0 +1,44 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use eframe::egui::{Context, Id, Modal, RichText, ViewportCommand};
+use multi_agent_sync::{SimulationControl, SimulationFailure};
+
+/// Show the failure reported by the simulation thread on top of the window.
+///
+/// The overlay offers to restart the simulation or to quit the application.
+#[inline]
+pub fn show_failure(ctx: &Context, control: &SimulationControl, failure: &SimulationFailure) {
+    Modal::new(Id::new("multi-agent-gui::overlay[failure]")).show(ctx, |ui| {
+        ui.heading("Simulation failed");
+        ui.label(format!("At tick {}", failure.tick));
+        ui.separator();
+        ui.label(RichText::new(&failure.message).monospace());
+        if let Some(location) = &failure.location {
+            ui.label(format!("Panicked at {location}"));
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui.button("Restart").clicked() {
+                control.restart();
+            }
+            if ui.button("Quit").clicked() {
+                ctx.send_viewport_cmd(ViewportCommand::Close);
+            }
+        });
+    });
+}
@@ -0,0 +1,174 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use eframe::egui::{
+    pos2, Align2, Color32, Context, Id, Key, Pos2, Sense, Shape, Stroke, Ui, Vec2, Window,
+};
+use multi_agent_sync::{ChannelFill, RuntimeStats, Shared};
+use std::collections::VecDeque;
+
+/// Floating window drawing rolling graphs of the statistics published by the runtime.
+///
+/// The overlay is hidden by default and toggled with `F3` or from the toolbar.
+#[derive(Debug)]
+pub struct PerformanceOverlay {
+    stats: Shared<RuntimeStats>,
+    history: VecDeque<RuntimeStats>,
+    visible: bool,
+}
+
+impl PerformanceOverlay {
+    /// Number of samples kept for the graphs.
+    const HISTORY_LENGTH: usize = 300;
+
+    /// Size of a single graph.
+    const GRAPH_SIZE: Vec2 = Vec2::new(260.0, 40.0);
+
+    /// Key toggling the overlay.
+    const TOGGLE_KEY: Key = Key::F3;
+
+    #[inline]
+    #[must_use]
+    pub fn new(stats: Shared<RuntimeStats>) -> Self {
+        Self {
+            stats,
+            history: VecDeque::with_capacity(Self::HISTORY_LENGTH),
+            visible: false,
+        }
+    }
+
+    /// Visibility of the overlay, for the toggle of the toolbar.
+    #[inline]
+    pub const fn visible_mut(&mut self) -> &mut bool {
+        &mut self.visible
+    }
+
+    /// Record the latest statistics and draw the overlay if it is visible.
+    ///
+    /// Samples are recorded even while the overlay is hidden, so that the
+    /// graphs are already filled when it is opened.
+    #[inline]
+    pub fn show(&mut self, ctx: &Context) {
+        if ctx.input(|input| input.key_pressed(Self::TOGGLE_KEY)) {
+            self.visible = !self.visible;
+        }
+        self.sample();
+
+        let history: &VecDeque<RuntimeStats> = &self.history;
+        Window::new("Performance")
+            .id(Id::new("multi-agent-gui::perf[overlay]"))
+            .open(&mut self.visible)
+            .resizable(false)
+            .collapsible(true)
+            .anchor(Align2::RIGHT_TOP, Vec2::new(-8.0, 48.0))
+            .show(ctx, |ui| Self::show_graphs(ui, history));
+    }
+
+    fn sample(&mut self) {
+        let stats: RuntimeStats = **self.stats.load();
+        if self.history.back() == Some(&stats) {
+            return;
+        }
+        if self.history.len() == Self::HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back(stats);
+    }
+
+    fn show_graphs(ui: &mut Ui, history: &VecDeque<RuntimeStats>) {
+        let Some(last) = history.back() else {
+            ui.label("Waiting for the first tick…");
+            return;
+        };
+        ui.label(format!("Tick {}, {} overrun(s)", last.tick, last.overruns));
+        ui.label(format!(
+            "Dropped messages: {} to the simulation, {} to the GUI",
+            last.gui_to_simulation.dropped, last.simulation_to_gui.dropped
+        ));
+        ui.separator();
+
+        let series =
+            |value: fn(&RuntimeStats) -> f32| -> Vec<f32> { history.iter().map(value).collect() };
+        let fill = |channel: &ChannelFill| channel.ratio().unwrap_or(0.0) * 100.0;
+
+        Self::graph(
+            ui,
+            "Update",
+            "ms",
+            &series(|stats| stats.update_time.as_secs_f32() * 1_000.0),
+            Color32::LIGHT_BLUE,
+        );
+        Self::graph(
+            ui,
+            "Publish",
+            "ms",
+            &series(|stats| stats.publish_time.as_secs_f32() * 1_000.0),
+            Color32::LIGHT_GREEN,
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        Self::graph(
+            ui,
+            "Achieved",
+            "Hz",
+            &series(|stats| stats.achieved_hz as f32),
+            Color32::GOLD,
+        );
+        Self::graph(
+            ui,
+            "GUI → simulation",
+            "%",
+            &history
+                .iter()
+                .map(|stats| fill(&stats.gui_to_simulation))
+                .collect::<Vec<f32>>(),
+            Color32::LIGHT_RED,
+        );
+        Self::graph(
+            ui,
+            "Simulation → GUI",
+            "%",
+            &history
+                .iter()
+                .map(|stats| fill(&stats.simulation_to_gui))
+                .collect::<Vec<f32>>(),
+            Color32::LIGHT_RED,
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn graph(ui: &mut Ui, label: &str, unit: &str, values: &[f32], color: Color32) {
+        let current: f32 = values.last().copied().unwrap_or_default();
+        let max: f32 = values.iter().copied().fold(f32::EPSILON, f32::max);
+        ui.label(format!("{label}: {current:.2} {unit} (max {max:.2})"));
+
+        let (response, painter) = ui.allocate_painter(Self::GRAPH_SIZE, Sense::hover());
+        let rect = response.rect;
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        let step: f32 = rect.width() / (Self::HISTORY_LENGTH - 1) as f32;
+        let points: Vec<Pos2> = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                pos2(
+                    (index as f32).mul_add(step, rect.left()),
+                    (value / max).mul_add(-rect.height(), rect.bottom()),
+                )
+            })
+            .collect();
+        painter.add(Shape::line(points, Stroke::new(1.5, color)));
+    }
+}
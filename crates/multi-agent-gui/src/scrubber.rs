@@ -0,0 +1,109 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use eframe::egui::{Slider, Ui};
+use multi_agent_sync::{SimulationControl, Timeline};
+use std::sync::Arc;
+
+/// Timeline bar scrubbing through the frames recorded from the simulation.
+///
+/// Moving the slider pauses the simulation and freezes the view on the
+/// selected frame until the bar goes back to live. The frame is kept while it
+/// is viewed, since a timeline of changes rebuilds it on every read.
+#[derive(Debug)]
+pub struct TimelineBar<T> {
+    timeline: Timeline<T>,
+    viewing: Option<u64>,
+    frame: Option<(u64, Arc<T>)>,
+}
+
+impl<T: Clone> TimelineBar<T> {
+    /// Width kept for the buttons next to the slider.
+    const BUTTONS_WIDTH_IN_PIXELS: f32 = 260.0;
+
+    #[inline]
+    #[must_use]
+    pub const fn new(timeline: Timeline<T>) -> Self {
+        Self {
+            timeline,
+            viewing: None,
+            frame: None,
+        }
+    }
+
+    /// Frame to show instead of the live data, if the view is frozen.
+    ///
+    /// Goes back to live if the frame has been dropped from the timeline.
+    #[inline]
+    pub fn frozen_frame(&mut self) -> Option<Arc<T>> {
+        let Some(tick) = self.viewing else {
+            self.frame = None;
+            return None;
+        };
+        if let Some((frame_tick, frame)) = &self.frame
+            && *frame_tick == tick
+            && self
+                .timeline
+                .ticks()
+                .is_some_and(|ticks| ticks.contains(&tick))
+        {
+            return Some(Arc::clone(frame));
+        }
+
+        self.frame = self.timeline.get(tick).map(|frame| (tick, frame));
+        if self.frame.is_none() {
+            self.viewing = None;
+        }
+        self.frame.as_ref().map(|(_, frame)| Arc::clone(frame))
+    }
+
+    /// Draw the bar on a single row.
+    #[inline]
+    pub fn show(&mut self, ui: &mut Ui, control: &SimulationControl) {
+        ui.horizontal(|ui| {
+            let Some(ticks) = self.timeline.ticks() else {
+                ui.label("Timeline: no frame recorded yet");
+                return;
+            };
+
+            if ui
+                .selectable_label(self.viewing.is_none(), "● Live")
+                .clicked()
+            {
+                self.viewing = None;
+            }
+
+            ui.spacing_mut().slider_width =
+                (ui.available_width() - Self::BUTTONS_WIDTH_IN_PIXELS).max(100.0);
+            let mut tick: u64 = self.viewing.unwrap_or_else(|| *ticks.end());
+            if ui.add(Slider::new(&mut tick, ticks).text("tick")).changed() {
+                control.pause();
+                self.viewing = Some(tick);
+            }
+
+            if let Some(tick) = self.viewing
+                && ui
+                    .button("⏵ Resume from here")
+                    .on_hover_text("Requires `MultiAgentSimulation::restore`")
+                    .clicked()
+            {
+                control.rewind_to(tick);
+                control.resume();
+                self.viewing = None;
+            }
+        });
+    }
+}
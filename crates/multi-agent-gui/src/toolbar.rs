@@ -0,0 +1,247 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use eframe::egui::{Color32, DragValue, Key, Slider, TextEdit, Ui};
+use multi_agent_core::SeedFactory;
+use multi_agent_sync::{
+    Recovery, RecoveryAction, ScriptStatus, SessionStatus, SimulationControl, SimulationFailure,
+};
+use std::ops::RangeInclusive;
+
+/// Transport toolbar driving a [`SimulationControl`].
+#[derive(Debug)]
+pub struct TransportToolbar {
+    step_ticks: u64,
+    seed_input: String,
+    script_input: String,
+    session_input: String,
+}
+
+impl TransportToolbar {
+    /// Range of the speed multiplier offered by the slider.
+    const TIME_SCALE_RANGE: RangeInclusive<f64> = 0.1..=10.0;
+
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            step_ticks: 1,
+            seed_input: String::new(),
+            script_input: String::new(),
+            session_input: String::new(),
+        }
+    }
+
+    /// Draw the toolbar on a single row.
+    ///
+    /// `performance` is the visibility of the performance overlay, if there is one.
+    #[inline]
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        control: &SimulationControl,
+        performance: Option<&mut bool>,
+    ) {
+        ui.horizontal(|ui| {
+            let paused: bool = control.is_paused();
+            if ui.selectable_label(!paused, "▶ Run").clicked() {
+                control.resume();
+            }
+            if ui.selectable_label(paused, "⏸ Pause").clicked() {
+                control.pause();
+            }
+
+            ui.separator();
+
+            if ui.button("⏭ Step").clicked() {
+                control.step(self.step_ticks);
+            }
+            ui.add(
+                DragValue::new(&mut self.step_ticks)
+                    .range(1..=10_000)
+                    .suffix(" ticks"),
+            );
+
+            ui.separator();
+
+            if ui
+                .button("⟲ Restart")
+                .on_hover_text("Recreate the simulation from the current settings")
+                .clicked()
+            {
+                control.restart();
+            }
+
+            self.show_seed(ui, control);
+
+            ui.separator();
+
+            let mut time_scale: f64 = control.time_scale();
+            let response = ui.add(
+                Slider::new(&mut time_scale, Self::TIME_SCALE_RANGE)
+                    .logarithmic(true)
+                    .suffix("×")
+                    .text("Speed"),
+            );
+            if response.changed() {
+                control.set_time_scale(time_scale);
+            }
+            if ui.button("1×").clicked() {
+                control.set_time_scale(1.0);
+            }
+
+            if let Some(status) = control.script_status() {
+                ui.separator();
+                self.show_script(ui, control, &status);
+            }
+
+            if let Some(status) = control.session_status() {
+                ui.separator();
+                self.show_session(ui, control, &status);
+            }
+
+            if let Some(visible) = performance {
+                ui.separator();
+                ui.toggle_value(visible, "📈 Stats")
+                    .on_hover_text("Show the performance overlay (F3)");
+            }
+
+            if let Some(recovery) = control.last_recovery() {
+                ui.separator();
+                Self::show_recoveries(ui, control.recovery_count(), &recovery);
+            }
+        });
+    }
+
+    fn show_seed(&mut self, ui: &mut Ui, control: &SimulationControl) {
+        ui.label(format!("Seed {}", control.seed()))
+            .on_hover_text("Master seed used when the simulation is created or restarted");
+
+        let response = ui.add(
+            TextEdit::singleline(&mut self.seed_input)
+                .hint_text("new seed")
+                .desired_width(120.0),
+        );
+        if response.lost_focus()
+            && ui.input(|input| input.key_pressed(Key::Enter))
+            && let Ok(seed) = self.seed_input.trim().parse::<u64>()
+        {
+            control.set_seed(seed);
+            control.restart();
+            self.seed_input.clear();
+        }
+
+        if ui
+            .button("🎲")
+            .on_hover_text("Restart with a random seed")
+            .clicked()
+        {
+            control.set_seed(SeedFactory::random().master());
+            control.restart();
+        }
+    }
+
+    fn show_script(&mut self, ui: &mut Ui, control: &SimulationControl, status: &ScriptStatus) {
+        let response = ui
+            .add(
+                TextEdit::singleline(&mut self.script_input)
+                    .hint_text("scenario.rhai")
+                    .desired_width(160.0),
+            )
+            .on_hover_text("Path of a scenario script to run before every tick");
+        let submitted: bool =
+            response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+        if (submitted || ui.button("📜 Load").clicked()) && !self.script_input.trim().is_empty() {
+            control.load_script(self.script_input.trim());
+        }
+
+        match status {
+            ScriptStatus::Idle => {}
+            ScriptStatus::Running { name } => {
+                ui.label(format!("Running {name}"));
+                if ui
+                    .button("✖")
+                    .on_hover_text("Stop running the script")
+                    .clicked()
+                {
+                    control.unload_script();
+                }
+            }
+            ScriptStatus::Failed { name, message } => {
+                ui.colored_label(Color32::RED, format!("⚠ {name} failed"))
+                    .on_hover_text(message);
+            }
+        }
+    }
+
+    fn show_session(&mut self, ui: &mut Ui, control: &SimulationControl, status: &SessionStatus) {
+        ui.add(
+            TextEdit::singleline(&mut self.session_input)
+                .hint_text("session.json")
+                .desired_width(160.0),
+        )
+        .on_hover_text("Path of the session file to save or open");
+
+        let path: &str = self.session_input.trim();
+        if ui
+            .button("💾 Save session")
+            .on_hover_text("Save the GUI data, the simulation data, the tick and the seed")
+            .clicked()
+            && !path.is_empty()
+        {
+            control.save_session(path);
+        }
+        if ui
+            .button("📂 Open session")
+            .on_hover_text("Requires `MultiAgentSimulation::restore_session`")
+            .clicked()
+            && !path.is_empty()
+        {
+            control.open_session(path);
+        }
+
+        match status {
+            SessionStatus::Idle => {}
+            SessionStatus::Saved { path, tick } => {
+                ui.label(format!("Saved at tick {tick}"))
+                    .on_hover_text(path.display().to_string());
+            }
+            SessionStatus::Opened { path, tick } => {
+                ui.label(format!("Opened at tick {tick}"))
+                    .on_hover_text(path.display().to_string());
+            }
+            SessionStatus::Failed { path, message } => {
+                ui.colored_label(Color32::RED, "⚠ Session failed")
+                    .on_hover_text(format!("{}:\n{message}", path.display()));
+            }
+        }
+    }
+
+    fn show_recoveries(ui: &mut Ui, count: u64, recovery: &Recovery) {
+        let action: &str = match recovery.action {
+            RecoveryAction::Restarted => "restarted",
+            RecoveryAction::RolledBack => "rolled back",
+        };
+        let failure: &SimulationFailure = &recovery.failure;
+        let location: &str = failure.location.as_deref().unwrap_or("unknown location");
+
+        ui.colored_label(Color32::YELLOW, format!("⚠ {count} panic(s) recovered"))
+            .on_hover_text(format!(
+                "Last one at tick {}, {action}:\n{}\nat {location}",
+                failure.tick, failure.message
+            ));
+    }
+}
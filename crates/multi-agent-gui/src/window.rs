@@ -0,0 +1,59 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// Options of the native window opened by [`AppGui::run`](crate::AppGui::run).
+///
+/// Sizes left to `None` fall back to the constants of the `MultiAgentGui` implementation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowOptions {
+    /// Initial window size in pixels [width, height].
+    ///
+    /// Default: `MultiAgentGui::WINDOW_SIZE_IN_PIXELS`
+    pub inner_size: Option<[f32; 2]>,
+
+    /// Minimum window size in pixels [width, height].
+    ///
+    /// Default: `MultiAgentGui::MIN_WINDOW_SIZE_IN_PIXELS`
+    pub min_inner_size: Option<[f32; 2]>,
+
+    /// Start with a maximized window.
+    ///
+    /// Default: true
+    pub maximized: bool,
+
+    /// Start in fullscreen mode.
+    ///
+    /// Default: false
+    pub fullscreen: bool,
+
+    /// Center the window on the screen.
+    ///
+    /// Default: true
+    pub centered: bool,
+}
+
+impl Default for WindowOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner_size: None,
+            min_inner_size: None,
+            maximized: true,
+            fullscreen: false,
+            centered: true,
+        }
+    }
+}
@@ -0,0 +1,450 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{panic, HeadlessRunner};
+use multi_agent_core::{Error, MultiAgentSimulation, Result};
+use std::{
+    fmt::Write as _,
+    io::Write,
+    marker::PhantomData,
+    num::NonZero,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+};
+
+/// Value of a metric taken from the simulation data at a given tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    /// Number of ticks executed when the sample was taken.
+    pub tick: u64,
+    /// Name of the metric.
+    pub metric: String,
+    /// Value of the metric.
+    pub value: f64,
+}
+
+/// Outcome of a single run of a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRun {
+    /// Index of the run in the batch, starting at 0.
+    pub run: usize,
+    /// Master seed the simulation was created with.
+    pub seed: u64,
+    /// Named parameters of the GUI data the simulation was created with.
+    pub parameters: Vec<(String, f64)>,
+    /// Metrics sampled during the run, in tick order.
+    pub samples: Vec<MetricSample>,
+    /// Error or panic that ended the run early, if any.
+    pub error: Option<String>,
+}
+
+/// Outcome of every run of a batch, ordered by run index.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    /// The runs of the batch.
+    pub runs: Vec<BatchRun>,
+}
+
+impl BatchReport {
+    /// Runs that ended with an error or a panic.
+    #[inline]
+    pub fn failures(&self) -> impl Iterator<Item = &BatchRun> {
+        self.runs.iter().filter(|run| run.error.is_some())
+    }
+
+    /// Write the samples as tidy CSV: one row per sample with the columns
+    /// `run`, `seed`, one column per parameter, `tick`, `metric` and `value`.
+    ///
+    /// The parameter columns are named after the parameters of the first run,
+    /// so every configuration should report the same parameters in the same order.
+    ///
+    /// # Errors
+    /// Returns `Error::Batch` if the writer fails.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<()> {
+        let parameters: Vec<&str> = self.runs.first().map_or_else(Vec::new, |run| {
+            run.parameters
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect()
+        });
+
+        let mut header: Vec<String> = vec![String::from("run"), String::from("seed")];
+        header.extend(parameters.iter().map(|name| csv_field(name)));
+        header.extend(["tick", "metric", "value"].map(String::from));
+        writeln!(writer, "{}", header.join(",")).map_err(|err| Error::Batch(err.to_string()))?;
+
+        for run in &self.runs {
+            let values: String =
+                run.parameters
+                    .iter()
+                    .fold(String::new(), |mut values, (_, value)| {
+                        let _ = write!(values, ",{value}");
+                        values
+                    });
+            for sample in &run.samples {
+                writeln!(
+                    writer,
+                    "{},{}{values},{},{},{}",
+                    run.run,
+                    run.seed,
+                    sample.tick,
+                    csv_field(&sample.metric),
+                    sample.value
+                )
+                .map_err(|err| Error::Batch(err.to_string()))?;
+            }
+        }
+        writer.flush().map_err(|err| Error::Batch(err.to_string()))
+    }
+
+    /// Write the samples as JSON Lines: one object per sample with the keys
+    /// `run`, `seed`, `parameters`, `tick`, `metric` and `value`.
+    ///
+    /// # Errors
+    /// Returns `Error::Batch` if the writer fails.
+    #[cfg(feature = "serde")]
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> Result<()> {
+        for run in &self.runs {
+            let parameters: serde_json::Map<String, serde_json::Value> = run
+                .parameters
+                .iter()
+                .map(|(name, value)| (name.clone(), serde_json::Value::from(*value)))
+                .collect();
+            for sample in &run.samples {
+                let line = serde_json::json!({
+                    "run": run.run,
+                    "seed": run.seed,
+                    "parameters": parameters,
+                    "tick": sample.tick,
+                    "metric": sample.metric,
+                    "value": sample.value,
+                });
+                writeln!(writer, "{line}").map_err(|err| Error::Batch(err.to_string()))?;
+            }
+        }
+        writer.flush().map_err(|err| Error::Batch(err.to_string()))
+    }
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+/// Runs many headless instances of a simulation in parallel to sweep parameters.
+///
+/// Every configuration is run once per seed, for the same number of ticks,
+/// on a pool of threads. Metrics are extracted from the simulation data every
+/// [`sample_every`](Self::sample_every) ticks and at the end of each run.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::BatchRunner;
+/// use std::fs::File;
+///
+/// let configurations = (0..=10).map(|step| BoidsConfig {
+///     separation_weight: 0.5 + 0.25 * step as f32,
+///     ..BoidsConfig::default()
+/// });
+///
+/// let report = BatchRunner::<Boids>::new(1_000)
+///     .seeds(0..20)
+///     .sample_every(100)
+///     .run(
+///         configurations,
+///         |config| vec![("separation_weight".into(), config.separation_weight.into())],
+///         |boids| vec![("alignment".into(), alignment(boids))],
+///     );
+///
+/// report.write_csv(File::create("sweep.csv")?)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchRunner<Simulation> {
+    ticks: u64,
+    sample_every: u64,
+    seeds: Vec<u64>,
+    threads: usize,
+    simulation: PhantomData<fn() -> Simulation>,
+}
+
+impl<Simulation> BatchRunner<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    /// Create a batch runner running every instance for `ticks` ticks.
+    ///
+    /// By default, each configuration runs once with seed 0, metrics are only
+    /// sampled at the end of the runs and one thread is used per available core.
+    #[inline]
+    #[must_use]
+    pub fn new(ticks: u64) -> Self {
+        Self {
+            ticks,
+            sample_every: ticks,
+            seeds: vec![0],
+            threads: thread::available_parallelism().map_or(1, NonZero::get),
+            simulation: PhantomData,
+        }
+    }
+
+    /// Run every configuration once per seed.
+    ///
+    /// Default: `[0]`
+    #[inline]
+    #[must_use]
+    pub fn seeds<I: IntoIterator<Item = u64>>(mut self, seeds: I) -> Self {
+        self.seeds = seeds.into_iter().collect();
+        self
+    }
+
+    /// Sample the metrics every `ticks` ticks, in addition to the end of the run.
+    ///
+    /// Default: the length of the runs, so only at the end
+    #[inline]
+    #[must_use]
+    pub fn sample_every(mut self, ticks: u64) -> Self {
+        self.sample_every = ticks.max(1);
+        self
+    }
+
+    /// Run at most `threads` instances at the same time.
+    ///
+    /// Default: the number of available cores
+    #[inline]
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Run every configuration with every seed and collect the sampled metrics.
+    ///
+    /// Runs are numbered by configuration first, then by seed. A run whose
+    /// simulation returns an error or panics stops there: its error is kept in
+    /// the report along with the samples taken before.
+    ///
+    /// # Arguments
+    /// * `configurations` - GUI data of the simulations to run
+    /// * `parameters` - Named parameters of a configuration, written next to its samples
+    /// * `metrics` - Named metrics extracted from the simulation data
+    pub fn run<I, P, M>(&self, configurations: I, parameters: P, metrics: M) -> BatchReport
+    where
+        I: IntoIterator<Item = Simulation::GuiData>,
+        P: Fn(&Simulation::GuiData) -> Vec<(String, f64)>,
+        M: Fn(&Simulation::SimulationData) -> Vec<(String, f64)> + Sync,
+    {
+        let jobs: Vec<(BatchRun, Simulation::GuiData)> = configurations
+            .into_iter()
+            .flat_map(|gui_data| {
+                let parameters: Vec<(String, f64)> = parameters(&gui_data);
+                self.seeds
+                    .iter()
+                    .map(move |seed| (*seed, parameters.clone(), gui_data.clone()))
+            })
+            .enumerate()
+            .map(|(run, (seed, parameters, gui_data))| {
+                let batch_run = BatchRun {
+                    run,
+                    seed,
+                    parameters,
+                    samples: Vec::new(),
+                    error: None,
+                };
+                (batch_run, gui_data)
+            })
+            .collect();
+
+        let next_job = AtomicUsize::new(0);
+        let runs: Mutex<Vec<BatchRun>> = Mutex::new(Vec::with_capacity(jobs.len()));
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(jobs.len()) {
+                scope.spawn(|| {
+                    while let Some((batch_run, gui_data)) =
+                        jobs.get(next_job.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let batch_run: BatchRun =
+                            self.run_one(batch_run.clone(), gui_data.clone(), &metrics);
+                        runs.lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .push(batch_run);
+                    }
+                });
+            }
+        });
+
+        let mut runs: Vec<BatchRun> = runs.into_inner().unwrap_or_else(PoisonError::into_inner);
+        runs.sort_by_key(|run| run.run);
+        BatchReport { runs }
+    }
+
+    /// Run a single simulation and fill in the samples and the error of `batch_run`.
+    fn run_one<M>(
+        &self,
+        mut batch_run: BatchRun,
+        gui_data: Simulation::GuiData,
+        metrics: &M,
+    ) -> BatchRun
+    where
+        M: Fn(&Simulation::SimulationData) -> Vec<(String, f64)>,
+    {
+        let seed: u64 = batch_run.seed;
+        let samples: &mut Vec<MetricSample> = &mut batch_run.samples;
+        let outcome = panic::catch(|| -> Result<()> {
+            let mut runner = HeadlessRunner::<Simulation>::new_seeded(gui_data, seed)?;
+            while runner.tick_count() < self.ticks {
+                let ticks: u64 = self.sample_every.min(self.ticks - runner.tick_count());
+                let report = runner.run_ticks(ticks)?;
+                samples.extend(metrics(&report.data).into_iter().map(|(metric, value)| {
+                    MetricSample {
+                        tick: runner.tick_count(),
+                        metric,
+                        value,
+                    }
+                }));
+            }
+            Ok(())
+        });
+
+        batch_run.error = match outcome {
+            Ok(Ok(())) => None,
+            Ok(Err(error)) => Some(error.to_string()),
+            Err(caught) => Some(format!("panicked: {}", caught.message)),
+        };
+        if let Some(error) = &batch_run.error {
+            log::warn!(
+                "Batch run {} with seed {seed} failed: {error}",
+                batch_run.run
+            );
+        }
+        batch_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::{CancellationToken, SeedFactory};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Walk {
+        position: f64,
+    }
+
+    impl MultiAgentSimulation for Walk {
+        type SimulationData = f64;
+        type GuiData = f64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(_initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
+            #[allow(clippy::cast_precision_loss)]
+            Ok(Self {
+                position: seeds.master() as f64,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            assert!(gui_data >= 0.0, "negative speed");
+            self.position += gui_data;
+            Ok(&self.position)
+        }
+    }
+
+    fn sweep(speeds: Vec<f64>) -> BatchReport {
+        BatchRunner::<Walk>::new(10)
+            .seeds([0, 100])
+            .sample_every(4)
+            .threads(3)
+            .run(
+                speeds,
+                |speed| vec![(String::from("speed"), *speed)],
+                |position| vec![(String::from("position"), *position)],
+            )
+    }
+
+    #[test]
+    fn test_batch_runs_every_configuration_and_seed() {
+        let report = sweep(vec![1.0, 2.0]);
+
+        assert_eq!(report.runs.len(), 4);
+        assert_eq!(report.failures().count(), 0);
+
+        let run = &report.runs[3];
+        assert_eq!((run.run, run.seed), (3, 100));
+        assert_eq!(run.parameters, vec![(String::from("speed"), 2.0)]);
+        let ticks: Vec<u64> = run.samples.iter().map(|sample| sample.tick).collect();
+        assert_eq!(ticks, vec![4, 8, 10]);
+        assert!((run.samples[2].value - 120.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_batch_keeps_failed_runs() {
+        let report = sweep(vec![-1.0, 1.0]);
+
+        assert_eq!(report.failures().count(), 2);
+        assert!(report.runs[0].samples.is_empty());
+        assert!(report.runs[2].error.is_none());
+    }
+
+    #[test]
+    fn test_batch_write_csv() {
+        let report = sweep(vec![1.0]);
+        let mut csv: Vec<u8> = Vec::new();
+
+        report.write_csv(&mut csv).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "run,seed,speed,tick,metric,value");
+        assert_eq!(lines[1], "0,0,1,4,position,4");
+        assert_eq!(lines[6], "1,100,1,10,position,110");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_batch_write_jsonl() {
+        let report = sweep(vec![1.0]);
+        let mut jsonl: Vec<u8> = Vec::new();
+
+        report.write_jsonl(&mut jsonl).unwrap();
+
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+        assert_eq!(first["parameters"]["speed"], 1.0);
+        assert_eq!(first["tick"], 4);
+        assert_eq!(jsonl.lines().count(), 6);
+    }
+}
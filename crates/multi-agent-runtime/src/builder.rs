@@ -0,0 +1,1100 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use crate::session::SessionFiles;
+#[cfg(feature = "scripting")]
+use crate::Scenario;
+use crate::{
+    driver::SimulationDriver, HeadlessReport, HeadlessRunner, PanicPolicy, Publication, Recorder,
+    Recording, Schedule,
+};
+#[cfg(feature = "http")]
+use crate::{HttpObserver, HttpServer};
+#[cfg(feature = "remote")]
+use crate::{DeltaCodec, RemoteAddress, RemoteClient, RemoteServer};
+use multi_agent_core::{Error, MultiAgentGui, MultiAgentSimulation, Result, SeedFactory};
+use multi_agent_gui::{AppGui, WindowOptions};
+#[cfg(feature = "remote")]
+use multi_agent_sync::DeltaFeed;
+use multi_agent_sync::{
+    message::{
+        self, ChannelPolicy, Coalesce, Coalescing, MessageChannel, MessageReceiver, MessageSender,
+    },
+    DiffBuffer, Diffable, Publisher, RuntimeStats, Shared, SimulationControl, Timeline,
+};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "scripting")]
+use std::path::PathBuf;
+use std::{
+    marker::PhantomData,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Configurable launcher for a multi-agent application.
+///
+/// Obtained from `AppLauncher::builder()`. Every setting has a default, so
+/// only the values that differ need to be set before calling [`run`].
+///
+/// The `GuiData` and `MessageFromGui` type parameters are inferred from the
+/// simulation given to [`run`].
+///
+/// [`run`]: RuntimeBuilder::run
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::{gui::WindowOptions, AppLauncher};
+/// use std::time::Duration;
+///
+/// fn main() -> multi_agent::Result<()> {
+///     AppLauncher::builder()
+///         .gui_to_simulation_capacity(1_000)
+///         .shutdown_timeout(Duration::from_secs(1))
+///         .frequency_in_hz(120)
+///         .window(WindowOptions {
+///             maximized: false,
+///             ..WindowOptions::default()
+///         })
+///         .run::<MySimulation, MyGui>()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuntimeBuilder<GuiData, MessageFromGui = (), SimulationData = (), MessageToGui = ()> {
+    simulation_to_gui_capacity: usize,
+    gui_to_simulation_capacity: usize,
+    simulation_to_gui_policy: ChannelPolicy<MessageToGui>,
+    gui_to_simulation_policy: ChannelPolicy<MessageFromGui>,
+    coalesce: Option<Coalescing<MessageFromGui>>,
+    shutdown_timeout: Duration,
+    frequency_in_hz: Option<u64>,
+    schedule: Schedule,
+    panic_policy: PanicPolicy,
+    publication: Publication,
+    diff_publication: Option<(NewPublisher<SimulationData>, u64)>,
+    #[cfg(feature = "remote")]
+    delta_codec: Option<DeltaCodec<SimulationData>>,
+    timeline_capacity: usize,
+    initial_gui_data: Option<GuiData>,
+    recorder: Option<Recorder<GuiData, MessageFromGui>>,
+    replay: Option<Recording<GuiData, MessageFromGui>>,
+    window: WindowOptions,
+    control: SimulationControl,
+    stats: Shared<RuntimeStats>,
+    #[cfg(feature = "http")]
+    observer: Option<HttpObserver<SimulationData, GuiData, MessageFromGui>>,
+    #[cfg(feature = "scripting")]
+    scenario: Option<Scenario<SimulationData, GuiData, MessageFromGui>>,
+    #[cfg(feature = "serde")]
+    sessions: Option<SessionFiles<GuiData, SimulationData>>,
+    simulation_data: PhantomData<fn() -> SimulationData>,
+}
+
+impl<GuiData, MessageFromGui, SimulationData, MessageToGui> Default
+    for RuntimeBuilder<GuiData, MessageFromGui, SimulationData, MessageToGui>
+{
+    #[inline]
+    fn default() -> Self {
+        Self {
+            simulation_to_gui_capacity: Self::DEFAULT_CHANNEL_CAPACITY,
+            gui_to_simulation_capacity: Self::DEFAULT_CHANNEL_CAPACITY,
+            simulation_to_gui_policy: ChannelPolicy::DropNewest,
+            gui_to_simulation_policy: ChannelPolicy::DropNewest,
+            coalesce: None,
+            shutdown_timeout: Self::DEFAULT_SHUTDOWN_TIMEOUT,
+            frequency_in_hz: None,
+            schedule: Schedule::Variable,
+            panic_policy: PanicPolicy::Stop,
+            publication: Publication::Allocate,
+            diff_publication: None,
+            #[cfg(feature = "remote")]
+            delta_codec: None,
+            timeline_capacity: 0,
+            initial_gui_data: None,
+            recorder: None,
+            replay: None,
+            window: WindowOptions::default(),
+            control: SimulationControl::new(),
+            stats: Shared::new(RuntimeStats::default()),
+            #[cfg(feature = "http")]
+            observer: None,
+            #[cfg(feature = "scripting")]
+            scenario: None,
+            #[cfg(feature = "serde")]
+            sessions: None,
+            simulation_data: PhantomData,
+        }
+    }
+}
+
+impl<GuiData, MessageFromGui, SimulationData, MessageToGui>
+    RuntimeBuilder<GuiData, MessageFromGui, SimulationData, MessageToGui>
+{
+    /// Default capacity of the message channels in both directions.
+    pub const DEFAULT_CHANNEL_CAPACITY: usize = 100;
+
+    /// Default time given to the simulation thread to stop once the window is closed.
+    pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Create a builder with the default settings.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the capacity of the message channels in both directions.
+    ///
+    /// Default: 100
+    #[inline]
+    #[must_use]
+    pub const fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.simulation_to_gui_capacity = capacity;
+        self.gui_to_simulation_capacity = capacity;
+        self
+    }
+
+    /// Set the capacity of the channel carrying messages from the simulation to the GUI.
+    ///
+    /// Default: 100
+    #[inline]
+    #[must_use]
+    pub const fn simulation_to_gui_capacity(mut self, capacity: usize) -> Self {
+        self.simulation_to_gui_capacity = capacity;
+        self
+    }
+
+    /// Set the capacity of the channel carrying messages from the GUI to the simulation.
+    ///
+    /// Default: 100
+    #[inline]
+    #[must_use]
+    pub const fn gui_to_simulation_capacity(mut self, capacity: usize) -> Self {
+        self.gui_to_simulation_capacity = capacity;
+        self
+    }
+
+    /// Set what the channel carrying messages from the simulation to the GUI does
+    /// when it is full.
+    ///
+    /// Default: [`ChannelPolicy::DropNewest`]
+    #[inline]
+    #[must_use]
+    pub const fn simulation_to_gui_policy(mut self, policy: ChannelPolicy<MessageToGui>) -> Self {
+        self.simulation_to_gui_policy = policy;
+        self
+    }
+
+    /// Set what the channel carrying messages from the GUI to the simulation does
+    /// when it is full.
+    ///
+    /// Default: [`ChannelPolicy::DropNewest`]
+    #[inline]
+    #[must_use]
+    pub const fn gui_to_simulation_policy(mut self, policy: ChannelPolicy<MessageFromGui>) -> Self {
+        self.gui_to_simulation_policy = policy;
+        self
+    }
+
+    /// Deliver to every tick only the latest message from the GUI per key, while
+    /// messages without a key are all delivered in order.
+    ///
+    /// Uses the same keys as [`ChannelPolicy::coalesce_by_key`], but superseded
+    /// messages still take room in the channel until the simulation drains it.
+    ///
+    /// Default: disabled
+    #[inline]
+    #[must_use]
+    pub fn coalesce_messages(mut self) -> Self
+    where
+        MessageFromGui: Coalesce,
+    {
+        self.coalesce = Some(message::coalesce);
+        self
+    }
+
+    /// Set how long the simulation thread may take to stop once the window is closed.
+    ///
+    /// Default: 5 seconds
+    #[inline]
+    #[must_use]
+    pub const fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Override the update frequency of the simulation.
+    ///
+    /// Only used by [`Schedule::Variable`]: a fixed schedule runs at `1 / delta_time`.
+    ///
+    /// Default: `MultiAgentSimulation::FREQUENCY_IN_HZ`
+    #[inline]
+    #[must_use]
+    pub const fn frequency_in_hz(mut self, frequency: u64) -> Self {
+        self.frequency_in_hz = Some(frequency);
+        self
+    }
+
+    /// Set how the simulation thread paces the calls to `update()`.
+    ///
+    /// Default: [`Schedule::Variable`]
+    #[inline]
+    #[must_use]
+    pub const fn schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Set what the simulation thread does when the simulation panics during a tick.
+    ///
+    /// Default: [`PanicPolicy::Stop`]
+    #[inline]
+    #[must_use]
+    pub const fn panic_policy(mut self, panic_policy: PanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
+    }
+
+    /// Set how the simulation thread publishes the data of every tick to the GUI.
+    ///
+    /// Default: [`Publication::Allocate`]
+    #[inline]
+    #[must_use]
+    pub const fn publication(mut self, publication: Publication) -> Self {
+        self.publication = publication;
+        self
+    }
+
+    /// Publish the data of every tick as the changes since the previous tick,
+    /// applied to recycled buffers, with a full copy every `keyframe_interval` ticks.
+    ///
+    /// Cheaper than [`Publication::TripleBuffer`] when `SimulationData` is large
+    /// and changes little from one tick to the next. Overrides
+    /// [`publication`](Self::publication).
+    ///
+    /// The timeline keeps the changes between frames rather than every frame, and
+    /// remote GUIs get them too with `delta_streaming`. Sessions still save the
+    /// whole data.
+    ///
+    /// Default: disabled
+    #[inline]
+    #[must_use]
+    pub fn diff_publication(mut self, keyframe_interval: u64) -> Self
+    where
+        SimulationData: Diffable + Send + Sync + 'static,
+    {
+        self.diff_publication = Some((
+            |simulation_data, keyframe_interval| {
+                Box::new(DiffBuffer::new(simulation_data).with_keyframe_interval(keyframe_interval))
+            },
+            keyframe_interval,
+        ));
+        self
+    }
+
+    /// Send the changes of the data of every tick to remote GUIs, rather than the
+    /// whole data, and rebuild it from them on the GUI side.
+    ///
+    /// Set it on both the [`serve`](Self::serve) and the [`connect`](Self::connect)
+    /// side; a side without it falls back to sending or receiving the whole data.
+    /// Enables [`diff_publication`](Self::diff_publication) with its default
+    /// keyframe interval if not set.
+    ///
+    /// Default: disabled
+    #[cfg(feature = "remote")]
+    #[inline]
+    #[must_use]
+    pub fn delta_streaming(mut self) -> Self
+    where
+        SimulationData: Diffable + Send + Sync + 'static,
+        SimulationData::Delta: Serialize + DeserializeOwned,
+    {
+        self.delta_codec = Some(DeltaCodec::new());
+        if self.diff_publication.is_none() {
+            self = self.diff_publication(DiffBuffer::<SimulationData>::DEFAULT_KEYFRAME_INTERVAL);
+        }
+        self
+    }
+
+    /// Keep the data published by the last `frames` ticks to scrub through them in the GUI.
+    ///
+    /// The frames share the snapshots already published to the GUI, but each of them
+    /// keeps its snapshot alive, so memory grows with the size of `SimulationData`.
+    /// Resuming from a frame requires `MultiAgentSimulation::restore`.
+    ///
+    /// Default: 0 (disabled)
+    #[inline]
+    #[must_use]
+    pub const fn timeline_capacity(mut self, frames: usize) -> Self {
+        self.timeline_capacity = frames;
+        self
+    }
+
+    /// Set the GUI data used to create the simulation and shared with it at startup.
+    ///
+    /// Default: `GuiData::default()`
+    #[inline]
+    #[must_use]
+    pub fn initial_gui_data(mut self, gui_data: GuiData) -> Self {
+        self.initial_gui_data = Some(gui_data);
+        self
+    }
+
+    /// Set the master seed handed to the simulation when it is created or restarted.
+    ///
+    /// The seed can also be changed from the toolbar of the window before a restart.
+    ///
+    /// Default: a random seed
+    #[inline]
+    #[must_use]
+    pub fn seed(self, seed: u64) -> Self {
+        self.control.set_seed(seed);
+        self
+    }
+
+    /// Record the inputs of the simulation with `recorder`.
+    ///
+    /// Keep a clone of the recorder to read the recording back once the window is closed.
+    ///
+    /// Default: no recording
+    #[inline]
+    #[must_use]
+    pub fn recorder(mut self, recorder: Recorder<GuiData, MessageFromGui>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Replay a recording: the simulation is created with its seed and initial GUI data
+    /// and takes the inputs of its first ticks from it instead of the GUI.
+    ///
+    /// Use a [`Schedule::Fixed`] with the recorded time step, or rely on the
+    /// recorded time steps, which take precedence over the schedule while replaying.
+    ///
+    /// Default: no replay
+    #[inline]
+    #[must_use]
+    pub fn replay(mut self, recording: Recording<GuiData, MessageFromGui>) -> Self {
+        self.replay = Some(recording);
+        self
+    }
+
+    /// Set the options of the native window.
+    ///
+    /// Default: `WindowOptions::default()`
+    #[inline]
+    #[must_use]
+    pub const fn window(mut self, options: WindowOptions) -> Self {
+        self.window = options;
+        self
+    }
+
+    /// Open the window in fullscreen mode, keeping the other window options.
+    ///
+    /// Default: false
+    #[inline]
+    #[must_use]
+    pub const fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.window.fullscreen = fullscreen;
+        self
+    }
+
+    /// Transport controls of the simulation, to pause, step, speed up or restart it
+    /// from another thread.
+    ///
+    /// The returned handle shares its state with the toolbar of the window.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let launcher = AppLauncher::builder();
+    /// let control = launcher.control();
+    /// control.set_time_scale(0.5);
+    ///
+    /// launcher.run::<MySimulation, MyGui>()
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn control(&self) -> SimulationControl {
+        self.control.clone()
+    }
+
+    /// Statistics published by the simulation thread after every tick.
+    ///
+    /// The same statistics are drawn by the performance overlay of the window,
+    /// toggled with `F3` or from the toolbar.
+    #[inline]
+    #[must_use]
+    pub fn stats(&self) -> Shared<RuntimeStats> {
+        self.stats.clone()
+    }
+
+    /// Serve the statistics, the GUI data and the metrics of `observer` over HTTP
+    /// on localhost while the application runs, and accept messages posted to it.
+    ///
+    /// Default: no endpoint
+    #[cfg(feature = "http")]
+    #[inline]
+    #[must_use]
+    pub fn observe(
+        mut self,
+        observer: HttpObserver<SimulationData, GuiData, MessageFromGui>,
+    ) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Run the scripts of `scenario` before every tick, and let the toolbar load
+    /// and unload them.
+    ///
+    /// Default: no scripting
+    #[cfg(feature = "scripting")]
+    #[inline]
+    #[must_use]
+    pub fn scenario(mut self, scenario: Scenario<SimulationData, GuiData, MessageFromGui>) -> Self {
+        self.scenario = Some(scenario);
+        self
+    }
+
+    /// Load the scenario script stored at `path` when the simulation starts, with
+    /// the scenario given to [`scenario`](Self::scenario) or one without metrics.
+    ///
+    /// Default: no script
+    #[cfg(feature = "scripting")]
+    #[inline]
+    #[must_use]
+    pub fn script_file<P: Into<PathBuf>>(mut self, path: P) -> Self
+    where
+        GuiData: Serialize + DeserializeOwned,
+        MessageFromGui: DeserializeOwned,
+    {
+        self.scenario = Some(self.scenario.take().unwrap_or_default().file(path));
+        self
+    }
+
+    /// Let the toolbar save the running simulation to a session file and open one,
+    /// to share an interesting state as a file.
+    ///
+    /// Sessions are JSON files holding the GUI data, the data published by the
+    /// last tick, the tick count and the master seed. Opening one recreates the
+    /// simulation with `MultiAgentSimulation::restore_session`.
+    ///
+    /// Default: no sessions
+    #[cfg(feature = "serde")]
+    #[inline]
+    #[must_use]
+    pub fn sessions(mut self) -> Self
+    where
+        GuiData: Serialize + DeserializeOwned,
+        SimulationData: Serialize + DeserializeOwned,
+    {
+        self.sessions = Some(SessionFiles::json());
+        self
+    }
+
+    /// Run the multi-agent application with the given simulation and GUI implementations.
+    ///
+    /// This method:
+    /// 1. Creates shared state for simulation and GUI data
+    /// 2. Sets up bidirectional message channels
+    /// 3. Spawns the simulation thread
+    /// 4. Runs the GUI on the main thread
+    /// 5. Performs graceful shutdown when the GUI closes
+    ///
+    /// # Type Parameters
+    /// * `Simulation` - Your simulation implementation
+    /// * `Gui` - Your GUI implementation
+    ///
+    /// The types must be compatible (same data and message types).
+    ///
+    /// # Returns
+    /// `Ok(())` if the application runs and closes successfully.
+    ///
+    /// # Errors
+    /// - `Err(Error::Config)` if the schedule has a zero period
+    /// - `Err(Error::SimulationFailed)` if the simulation returned an error and was not restarted
+    /// - `Err(Error::SimulationPanic)` if the simulation thread panics outside of a tick
+    /// - `Err(Error::ShutdownTimeout)` if the simulation thread doesn't stop within the shutdown timeout
+    /// - `Err(Error::Gui)` if the GUI framework returns an error
+    pub fn run<Simulation, Gui>(mut self) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        Gui: MultiAgentGui<
+            GuiData = Simulation::GuiData,
+            SimulationData = Simulation::SimulationData,
+            MessageFromSimulation = Simulation::MessageToGui,
+            MessageToSimulation = Simulation::MessageFromGui,
+        >,
+        SimulationData: Default + Clone + Sync + Send + 'static,
+        GuiData: Default + Clone + Sync + Send + 'static,
+        MessageFromGui: Clone + Send + 'static,
+    {
+        let Wiring {
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            ..
+        } = self.wire::<Simulation>()?;
+        let mut driver: SimulationDriver<Simulation> = driver;
+        #[cfg(feature = "http")]
+        let _observer: Option<HttpServer> =
+            self.start_observer(&simulation_data, &gui_data, &sender)?;
+
+        let mut gui: AppGui<Gui> = AppGui::new(
+            sender,
+            receiver,
+            gui_data,
+            simulation_data,
+            self.control.clone(),
+        )
+        .with_stats(self.stats.clone());
+
+        if self.timeline_capacity > 0 {
+            let timeline = Timeline::new(self.timeline_capacity);
+            gui = gui.with_timeline(timeline.clone());
+            driver = driver.with_timeline(timeline);
+        }
+
+        let window: WindowOptions = self.window.clone();
+        self.drive::<Simulation, _>(driver, move || gui.run(&window))
+    }
+
+    /// Run `ticks` ticks of the simulation without any window, back to back.
+    ///
+    /// The simulation is wired like in [`run`](Self::run), with the seed, initial GUI
+    /// data, recorder, replay and statistics of the builder. Every tick is given
+    /// the time step of the schedule: `1 / frequency` for [`Schedule::Variable`].
+    ///
+    /// # Errors
+    /// Returns `Error::Config` if the schedule has a zero period, the error
+    /// produced by `MultiAgentSimulation::new` or the first error produced by
+    /// `MultiAgentSimulation::update`.
+    pub fn run_headless<Simulation>(mut self, ticks: u64) -> Result<HeadlessReport<SimulationData>>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        SimulationData: Default + Clone + Sync + Send + 'static,
+        GuiData: Default + Clone + Sync + Send + 'static,
+        MessageFromGui: Clone + Send + 'static,
+    {
+        let Wiring {
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            ..
+        } = self.wire::<Simulation>()?;
+        #[cfg(feature = "http")]
+        let _observer: Option<HttpServer> =
+            self.start_observer(&simulation_data, &gui_data, &sender)?;
+
+        let time_step: Duration = self.period::<Simulation>()?;
+        HeadlessRunner::from_parts(
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            self.stats.clone(),
+        )
+        .with_time_step(time_step)
+        .run_ticks(ticks)
+    }
+
+    /// Run the simulation and serve it on `server` to a GUI running in another process,
+    /// possibly on another machine, instead of opening a window.
+    ///
+    /// GUIs started with [`connect`](Self::connect) on the address of the server
+    /// can connect, disconnect and reconnect while the simulation keeps running.
+    /// Serving stops once the [`control`](Self::control) of the builder is stopped.
+    ///
+    /// # Errors
+    /// - `Err(Error::Config)` if the schedule has a zero period
+    /// - `Err(Error::SimulationFailed)` if the simulation returned an error and was not restarted
+    /// - `Err(Error::SimulationPanic)` if the simulation thread panics outside of a tick
+    /// - `Err(Error::ShutdownTimeout)` if the simulation thread doesn't stop within the shutdown timeout
+    /// - `Err(Error::Remote)` if the server stops accepting connections
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use multi_agent::{AppLauncher, RemoteServer};
+    ///
+    /// let server = RemoteServer::bind(&"0.0.0.0:7878".parse()?)?;
+    /// AppLauncher::builder().serve::<MySimulation>(&server)
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn serve<Simulation>(mut self, server: &RemoteServer) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        Simulation::MessageToGui: Serialize,
+        SimulationData: Default + Clone + Serialize + Sync + Send + 'static,
+        GuiData: Default + Clone + DeserializeOwned + Sync + Send + 'static,
+        MessageFromGui: Clone + DeserializeOwned + Send + 'static,
+    {
+        let Wiring {
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            feed,
+        } = self.wire::<Simulation>()?;
+        #[cfg(feature = "http")]
+        let _observer: Option<HttpServer> =
+            self.start_observer(&simulation_data, &gui_data, &sender)?;
+
+        let deltas = feed.zip(self.delta_codec);
+        let control: SimulationControl = self.control.clone();
+        self.drive::<Simulation, _>(driver, move || {
+            server.serve::<Simulation>(
+                &simulation_data,
+                &gui_data,
+                &sender,
+                &receiver,
+                &control,
+                deltas.as_ref(),
+            )
+        })
+    }
+
+    /// Open the window of `Gui` and connect it to a simulation served with
+    /// [`serve`](Self::serve) on `address`.
+    ///
+    /// Only the window options of the builder apply: the other settings belong
+    /// to the serving side. The connection is closed with the window.
+    ///
+    /// # Errors
+    /// - `Err(Error::Remote)` if the server cannot be reached
+    /// - `Err(Error::Gui)` if the GUI framework returns an error
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use multi_agent::AppLauncher;
+    ///
+    /// AppLauncher::builder().connect::<MySimulation, MyGui>(&"192.168.1.20:7878".parse()?)
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn connect<Simulation, Gui>(self, address: &RemoteAddress) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        Gui: MultiAgentGui<
+            GuiData = Simulation::GuiData,
+            SimulationData = Simulation::SimulationData,
+            MessageFromSimulation = Simulation::MessageToGui,
+            MessageToSimulation = Simulation::MessageFromGui,
+        >,
+        Simulation::MessageToGui: DeserializeOwned,
+        SimulationData: Default + Clone + DeserializeOwned,
+        GuiData: Clone + Serialize,
+        MessageFromGui: Clone + Serialize,
+    {
+        let client = match self.delta_codec {
+            Some(codec) => RemoteClient::<Simulation>::connect_with_deltas(address, codec)?,
+            None => RemoteClient::<Simulation>::connect(address)?,
+        };
+        let gui: AppGui<Gui> = AppGui::new(
+            client.sender().clone(),
+            client.receiver().clone(),
+            client.gui_data().clone(),
+            client.simulation_data().clone(),
+            client.control().clone(),
+        );
+        gui.run(&self.window)
+    }
+
+    /// Take the initial GUI data given to the builder, if any.
+    #[cfg(feature = "cli")]
+    pub(crate) const fn take_initial_gui_data(&mut self) -> Option<GuiData> {
+        self.initial_gui_data.take()
+    }
+
+    /// Time between two wake-ups of the simulation thread with the schedule and
+    /// frequency of the builder.
+    ///
+    /// A zero period would make the simulation thread spin without ever sleeping,
+    /// so it is rejected with `Error::Config`.
+    fn period<Simulation: MultiAgentSimulation>(&self) -> Result<Duration> {
+        let period: Duration = self
+            .schedule
+            .period(self.frequency_in_hz.unwrap_or(Simulation::FREQUENCY_IN_HZ));
+        if period.is_zero() {
+            return Err(Error::Config(format!(
+                "The schedule {:?} has a zero period",
+                self.schedule
+            )));
+        }
+        Ok(period)
+    }
+
+    /// Create the simulation and wire it to fresh shared state and message channels, with
+    /// the seed, recorder, replay, panic policy, statistics, scenario and sessions of the builder.
+    fn wire<Simulation>(&mut self) -> Result<Wiring<Simulation>>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        SimulationData: Default + Clone,
+        GuiData: Default + Clone,
+    {
+        self.period::<Simulation>()?;
+
+        let (initial_gui_data, replay) = match self.replay.take() {
+            Some(recording) => {
+                self.control.set_seed(recording.seed);
+                (recording.initial_gui_data, Some(recording.ticks))
+            }
+            None => (self.initial_gui_data.take().unwrap_or_default(), None),
+        };
+
+        let simulation_data = Shared::new(Simulation::SimulationData::default());
+        let gui_data = Shared::new(initial_gui_data.clone());
+
+        let (sim_sender, receiver) = MessageChannel::with_policy(
+            self.simulation_to_gui_capacity,
+            self.simulation_to_gui_policy,
+        )
+        .split();
+        let (sender, sim_receiver) = MessageChannel::with_policy(
+            self.gui_to_simulation_capacity,
+            self.gui_to_simulation_policy,
+        )
+        .split();
+
+        let seeds: SeedFactory = self.control.seeds();
+        let mut driver = SimulationDriver::new(
+            Simulation::new(initial_gui_data, seeds)?,
+            seeds,
+            simulation_data.clone(),
+            gui_data.clone(),
+            sim_receiver,
+            sim_sender,
+        )
+        .with_panic_policy(self.panic_policy)
+        .with_publication(self.publication)
+        .with_stats(self.stats.clone());
+
+        if let Some(recorder) = self.recorder.take() {
+            driver = driver.with_recorder(recorder);
+        }
+        if let Some(ticks) = replay {
+            driver = driver.with_replay(ticks);
+        }
+        #[cfg(feature = "scripting")]
+        if let Some(scenario) = self.scenario.take() {
+            driver = driver.with_scenario(scenario);
+        }
+        #[cfg(feature = "serde")]
+        if let Some(sessions) = self.sessions.take() {
+            driver = driver.with_sessions(sessions);
+        }
+        if let Some(coalesce) = self.coalesce {
+            driver = driver.with_coalescing(coalesce);
+        }
+        #[cfg(feature = "remote")]
+        let mut feed: Option<DeltaFeed<SimulationData>> = None;
+        if let Some((new_publisher, keyframe_interval)) = self.diff_publication {
+            let publisher = new_publisher(simulation_data.clone(), keyframe_interval);
+            #[cfg(feature = "remote")]
+            {
+                feed = publisher.feed();
+            }
+            driver = driver.with_publisher(publisher);
+        }
+
+        Ok(Wiring {
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            #[cfg(feature = "remote")]
+            feed,
+        })
+    }
+
+    /// Start the observation endpoint, if any, on the simulation side of the shared state.
+    #[cfg(feature = "http")]
+    fn start_observer(
+        &mut self,
+        simulation_data: &Shared<SimulationData>,
+        gui_data: &Shared<GuiData>,
+        sender: &MessageSender<MessageFromGui>,
+    ) -> Result<Option<HttpServer>>
+    where
+        SimulationData: Clone + Send + Sync + 'static,
+        GuiData: Clone + Send + Sync + 'static,
+        MessageFromGui: Clone + Send + 'static,
+    {
+        self.observer
+            .take()
+            .map(|observer| {
+                observer.start(
+                    simulation_data.clone(),
+                    gui_data.clone(),
+                    sender.clone(),
+                    self.stats.clone(),
+                )
+            })
+            .transpose()
+    }
+
+    /// Run `driver` on a new thread and `front` on the current one, then stop the
+    /// simulation and wait for its thread within the shutdown timeout.
+    fn drive<Simulation, F>(self, mut driver: SimulationDriver<Simulation>, front: F) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation,
+        F: FnOnce() -> Result<()>,
+    {
+        let schedule: Schedule = self.schedule;
+        let period: Duration = self.period::<Simulation>()?;
+
+        let control: SimulationControl = self.control.clone();
+        let simulation_thread = thread::spawn(move || driver.run(schedule, period, &control));
+
+        let front_result: Result<()> = front();
+        self.control.stop();
+        front_result?;
+
+        let timeout: Duration = self.shutdown_timeout;
+        let start = Instant::now();
+        loop {
+            if simulation_thread.is_finished() {
+                return simulation_thread
+                    .join()
+                    .map_err(|e| Error::SimulationPanic(format!("{e:?}")))?;
+            }
+            if start.elapsed() >= timeout {
+                return Err(Error::ShutdownTimeout { timeout });
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Create the publisher of a simulation from its shared data and keyframe interval.
+type NewPublisher<SimulationData> =
+    fn(Shared<SimulationData>, u64) -> Box<dyn Publisher<SimulationData>>;
+
+/// Simulation side of an application, with the shared state and channel ends left to the GUI.
+struct Wiring<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    driver: SimulationDriver<Simulation>,
+    simulation_data: Shared<Simulation::SimulationData>,
+    gui_data: Shared<Simulation::GuiData>,
+    sender: MessageSender<Simulation::MessageFromGui>,
+    receiver: MessageReceiver<Simulation::MessageToGui>,
+    #[cfg(feature = "remote")]
+    feed: Option<DeltaFeed<Simulation::SimulationData>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CatchUpPolicy;
+    use multi_agent_core::CancellationToken;
+
+    #[test]
+    fn test_builder_defaults() {
+        let builder: RuntimeBuilder<u32> = RuntimeBuilder::new();
+
+        assert_eq!(builder.simulation_to_gui_capacity, 100);
+        assert_eq!(builder.gui_to_simulation_capacity, 100);
+        assert!(matches!(
+            builder.simulation_to_gui_policy,
+            ChannelPolicy::DropNewest
+        ));
+        assert!(matches!(
+            builder.gui_to_simulation_policy,
+            ChannelPolicy::DropNewest
+        ));
+        assert_eq!(builder.shutdown_timeout, Duration::from_secs(5));
+        assert_eq!(builder.frequency_in_hz, None);
+        assert_eq!(builder.schedule, Schedule::Variable);
+        assert_eq!(builder.panic_policy, PanicPolicy::Stop);
+        assert_eq!(builder.publication, Publication::Allocate);
+        assert!(builder.coalesce.is_none());
+        assert!(builder.diff_publication.is_none());
+        assert_eq!(builder.timeline_capacity, 0);
+        assert_eq!(builder.initial_gui_data, None);
+        assert!(builder.recorder.is_none());
+        assert_eq!(builder.replay, None);
+        assert_eq!(builder.window, WindowOptions::default());
+    }
+
+    #[test]
+    fn test_builder_setters() {
+        let builder: RuntimeBuilder<u32> = RuntimeBuilder::new()
+            .channel_capacity(10)
+            .gui_to_simulation_capacity(20)
+            .simulation_to_gui_policy(ChannelPolicy::Unbounded)
+            .gui_to_simulation_policy(ChannelPolicy::DropOldest)
+            .shutdown_timeout(Duration::from_millis(500))
+            .frequency_in_hz(60)
+            .schedule(Schedule::fixed(50))
+            .panic_policy(PanicPolicy::Rollback)
+            .publication(Publication::TripleBuffer)
+            .timeline_capacity(600)
+            .seed(42)
+            .initial_gui_data(7);
+
+        assert_eq!(builder.simulation_to_gui_capacity, 10);
+        assert_eq!(builder.gui_to_simulation_capacity, 20);
+        assert!(matches!(
+            builder.simulation_to_gui_policy,
+            ChannelPolicy::Unbounded
+        ));
+        assert!(matches!(
+            builder.gui_to_simulation_policy,
+            ChannelPolicy::DropOldest
+        ));
+        assert_eq!(builder.shutdown_timeout, Duration::from_millis(500));
+        assert_eq!(builder.frequency_in_hz, Some(60));
+        assert_eq!(builder.schedule, Schedule::fixed(50));
+        assert_eq!(builder.panic_policy, PanicPolicy::Rollback);
+        assert_eq!(builder.publication, Publication::TripleBuffer);
+        assert_eq!(builder.timeline_capacity, 600);
+        assert_eq!(builder.control.seed(), 42);
+        assert_eq!(builder.initial_gui_data, Some(7));
+    }
+
+    #[test]
+    fn test_builder_diff_publication() {
+        #[derive(Debug, Clone)]
+        struct Counter(u64);
+
+        impl Diffable for Counter {
+            type Delta = u64;
+
+            fn diff(&self, previous: &Self) -> Self::Delta {
+                self.0 - previous.0
+            }
+
+            fn apply(&mut self, delta: &Self::Delta) {
+                self.0 += delta;
+            }
+        }
+
+        let builder: RuntimeBuilder<u32, (), Counter> = RuntimeBuilder::new().diff_publication(60);
+
+        assert!(matches!(builder.diff_publication, Some((_, 60))));
+    }
+
+    #[test]
+    fn test_builder_coalesce_messages() {
+        #[derive(Debug, PartialEq, Eq)]
+        enum Message {
+            Speed(u32),
+            Click,
+        }
+
+        impl Coalesce for Message {
+            type Key = ();
+
+            fn coalescing_key(&self) -> Option<Self::Key> {
+                matches!(self, Self::Speed(_)).then_some(())
+            }
+        }
+
+        let builder: RuntimeBuilder<u32, Message> = RuntimeBuilder::new().coalesce_messages();
+        let coalesce = builder.coalesce.unwrap();
+
+        assert_eq!(
+            coalesce(vec![Message::Speed(1), Message::Click, Message::Speed(2)]),
+            vec![Message::Click, Message::Speed(2)]
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_zero_period() {
+        #[derive(Debug)]
+        struct Idle;
+
+        impl MultiAgentSimulation for Idle {
+            type SimulationData = ();
+            type GuiData = u32;
+            type MessageFromGui = ();
+            type MessageToGui = ();
+
+            fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+                Ok(Self)
+            }
+
+            fn update<F>(
+                &mut self,
+                _gui_data: Self::GuiData,
+                _messages: Vec<Self::MessageFromGui>,
+                _delta_time: Duration,
+                _send_message_to_gui: F,
+                _cancellation: &CancellationToken,
+            ) -> Result<&Self::SimulationData>
+            where
+                F: Fn(Self::MessageToGui),
+            {
+                Ok(&())
+            }
+        }
+
+        let fixed: Schedule = Schedule::Fixed {
+            delta_time: Duration::ZERO,
+            max_catch_up_steps: 1,
+            when_late: CatchUpPolicy::DropTime,
+        };
+
+        assert!(matches!(
+            RuntimeBuilder::<u32>::new()
+                .schedule(fixed)
+                .run_headless::<Idle>(1),
+            Err(Error::Config(_))
+        ));
+        assert!(matches!(
+            RuntimeBuilder::<u32>::new()
+                .frequency_in_hz(u64::MAX)
+                .run_headless::<Idle>(1),
+            Err(Error::Config(_))
+        ));
+        assert!(RuntimeBuilder::<u32>::new()
+            .frequency_in_hz(0)
+            .run_headless::<Idle>(1)
+            .is_ok());
+    }
+}
@@ -0,0 +1,410 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{Recorder, Recording, RuntimeBuilder};
+use clap::{Args, Parser};
+use multi_agent_core::{Error, MultiAgentGui, MultiAgentSimulation, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+/// Command line of a multi-agent application: the standard [`LaunchOptions`]
+/// followed by the options `Extra` added by the simulation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use clap::Args;
+/// use multi_agent::AppLauncher;
+///
+/// #[derive(Debug, Clone, Args)]
+/// struct BoidsOptions {
+///     /// Number of boids at launch
+///     #[arg(long, default_value_t = 500)]
+///     boids: usize,
+/// }
+///
+/// fn main() -> multi_agent::Result<()> {
+///     AppLauncher::builder().run_cli_with::<Boids, BoidsGui, BoidsOptions, _>(
+///         |options, config| config.boid_count = options.boids,
+///     )
+/// }
+/// ```
+///
+/// ```text
+/// $ boids --headless --ticks 10000 --seed 42 --boids 2000 --record run.json
+/// ```
+#[derive(Debug, Clone, Parser)]
+pub struct CommandLine<Extra: Args = NoOptions> {
+    /// Options shared by every multi-agent application.
+    #[command(flatten)]
+    pub launch: LaunchOptions,
+
+    /// Options added by the simulation.
+    #[command(flatten)]
+    pub extra: Extra,
+}
+
+/// Absence of simulation-specific command-line options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Args)]
+pub struct NoOptions {}
+
+/// Standard command-line options of a multi-agent application.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct LaunchOptions {
+    /// Run without any window, as fast as possible
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Number of ticks to run in headless mode [default: 1000, or the length of the replay]
+    #[arg(long, value_name = "N", requires = "headless")]
+    pub ticks: Option<u64>,
+
+    /// Master seed of the simulation [default: random]
+    #[arg(long, value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Tick frequency, overriding the one of the simulation
+    #[arg(long, value_name = "HZ")]
+    pub hz: Option<u64>,
+
+    /// JSON file holding the initial GUI data
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Record the inputs of the run to a JSON file, written once the run ends
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Replay the inputs recorded in a JSON file
+    #[arg(long, value_name = "FILE", conflicts_with = "config")]
+    pub replay: Option<PathBuf>,
+
+    /// Open the window in fullscreen mode
+    #[arg(long, conflicts_with = "headless")]
+    pub fullscreen: bool,
+
+    /// Scenario script to run before every tick
+    #[cfg(feature = "scripting")]
+    #[arg(long, value_name = "FILE")]
+    pub script: Option<PathBuf>,
+}
+
+impl LaunchOptions {
+    /// Number of ticks of a headless run when neither `--ticks` nor `--replay` is given.
+    pub const DEFAULT_HEADLESS_TICKS: u64 = 1_000;
+
+    /// Initial GUI data read from the `--config` file, or `fallback` without one.
+    ///
+    /// # Errors
+    /// Returns `Error::Config` if the file cannot be read or parsed.
+    pub fn initial_gui_data<GuiData>(&self, fallback: GuiData) -> Result<GuiData>
+    where
+        GuiData: DeserializeOwned,
+    {
+        let Some(path) = &self.config else {
+            return Ok(fallback);
+        };
+        let config = |err: &dyn std::fmt::Display| {
+            Error::Config(format!("Cannot load {}: {err}", path.display()))
+        };
+        let file = File::open(path).map_err(|err| config(&err))?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| config(&err))
+    }
+
+    /// Apply the options to `builder`, then run the application in a window or headless.
+    ///
+    /// The `--config` file is not read here: see [`initial_gui_data`](Self::initial_gui_data).
+    ///
+    /// # Errors
+    /// Returns `Error::Recording` if the replay cannot be loaded or the recording
+    /// cannot be saved, or the error of the run.
+    pub fn launch<Simulation, Gui>(
+        &self,
+        mut builder: RuntimeBuilder<
+            Simulation::GuiData,
+            Simulation::MessageFromGui,
+            Simulation::SimulationData,
+            Simulation::MessageToGui,
+        >,
+    ) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation,
+        Gui: MultiAgentGui<
+            GuiData = Simulation::GuiData,
+            SimulationData = Simulation::SimulationData,
+            MessageFromSimulation = Simulation::MessageToGui,
+            MessageToSimulation = Simulation::MessageFromGui,
+        >,
+        Simulation::SimulationData: 'static,
+        Simulation::GuiData: Serialize + DeserializeOwned + 'static,
+        Simulation::MessageFromGui: Serialize + DeserializeOwned,
+    {
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(hz) = self.hz {
+            builder = builder.frequency_in_hz(hz);
+        }
+        if self.fullscreen {
+            builder = builder.fullscreen(true);
+        }
+        #[cfg(feature = "scripting")]
+        if let Some(path) = &self.script {
+            builder = builder.script_file(path);
+        }
+
+        let mut replayed_ticks: Option<u64> = None;
+        if let Some(path) = &self.replay {
+            let recording: Recording<Simulation::GuiData, Simulation::MessageFromGui> =
+                Recording::load(path)?;
+            replayed_ticks = Some(recording.len());
+            builder = builder.replay(recording);
+        }
+
+        let recorder: Option<Recorder<Simulation::GuiData, Simulation::MessageFromGui>> =
+            self.record.as_ref().map(|_| Recorder::new());
+        if let Some(recorder) = &recorder {
+            builder = builder.recorder(recorder.clone());
+        }
+
+        let result: Result<()> = if self.headless {
+            let ticks: u64 = self
+                .ticks
+                .or(replayed_ticks)
+                .unwrap_or(Self::DEFAULT_HEADLESS_TICKS);
+            builder.run_headless::<Simulation>(ticks).map(|report| {
+                log::info!(
+                    "Ran {} ticks headless in {:.3?}",
+                    report.ticks.len(),
+                    report.elapsed
+                );
+            })
+        } else {
+            builder.run::<Simulation, Gui>()
+        };
+
+        match (recorder, &self.record) {
+            (Some(recorder), Some(path)) => save_recording(&recorder, path, result),
+            _ => result,
+        }
+    }
+}
+
+/// Save the recording of a run to `path`, even when the run failed since that is
+/// when it is most needed, and return the outcome of the run first.
+fn save_recording<GuiData, Message>(
+    recorder: &Recorder<GuiData, Message>,
+    path: &Path,
+    result: Result<()>,
+) -> Result<()>
+where
+    GuiData: Clone + Serialize + DeserializeOwned,
+    Message: Clone + Serialize + DeserializeOwned,
+{
+    match recorder.recording().save(path) {
+        Ok(()) => log::info!("Recording saved to {}", path.display()),
+        Err(error) if result.is_err() => log::warn!("Cannot save the recording: {error}"),
+        Err(error) => return Err(error),
+    }
+    result
+}
+
+impl<GuiData, MessageFromGui, SimulationData, MessageToGui>
+    RuntimeBuilder<GuiData, MessageFromGui, SimulationData, MessageToGui>
+{
+    /// Parse the standard [`LaunchOptions`] from the command line, then run the
+    /// application in a window or headless.
+    ///
+    /// Settings given to the builder apply unless an option overrides them.
+    /// `--help` prints the options and exits.
+    ///
+    /// # Errors
+    /// Returns `Error::Config` if the `--config` file cannot be loaded,
+    /// `Error::Recording` if the replay cannot be loaded or the recording
+    /// cannot be saved, or the error of the run.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use multi_agent::AppLauncher;
+    ///
+    /// fn main() -> multi_agent::Result<()> {
+    ///     AppLauncher::builder().channel_capacity(1_000).run_cli::<MySimulation, MyGui>()
+    /// }
+    /// ```
+    #[inline]
+    pub fn run_cli<Simulation, Gui>(self) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        Gui: MultiAgentGui<
+            GuiData = Simulation::GuiData,
+            SimulationData = Simulation::SimulationData,
+            MessageFromSimulation = Simulation::MessageToGui,
+            MessageToSimulation = Simulation::MessageFromGui,
+        >,
+        SimulationData: 'static,
+        GuiData: Default + Serialize + DeserializeOwned + 'static,
+        MessageFromGui: Serialize + DeserializeOwned,
+    {
+        self.run_cli_with::<Simulation, Gui, NoOptions, _>(|_, _| {})
+    }
+
+    /// Parse the standard [`LaunchOptions`] and the options `Extra` of the
+    /// simulation from the command line, then run the application in a window
+    /// or headless.
+    ///
+    /// `extend` adjusts the initial GUI data, read from `--config` if given, to
+    /// the options of the simulation before anything runs.
+    ///
+    /// # Errors
+    /// Returns `Error::Config` if the `--config` file cannot be loaded,
+    /// `Error::Recording` if the replay cannot be loaded or the recording
+    /// cannot be saved, or the error of the run.
+    pub fn run_cli_with<Simulation, Gui, Extra, F>(mut self, extend: F) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation<
+            GuiData = GuiData,
+            MessageFromGui = MessageFromGui,
+            SimulationData = SimulationData,
+            MessageToGui = MessageToGui,
+        >,
+        Gui: MultiAgentGui<
+            GuiData = Simulation::GuiData,
+            SimulationData = Simulation::SimulationData,
+            MessageFromSimulation = Simulation::MessageToGui,
+            MessageToSimulation = Simulation::MessageFromGui,
+        >,
+        SimulationData: 'static,
+        GuiData: Default + Serialize + DeserializeOwned + 'static,
+        MessageFromGui: Serialize + DeserializeOwned,
+        Extra: Args,
+        F: FnOnce(&Extra, &mut GuiData),
+    {
+        let command_line: CommandLine<Extra> = CommandLine::parse();
+        let fallback: GuiData = self.take_initial_gui_data().unwrap_or_default();
+        let mut gui_data: GuiData = command_line.launch.initial_gui_data(fallback)?;
+        extend(&command_line.extra, &mut gui_data);
+        command_line
+            .launch
+            .launch::<Simulation, Gui>(self.initial_gui_data(gui_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use std::{fs, sync::Arc, time::Duration};
+
+    #[derive(Debug, Clone, Args)]
+    struct Population {
+        /// Number of agents at launch
+        #[arg(long, default_value_t = 10)]
+        agents: u32,
+    }
+
+    #[test]
+    fn test_command_line_definition() {
+        CommandLine::<NoOptions>::command().debug_assert();
+        CommandLine::<Population>::command().debug_assert();
+    }
+
+    #[test]
+    fn test_command_line_parsing() {
+        let command_line = CommandLine::<Population>::try_parse_from([
+            "app",
+            "--headless",
+            "--ticks",
+            "50",
+            "--seed",
+            "7",
+            "--hz",
+            "30",
+            "--agents",
+            "200",
+        ])
+        .unwrap();
+
+        assert!(command_line.launch.headless);
+        assert_eq!(command_line.launch.ticks, Some(50));
+        assert_eq!(command_line.launch.seed, Some(7));
+        assert_eq!(command_line.launch.hz, Some(30));
+        assert!(!command_line.launch.fullscreen);
+        assert_eq!(command_line.extra.agents, 200);
+
+        let defaults = CommandLine::<Population>::try_parse_from(["app"]).unwrap();
+        assert_eq!(defaults.launch, LaunchOptions::default());
+        assert_eq!(defaults.extra.agents, 10);
+    }
+
+    #[test]
+    fn test_command_line_conflicts() {
+        assert!(CommandLine::<NoOptions>::try_parse_from(["app", "--ticks", "5"]).is_err());
+        assert!(
+            CommandLine::<NoOptions>::try_parse_from(["app", "--headless", "--fullscreen"])
+                .is_err()
+        );
+        assert!(CommandLine::<NoOptions>::try_parse_from(["app", "--seed", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_recording_is_saved_when_the_run_fails() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-cli-recording-{}.json",
+            std::process::id()
+        ));
+        let recorder: Recorder<u32, String> = Recorder::new();
+        recorder.record(Duration::from_millis(20), &Arc::new(4), &[]);
+
+        let result = save_recording(&recorder, &path, Err(Error::Gui(String::from("boom"))));
+        let recording: Recording<u32, String> = Recording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::Gui(_))));
+        assert_eq!(recording.len(), 1);
+    }
+
+    #[test]
+    fn test_initial_gui_data_from_config() {
+        let path = std::env::temp_dir().join(format!("multi-agent-cli-{}.json", std::process::id()));
+        fs::write(&path, "[3, 4]").unwrap();
+
+        let options = LaunchOptions {
+            config: Some(path.clone()),
+            ..LaunchOptions::default()
+        };
+        assert_eq!(options.initial_gui_data((0, 0)).unwrap(), (3, 4));
+        assert_eq!(
+            LaunchOptions::default().initial_gui_data((1, 2)).unwrap(),
+            (1, 2)
+        );
+
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            options.initial_gui_data((0, 0)),
+            Err(Error::Config(_))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,1717 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use crate::session::SessionFiles;
+use crate::{
+    panic::{self, CaughtPanic},
+    record::TickInputs,
+    schedule::Scheduler,
+    stats::RateMeter,
+    PanicPolicy, Publication, Recorder, Schedule, TickStats,
+};
+#[cfg(feature = "scripting")]
+use crate::{scenario::ScenarioRunner, Scenario};
+use multi_agent_core::{
+    CancellationToken, Error, MultiAgentSimulation, Result, SeedFactory, Session,
+};
+use multi_agent_sync::{
+    message::{Coalescing, CoalescingReceiver, MessageReceiver, MessageSender},
+    ChannelFill, DeltaFeed, Publisher, Recovery, RecoveryAction, RuntimeStats, Shared, Signal,
+    SimulationControl, SimulationFailure, Timeline, TripleBuffer,
+};
+#[cfg(feature = "serde")]
+use multi_agent_sync::{SessionRequest, SessionStatus};
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+    vec,
+};
+
+/// Drives a simulation one tick at a time.
+///
+/// The driver owns the simulation instance together with its side of the
+/// shared state and message channels. It is used by both the windowed and the
+/// headless runtimes so that a tick behaves the same way in both modes.
+#[derive(Debug)]
+pub struct SimulationDriver<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    simulation: Simulation,
+    simulation_data: Shared<Simulation::SimulationData>,
+    gui_data: Shared<Simulation::GuiData>,
+    receiver: MessageReceiver<Simulation::MessageFromGui>,
+    sender: MessageSender<Simulation::MessageToGui>,
+    tick: u64,
+    seeds: SeedFactory,
+    panic_policy: PanicPolicy,
+    publisher: Option<Box<dyn Publisher<Simulation::SimulationData>>>,
+    feed: Option<DeltaFeed<Simulation::SimulationData>>,
+    timeline: Option<Timeline<Simulation::SimulationData>>,
+    timeline_version: Option<u64>,
+    recorder: Option<Recorder<Simulation::GuiData, Simulation::MessageFromGui>>,
+    replay: Option<Replay<Simulation::GuiData, Simulation::MessageFromGui>>,
+    stats: Option<Shared<RuntimeStats>>,
+    rate_meter: Option<RateMeter>,
+    overruns: u64,
+    cancellation: CancellationToken,
+    started: bool,
+    paused: bool,
+    last_gui_data: Arc<Simulation::GuiData>,
+    last_gui_generation: u64,
+    events: Arc<Signal>,
+    coalescing: Option<CoalescingReceiver<Simulation::MessageFromGui>>,
+    #[cfg(feature = "scripting")]
+    scenario: Option<
+        ScenarioRunner<Simulation::SimulationData, Simulation::GuiData, Simulation::MessageFromGui>,
+    >,
+    #[cfg(feature = "serde")]
+    sessions: Option<SessionFiles<Simulation::GuiData, Simulation::SimulationData>>,
+}
+
+impl<Simulation> SimulationDriver<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    /// Wrap a simulation created with `seeds`.
+    #[inline]
+    pub fn new(
+        simulation: Simulation,
+        seeds: SeedFactory,
+        simulation_data: Shared<Simulation::SimulationData>,
+        gui_data: Shared<Simulation::GuiData>,
+        receiver: MessageReceiver<Simulation::MessageFromGui>,
+        sender: MessageSender<Simulation::MessageToGui>,
+    ) -> Self {
+        let events = Arc::new(Signal::default());
+        gui_data.on_change(notifier(&events));
+        receiver.on_message(notifier(&events));
+        Self {
+            simulation,
+            simulation_data,
+            last_gui_generation: gui_data.generation(),
+            last_gui_data: gui_data.load_full(),
+            gui_data,
+            receiver,
+            sender,
+            tick: 0,
+            seeds,
+            panic_policy: PanicPolicy::Stop,
+            publisher: None,
+            feed: None,
+            timeline: None,
+            timeline_version: None,
+            recorder: None,
+            replay: None,
+            stats: None,
+            rate_meter: None,
+            overruns: 0,
+            cancellation: CancellationToken::new(),
+            started: false,
+            paused: false,
+            events,
+            coalescing: None,
+            #[cfg(feature = "scripting")]
+            scenario: None,
+            #[cfg(feature = "serde")]
+            sessions: None,
+        }
+    }
+
+    /// Set what [`run`](Self::run) does when the simulation panics during a tick.
+    #[inline]
+    #[must_use]
+    pub const fn with_panic_policy(mut self, panic_policy: PanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
+    }
+
+    /// Set how every tick publishes its data to the GUI.
+    #[inline]
+    #[must_use]
+    pub fn with_publication(mut self, publication: Publication) -> Self {
+        self.publisher = match publication {
+            Publication::Allocate => None,
+            Publication::TripleBuffer => {
+                Some(Box::new(TripleBuffer::new(self.simulation_data.clone())))
+            }
+        };
+        self.feed = None;
+        self
+    }
+
+    /// Publish the data of every tick with `publisher`, which must write to the
+    /// simulation data shared by this driver.
+    ///
+    /// If the publisher records the changes it publishes, the timeline keeps
+    /// them instead of every frame in full.
+    #[inline]
+    #[must_use]
+    pub fn with_publisher(
+        mut self,
+        publisher: Box<dyn Publisher<Simulation::SimulationData>>,
+    ) -> Self {
+        self.feed = publisher.feed();
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Pass the messages of every tick through `coalesce`.
+    #[inline]
+    #[must_use]
+    pub fn with_coalescing(mut self, coalesce: Coalescing<Simulation::MessageFromGui>) -> Self {
+        self.coalescing = Some(CoalescingReceiver::new(self.receiver.clone(), coalesce));
+        self
+    }
+
+    /// Record the data published by every tick in `timeline`.
+    #[inline]
+    #[must_use]
+    pub fn with_timeline(mut self, timeline: Timeline<Simulation::SimulationData>) -> Self {
+        self.timeline = Some(timeline);
+        self
+    }
+
+    /// Record the inputs of every tick with `recorder`, starting a new recording.
+    #[inline]
+    #[must_use]
+    pub fn with_recorder(
+        mut self,
+        recorder: Recorder<Simulation::GuiData, Simulation::MessageFromGui>,
+    ) -> Self {
+        recorder.start(self.seeds.master(), self.gui_data.load_full());
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Take the inputs of the next ticks from `ticks` instead of the GUI.
+    ///
+    /// Messages sent and GUI data written by the GUI meanwhile are ignored, the
+    /// recorded GUI data is only published for display. Once every recorded
+    /// tick has been replayed, the simulation goes back to the live inputs.
+    #[inline]
+    #[must_use]
+    pub fn with_replay(
+        mut self,
+        ticks: Vec<TickInputs<Simulation::GuiData, Simulation::MessageFromGui>>,
+    ) -> Self {
+        self.replay = Some(Replay {
+            ticks: ticks.into_iter(),
+            gui_data: self.gui_data.load_full(),
+        });
+        self
+    }
+
+    /// Publish the measurements of every tick in `stats`.
+    #[inline]
+    #[must_use]
+    pub fn with_stats(mut self, stats: Shared<RuntimeStats>) -> Self {
+        self.stats = Some(stats);
+        self.rate_meter = Some(RateMeter::new());
+        self
+    }
+
+    /// Run the scripts of `scenario` before every live tick.
+    #[cfg(feature = "scripting")]
+    #[inline]
+    #[must_use]
+    pub fn with_scenario(
+        mut self,
+        scenario: Scenario<
+            Simulation::SimulationData,
+            Simulation::GuiData,
+            Simulation::MessageFromGui,
+        >,
+    ) -> Self {
+        self.scenario = Some(ScenarioRunner::new(scenario));
+        self
+    }
+
+    /// Save and open sessions with `sessions` when `control` asks for it.
+    #[cfg(feature = "serde")]
+    #[inline]
+    #[must_use]
+    pub const fn with_sessions(
+        mut self,
+        sessions: SessionFiles<Simulation::GuiData, Simulation::SimulationData>,
+    ) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
+    /// Number of ticks executed so far.
+    #[inline]
+    pub const fn tick_count(&self) -> u64 {
+        self.tick
+    }
+
+    /// Seeds handed to the current simulation instance.
+    #[inline]
+    pub const fn seeds(&self) -> SeedFactory {
+        self.seeds
+    }
+
+    /// Run a single tick and publish the resulting data.
+    ///
+    /// When the cancellation token is cancelled during `update`, the data it
+    /// returns is discarded: nothing is published or recorded and the tick does
+    /// not count. Ticks that fail or panic are not recorded either.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::update`.
+    pub fn tick(&mut self, delta_time: Duration) -> Result<TickStats> {
+        let gui_to_simulation = ChannelFill {
+            pending: self.receiver.pending(),
+            capacity: self.receiver.capacity(),
+            dropped: self.receiver.dropped(),
+        };
+        let (delta_time, gui_data, messages) = self.next_inputs(delta_time);
+        let message_count: usize = messages.len();
+        let recorded_messages: Option<Vec<Simulation::MessageFromGui>> =
+            self.recorder.as_ref().map(|_| messages.clone());
+
+        if !self.started {
+            self.simulation.on_start();
+            self.started = true;
+        }
+        if !Arc::ptr_eq(&gui_data, &self.last_gui_data) {
+            self.simulation.on_gui_data_changed(&gui_data);
+            self.last_gui_data = Arc::clone(&gui_data);
+        }
+
+        let start = Instant::now();
+        let new_simulation_data = self.simulation.update(
+            (*gui_data).clone(),
+            messages,
+            delta_time,
+            |message| self.sender.send_lossy(message),
+            &self.cancellation,
+        )?;
+        let update_time: Duration = start.elapsed();
+        if self.cancellation.is_cancelled() {
+            return Ok(TickStats {
+                tick: self.tick,
+                delta_time,
+                update_time,
+                publish_time: Duration::ZERO,
+                messages: message_count,
+            });
+        }
+
+        if let (Some(recorder), Some(messages)) = (&self.recorder, &recorded_messages) {
+            recorder.record(delta_time, &gui_data, messages);
+        }
+
+        let start = Instant::now();
+        match &mut self.publisher {
+            Some(publisher) => publisher.publish(new_simulation_data),
+            None => self.simulation_data.store(new_simulation_data.clone()),
+        }
+        let publish_time: Duration = start.elapsed();
+
+        if let Some(timeline) = &self.timeline {
+            let simulation_data = &self.simulation_data;
+            match self.feed.as_ref().and_then(DeltaFeed::latest) {
+                Some((version, change))
+                    if self
+                        .timeline_version
+                        .is_some_and(|last| last + 1 == version) =>
+                {
+                    timeline.push_change(self.tick, change, || simulation_data.load_full());
+                }
+                _ => timeline.push(self.tick, simulation_data.load_full()),
+            }
+            self.timeline_version = self.feed.as_ref().map(DeltaFeed::version);
+        }
+
+        let stats = TickStats {
+            tick: self.tick,
+            delta_time,
+            update_time,
+            publish_time,
+            messages: message_count,
+        };
+        self.publish_stats(&stats, gui_to_simulation);
+        self.tick += 1;
+
+        Ok(stats)
+    }
+
+    /// Replace the simulation with a new instance created from the current GUI data
+    /// and `seeds`.
+    ///
+    /// The previous instance is dropped and the tick counter starts again from 0.
+    /// Messages sent to the previous instance and not delivered yet are discarded,
+    /// and so are the timeline and the replay. The recording starts over.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::new`. The previous
+    /// instance and seeds are kept in that case.
+    pub fn restart(&mut self, seeds: SeedFactory) -> Result<()> {
+        let gui_data: Arc<Simulation::GuiData> = self.gui_data.load_full();
+        let simulation = Simulation::new((*gui_data).clone(), seeds)?;
+        self.install(simulation, Arc::clone(&gui_data));
+        self.seeds = seeds;
+        self.tick = 0;
+        self.receiver.drain();
+        if let Some(timeline) = &self.timeline {
+            timeline.clear();
+        }
+        if let Some(recorder) = &self.recorder {
+            recorder.start(seeds.master(), gui_data);
+        }
+        self.replay = None;
+        Ok(())
+    }
+
+    /// Replace the simulation with an instance restored from the last published data.
+    ///
+    /// The tick counter is left untouched, so the next tick replays the one
+    /// that did not publish anything.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::restore`. The
+    /// previous instance is kept in that case.
+    pub fn roll_back(&mut self) -> Result<()> {
+        let gui_data: Arc<Simulation::GuiData> = self.gui_data.load_full();
+        let simulation = Simulation::restore(
+            (*gui_data).clone(),
+            (**self.simulation_data.load()).clone(),
+            self.seeds,
+        )?;
+        self.install(simulation, gui_data);
+        Ok(())
+    }
+
+    /// Replace the simulation with an instance restored from the frame of `tick`
+    /// kept in the timeline, and continue from the following tick.
+    ///
+    /// The frames recorded after `tick` are dropped, and so is the replay, whose
+    /// next inputs belong to the ticks that followed the latest one.
+    ///
+    /// # Errors
+    /// Returns `Error::FrameUnavailable` if the frame is not in the timeline, or
+    /// the error produced by `MultiAgentSimulation::restore`. The current
+    /// instance is kept in both cases.
+    pub fn rewind(&mut self, tick: u64) -> Result<()> {
+        let timeline = self
+            .timeline
+            .as_ref()
+            .ok_or(Error::FrameUnavailable { tick })?;
+        let data: Arc<Simulation::SimulationData> =
+            timeline.get(tick).ok_or(Error::FrameUnavailable { tick })?;
+
+        let gui_data: Arc<Simulation::GuiData> = self.gui_data.load_full();
+        let simulation = Simulation::restore((*gui_data).clone(), (*data).clone(), self.seeds)?;
+        timeline.truncate_after(tick);
+        self.timeline_version = None;
+        self.publish(&data);
+        self.install(simulation, gui_data);
+        self.tick = tick + 1;
+        self.replay = None;
+        Ok(())
+    }
+
+    /// Snapshot of the current GUI data, published data, tick count and master seed.
+    pub fn session(&self) -> Session<Simulation::GuiData, Simulation::SimulationData> {
+        Session::new(
+            self.seeds.master(),
+            self.tick,
+            (**self.gui_data.load()).clone(),
+            (**self.simulation_data.load()).clone(),
+        )
+    }
+
+    /// Replace the simulation with an instance restored from `session`, and continue
+    /// from its tick with its GUI data and master seed.
+    ///
+    /// Messages sent to the previous instance and not delivered yet are discarded,
+    /// and so are the timeline and the replay.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::restore_session`. The
+    /// current instance is kept in that case.
+    pub fn open_session(
+        &mut self,
+        session: Session<Simulation::GuiData, Simulation::SimulationData>,
+    ) -> Result<()> {
+        let seeds = SeedFactory::new(session.seed);
+        let tick: u64 = session.tick;
+        let gui_data: Simulation::GuiData = session.gui_data.clone();
+        let simulation_data: Simulation::SimulationData = session.simulation_data.clone();
+        let simulation = Simulation::restore_session(session)?;
+
+        self.gui_data.store(gui_data);
+        self.last_gui_generation = self.gui_data.generation();
+        self.publish(&simulation_data);
+        self.install(simulation, self.gui_data.load_full());
+        self.seeds = seeds;
+        self.tick = tick;
+        self.receiver.drain();
+        if let Some(timeline) = &self.timeline {
+            timeline.clear();
+        }
+        self.replay = None;
+        Ok(())
+    }
+
+    /// Run ticks paced by `schedule` until `control` is stopped.
+    ///
+    /// The thread wakes up once per `period` and executes the steps the
+    /// schedule asks for, with the elapsed time multiplied by the time scale of
+    /// `control`. Wake-ups are aligned on a fixed grid so that time spent in
+    /// `update()` does not make the loop drift. Wake-ups that come too late
+    /// are counted as overruns in the published statistics.
+    ///
+    /// While paused, the thread blocks and only executes the ticks requested
+    /// through `SimulationControl::step`, one per period. While the simulation
+    /// reports itself idle, the thread blocks until a message arrives or the GUI
+    /// data changes, then executes a single tick.
+    ///
+    /// The pause and resume hooks of the simulation are called when `control`
+    /// is paused or resumed, and the tick in progress is cancelled when it is stopped.
+    /// Restart requests create the new instance with the seed of `control`,
+    /// script requests are handled by the scenario, if any, and session requests
+    /// are handled if the driver was given session files.
+    /// An error returned by the simulation is logged and reported to `control`,
+    /// then the thread waits for a restart request or for the stop. Panics are
+    /// caught on every tick and handled according to the panic policy.
+    ///
+    /// # Errors
+    /// Returns `Error::SimulationFailed` with the last error of the simulation
+    /// if it was not restarted afterwards.
+    pub fn run(
+        &mut self,
+        schedule: Schedule,
+        period: Duration,
+        control: &SimulationControl,
+    ) -> Result<()> {
+        let mut scheduler = Scheduler::new(schedule);
+        let step_delta_time: Duration = match schedule {
+            Schedule::Variable => period,
+            Schedule::Fixed { delta_time, .. } => delta_time,
+        };
+
+        self.cancellation = control.cancellation_token();
+        control.on_change(notifier(&self.events));
+        self.paused = control.is_paused();
+        #[cfg(feature = "serde")]
+        if self.sessions.is_some() {
+            control.set_session_status(SessionStatus::Idle);
+        }
+
+        let mut failure: Option<Error> = None;
+        let mut last_wake_up = Instant::now();
+        let mut next_wake_up = last_wake_up;
+        while !control.is_stopped() {
+            #[cfg(feature = "scripting")]
+            self.sync_scenario(control);
+
+            if control.take_restart() {
+                failure = self.restart(control.seeds()).err();
+                if let Some(error) = &failure {
+                    self.report(error, control);
+                }
+                scheduler = Scheduler::new(schedule);
+                last_wake_up = Instant::now();
+                next_wake_up = last_wake_up;
+                continue;
+            }
+
+            #[cfg(feature = "serde")]
+            if self.sync_sessions(control) {
+                scheduler = Scheduler::new(schedule);
+                last_wake_up = Instant::now();
+                next_wake_up = last_wake_up;
+                continue;
+            }
+
+            if let Some(tick) = control.take_rewind() {
+                if let Err(error) = self.rewind(tick) {
+                    log::warn!("Cannot resume the simulation from tick {tick}: {error}");
+                }
+                scheduler = Scheduler::new(schedule);
+                last_wake_up = Instant::now();
+                next_wake_up = last_wake_up;
+                continue;
+            }
+
+            self.notify_pause(control.is_paused());
+
+            if control.is_halted() {
+                control.wait_while_halted();
+                if control.take_step() {
+                    failure = self.guarded_tick(step_delta_time, control);
+                    thread::sleep(period);
+                }
+
+                last_wake_up = Instant::now();
+                next_wake_up = last_wake_up;
+                continue;
+            }
+
+            if self.simulation.is_idle() {
+                if self.wait_for_event(control) {
+                    failure = self.guarded_tick(step_delta_time, control);
+                }
+
+                scheduler = Scheduler::new(schedule);
+                last_wake_up = Instant::now();
+                next_wake_up = last_wake_up;
+                continue;
+            }
+
+            let now = Instant::now();
+            let elapsed: Duration = now.duration_since(last_wake_up);
+            let steps = scheduler.advance(
+                Duration::try_from_secs_f64(elapsed.as_secs_f64() * control.time_scale())
+                    .unwrap_or(Duration::MAX),
+            );
+            last_wake_up = now;
+
+            for _ in 0..steps.count {
+                failure = self.guarded_tick(steps.delta_time, control);
+                if failure.is_some() {
+                    break;
+                }
+            }
+
+            next_wake_up += period;
+            let now = Instant::now();
+            if let Some(remaining) = next_wake_up.checked_duration_since(now) {
+                thread::sleep(remaining);
+            } else {
+                next_wake_up = now;
+                self.overruns += 1;
+            }
+        }
+
+        failure.map_or(Ok(()), |error| {
+            Err(Error::SimulationFailed {
+                tick: self.tick,
+                source: Box::new(error),
+            })
+        })
+    }
+
+    /// Replace the simulation with `simulation`, created or restored from `gui_data`.
+    ///
+    /// The start hook of the new instance is called before its first tick.
+    /// Publish data that was not produced by a tick, through the publisher if
+    /// any, so that it also reaches the readers of its feed.
+    fn publish(&mut self, simulation_data: &Simulation::SimulationData) {
+        match &mut self.publisher {
+            Some(publisher) => publisher.publish(simulation_data),
+            None => self.simulation_data.store(simulation_data.clone()),
+        }
+    }
+
+    fn install(&mut self, simulation: Simulation, gui_data: Arc<Simulation::GuiData>) {
+        self.shut_down();
+        self.simulation = simulation;
+        self.last_gui_data = gui_data;
+        self.started = false;
+    }
+
+    /// Call `on_shutdown` on the current instance, which may have panicked during
+    /// the last tick, logging a panic instead of unwinding.
+    fn shut_down(&mut self) {
+        if let Err(caught) = panic::catch(|| self.simulation.on_shutdown()) {
+            log::warn!(
+                "Simulation panicked while shutting down: {}",
+                caught.message
+            );
+        }
+    }
+
+    fn notify_pause(&mut self, paused: bool) {
+        if paused == self.paused {
+            return;
+        }
+        self.paused = paused;
+        if paused {
+            self.simulation.on_pause();
+        } else {
+            self.simulation.on_resume();
+        }
+    }
+
+    /// Block until a message arrives or the GUI data changes, and return whether
+    /// one of them happened.
+    ///
+    /// Gives up early, returning `false`, when `control` is halted or has a request.
+    /// Sleeps in between, since all three of them signal `events`.
+    fn wait_for_event(&self, control: &SimulationControl) -> bool {
+        loop {
+            let generation: u64 = self.events.generation();
+            if control.is_halted() || control.has_pending_request() {
+                return false;
+            }
+            if !self.receiver.is_empty() {
+                return true;
+            }
+            if self.gui_data.generation() != self.last_gui_generation {
+                return true;
+            }
+            let _ = self.events.wait_for_change(generation, Duration::MAX);
+        }
+    }
+
+    /// Handle the script request of `control`, if any, and report the state of the script.
+    #[cfg(feature = "scripting")]
+    fn sync_scenario(&mut self, control: &SimulationControl) {
+        let Some(scenario) = &mut self.scenario else {
+            return;
+        };
+        if let Some(request) = control.take_script_request() {
+            scenario.handle(&request);
+        }
+        if let Some(status) = scenario.take_status_change() {
+            control.set_script_status(status);
+        }
+    }
+
+    /// Handle the session request of `control`, if any, report its outcome and
+    /// return whether a session was opened.
+    #[cfg(feature = "serde")]
+    fn sync_sessions(&mut self, control: &SimulationControl) -> bool {
+        let Some(sessions) = self.sessions.clone() else {
+            return false;
+        };
+        let Some(request) = control.take_session_request() else {
+            return false;
+        };
+
+        let (status, opened): (SessionStatus, bool) = match request {
+            SessionRequest::Save(path) => match sessions.save(&self.session(), &path) {
+                Ok(()) => {
+                    log::info!("Session saved to {} at tick {}", path.display(), self.tick);
+                    let tick: u64 = self.tick;
+                    (SessionStatus::Saved { path, tick }, false)
+                }
+                Err(error) => (Self::session_failed(path, &error), false),
+            },
+            SessionRequest::Open(path) => match sessions
+                .load(&path)
+                .and_then(|session| self.open_session(session))
+            {
+                Ok(()) => {
+                    log::info!(
+                        "Session opened from {} at tick {}",
+                        path.display(),
+                        self.tick
+                    );
+                    control.set_seed(self.seeds.master());
+                    let tick: u64 = self.tick;
+                    (SessionStatus::Opened { path, tick }, true)
+                }
+                Err(error) => (Self::session_failed(path, &error), false),
+            },
+        };
+        control.set_session_status(status);
+        opened
+    }
+
+    #[cfg(feature = "serde")]
+    fn session_failed(path: PathBuf, error: &Error) -> SessionStatus {
+        log::warn!("Session {} failed: {error}", path.display());
+        SessionStatus::Failed {
+            path,
+            message: error.to_string(),
+        }
+    }
+
+    /// Time step, GUI data and messages of the next tick, taken from the replay if any.
+    ///
+    /// Live inputs include the changes made by the scenario script, if any.
+    fn next_inputs(
+        &mut self,
+        delta_time: Duration,
+    ) -> (
+        Duration,
+        Arc<Simulation::GuiData>,
+        Vec<Simulation::MessageFromGui>,
+    ) {
+        let live_messages: Vec<Simulation::MessageFromGui> = self
+            .coalescing
+            .as_ref()
+            .map_or_else(|| self.receiver.drain(), CoalescingReceiver::drain);
+        if let Some(replay) = &mut self.replay {
+            if let Some(inputs) = replay.ticks.next() {
+                if let Some(gui_data) = inputs.gui_data {
+                    replay.gui_data = Arc::new(gui_data);
+                    self.gui_data.store((*replay.gui_data).clone());
+                }
+                self.last_gui_generation = self.gui_data.generation();
+                return (
+                    inputs.delta_time,
+                    Arc::clone(&replay.gui_data),
+                    inputs.messages,
+                );
+            }
+            log::info!("Replay finished at tick {}, back to live inputs", self.tick);
+            self.replay = None;
+        }
+
+        #[cfg(feature = "scripting")]
+        let live_messages = self.run_scenario(live_messages);
+        self.last_gui_generation = self.gui_data.generation();
+        (delta_time, self.gui_data.load_full(), live_messages)
+    }
+
+    /// Run the scenario script, if any, for the next tick and queue the messages it
+    /// sent after `messages`.
+    #[cfg(feature = "scripting")]
+    fn run_scenario(
+        &mut self,
+        mut messages: Vec<Simulation::MessageFromGui>,
+    ) -> Vec<Simulation::MessageFromGui> {
+        if let Some(scenario) = &mut self.scenario {
+            let simulation_data = self.simulation_data.load();
+            messages.extend(scenario.run(self.tick, &simulation_data, &self.gui_data));
+        }
+        messages
+    }
+
+    fn publish_stats(&mut self, tick: &TickStats, gui_to_simulation: ChannelFill) {
+        let (Some(stats), Some(rate_meter)) = (&self.stats, &mut self.rate_meter) else {
+            return;
+        };
+
+        stats.store(RuntimeStats {
+            tick: tick.tick,
+            update_time: tick.update_time,
+            publish_time: tick.publish_time,
+            achieved_hz: rate_meter.tick(Instant::now()),
+            overruns: self.overruns,
+            gui_to_simulation,
+            simulation_to_gui: ChannelFill {
+                pending: self.sender.pending(),
+                capacity: self.sender.capacity(),
+                dropped: self.sender.dropped(),
+            },
+        });
+    }
+
+    /// Run a tick, catching panics, and return the error that halted the simulation, if any.
+    fn guarded_tick(&mut self, delta_time: Duration, control: &SimulationControl) -> Option<Error> {
+        let caught: CaughtPanic = match panic::catch(|| self.tick(delta_time)) {
+            Ok(Ok(_)) => return None,
+            Ok(Err(error)) => {
+                self.report(&error, control);
+                return Some(error);
+            }
+            Err(caught) => caught,
+        };
+
+        let failure = SimulationFailure {
+            tick: self.tick,
+            message: caught.message,
+            location: caught.location,
+        };
+        let description: String = match &failure.location {
+            Some(location) => format!("{} at {location}", failure.message),
+            None => failure.message.clone(),
+        };
+        log::error!("Simulation panicked at tick {}: {description}", self.tick);
+
+        let action: Option<RecoveryAction> = match self.panic_policy {
+            PanicPolicy::Stop => None,
+            PanicPolicy::Restart => self
+                .restart(self.seeds)
+                .ok()
+                .map(|()| RecoveryAction::Restarted),
+            PanicPolicy::Rollback => self.roll_back().ok().map(|()| RecoveryAction::RolledBack),
+        };
+
+        if let Some(action) = action {
+            log::warn!("Simulation recovered from the panic: {action:?}");
+            control.recovered(Recovery { failure, action });
+            None
+        } else {
+            control.fail(failure);
+            Some(Error::SimulationPanic(description))
+        }
+    }
+
+    fn report(&self, error: &Error, control: &SimulationControl) {
+        log::error!("Simulation failed at tick {}: {error}", self.tick);
+        control.fail(SimulationFailure {
+            tick: self.tick,
+            message: error.to_string(),
+            location: None,
+        });
+    }
+}
+
+/// Callback notifying `events`, to register on one of their sources.
+fn notifier(events: &Arc<Signal>) -> impl Fn() + Send + Sync + 'static {
+    let events: Arc<Signal> = Arc::clone(events);
+    move || events.notify()
+}
+
+/// Recorded inputs left to replay, and the recorded GUI data they apply to.
+#[derive(Debug)]
+struct Replay<GuiData, Message> {
+    ticks: vec::IntoIter<TickInputs<GuiData, Message>>,
+    gui_data: Arc<GuiData>,
+}
+
+impl<Simulation> Drop for SimulationDriver<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.shut_down();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_sync::{message::MessageChannel, Change, DiffBuffer, Diffable};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Debug)]
+    struct FailAt {
+        tick: u64,
+        fail_at: u64,
+    }
+
+    #[derive(Debug)]
+    struct Hooks {
+        starts: u64,
+        changes: u64,
+        pauses: u64,
+        data: u64,
+    }
+
+    #[derive(Debug)]
+    struct IdleAfter {
+        tick: u64,
+        busy_ticks: u64,
+    }
+
+    /// Counts its ticks, published as the difference with the previous count.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    struct Steps(u64);
+
+    impl Diffable for Steps {
+        type Delta = u64;
+
+        fn diff(&self, previous: &Self) -> Self::Delta {
+            self.0.wrapping_sub(previous.0)
+        }
+
+        fn apply(&mut self, delta: &Self::Delta) {
+            self.0 = self.0.wrapping_add(*delta);
+        }
+    }
+
+    #[derive(Debug)]
+    struct Stepper {
+        steps: Steps,
+    }
+
+    #[derive(Debug)]
+    struct PanicAt {
+        tick: u64,
+        trigger: u64,
+        restored: bool,
+    }
+
+    impl MultiAgentSimulation for PanicAt {
+        const FREQUENCY_IN_HZ: u64 = 1_000;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                tick: 0,
+                trigger: initial_gui_data,
+                restored: false,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.tick += 1;
+            assert!(
+                self.restored || self.tick != self.trigger,
+                "panic at {}",
+                self.trigger
+            );
+            Ok(&self.tick)
+        }
+
+        fn restore(
+            gui_data: Self::GuiData,
+            simulation_data: Self::SimulationData,
+            _seeds: SeedFactory,
+        ) -> Result<Self> {
+            Ok(Self {
+                tick: simulation_data,
+                trigger: gui_data,
+                restored: true,
+            })
+        }
+    }
+
+    impl MultiAgentSimulation for Stepper {
+        type SimulationData = Steps;
+        type GuiData = ();
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                steps: Steps::default(),
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.steps.0 += 1;
+            Ok(&self.steps)
+        }
+
+        fn restore(
+            _gui_data: Self::GuiData,
+            simulation_data: Self::SimulationData,
+            _seeds: SeedFactory,
+        ) -> Result<Self> {
+            Ok(Self {
+                steps: simulation_data,
+            })
+        }
+    }
+
+    impl MultiAgentSimulation for FailAt {
+        const FREQUENCY_IN_HZ: u64 = 1_000;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                tick: 0,
+                fail_at: initial_gui_data,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            if self.tick == self.fail_at {
+                return Err(Error::Gui(String::from("boom")));
+            }
+            self.tick += 1;
+            Ok(&self.tick)
+        }
+    }
+
+    impl MultiAgentSimulation for Hooks {
+        const FREQUENCY_IN_HZ: u64 = 1_000;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                starts: 0,
+                changes: 0,
+                pauses: 0,
+                data: 0,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.data = self.starts * 100 + self.changes * 10 + self.pauses;
+            Ok(&self.data)
+        }
+
+        fn on_start(&mut self) {
+            self.starts += 1;
+        }
+
+        fn on_pause(&mut self) {
+            self.pauses += 1;
+        }
+
+        fn on_gui_data_changed(&mut self, _gui_data: &Self::GuiData) {
+            self.changes += 1;
+        }
+    }
+
+    impl MultiAgentSimulation for IdleAfter {
+        const FREQUENCY_IN_HZ: u64 = 1_000;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                tick: 0,
+                busy_ticks: initial_gui_data,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.tick += 1;
+            Ok(&self.tick)
+        }
+
+        fn is_idle(&self) -> bool {
+            self.tick >= self.busy_ticks
+        }
+    }
+
+    fn driver<Simulation>(gui_data: u64) -> SimulationDriver<Simulation>
+    where
+        Simulation: MultiAgentSimulation<
+            SimulationData = u64,
+            GuiData = u64,
+            MessageFromGui = (),
+            MessageToGui = (),
+        >,
+    {
+        let (sim_sender, _) = MessageChannel::new(1).split();
+        let (_, sim_receiver) = MessageChannel::new(1).split();
+        let seeds = SeedFactory::new(0);
+        SimulationDriver::new(
+            Simulation::new(gui_data, seeds).unwrap(),
+            seeds,
+            Shared::new(0),
+            Shared::new(gui_data),
+            sim_receiver,
+            sim_sender,
+        )
+    }
+
+    #[test]
+    fn test_lifecycle_hooks() {
+        let mut driver = driver::<Hooks>(0);
+        let gui_data: Shared<u64> = driver.gui_data.clone();
+
+        driver.tick(Duration::from_millis(1)).unwrap();
+        assert_eq!(**driver.simulation_data.load(), 100);
+
+        gui_data.store(5);
+        driver.tick(Duration::from_millis(1)).unwrap();
+        driver.tick(Duration::from_millis(1)).unwrap();
+        assert_eq!(**driver.simulation_data.load(), 110);
+
+        driver.notify_pause(true);
+        driver.notify_pause(true);
+        driver.tick(Duration::from_millis(1)).unwrap();
+        assert_eq!(**driver.simulation_data.load(), 111);
+
+        driver.restart(SeedFactory::new(0)).unwrap();
+        driver.tick(Duration::from_millis(1)).unwrap();
+        assert_eq!(**driver.simulation_data.load(), 100);
+    }
+
+    #[test]
+    fn test_replaced_simulation_shuts_down() {
+        static SHUTDOWNS: AtomicU64 = AtomicU64::new(0);
+
+        #[derive(Debug)]
+        struct Shutdowns(u64);
+
+        impl MultiAgentSimulation for Shutdowns {
+            const FREQUENCY_IN_HZ: u64 = 1_000;
+
+            type SimulationData = u64;
+            type GuiData = u64;
+            type MessageFromGui = ();
+            type MessageToGui = ();
+
+            fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+                Ok(Self(0))
+            }
+
+            fn update<F>(
+                &mut self,
+                _gui_data: Self::GuiData,
+                _messages: Vec<Self::MessageFromGui>,
+                _delta_time: Duration,
+                _send_message_to_gui: F,
+                _cancellation: &CancellationToken,
+            ) -> Result<&Self::SimulationData>
+            where
+                F: Fn(Self::MessageToGui),
+            {
+                self.0 += 1;
+                Ok(&self.0)
+            }
+
+            fn on_shutdown(&mut self) {
+                SHUTDOWNS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut driver = driver::<Shutdowns>(0);
+        driver.tick(Duration::from_millis(1)).unwrap();
+        driver.restart(SeedFactory::new(0)).unwrap();
+        driver.restart(SeedFactory::new(1)).unwrap();
+        assert_eq!(SHUTDOWNS.load(Ordering::Relaxed), 2);
+
+        drop(driver);
+        assert_eq!(SHUTDOWNS.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_triple_buffer_publication() {
+        let mut driver = driver::<PanicAt>(0).with_publication(Publication::TripleBuffer);
+        let simulation_data: Shared<u64> = driver.simulation_data.clone();
+
+        let held: Arc<u64> = {
+            driver.tick(Duration::from_millis(1)).unwrap();
+            simulation_data.load_full()
+        };
+        for _ in 0..5 {
+            driver.tick(Duration::from_millis(1)).unwrap();
+        }
+
+        assert_eq!(*held, 1);
+        assert_eq!(**simulation_data.load(), 6);
+    }
+
+    #[test]
+    fn test_coalesced_messages() {
+        #[derive(Debug)]
+        struct LastSpeed(u64);
+
+        impl MultiAgentSimulation for LastSpeed {
+            const FREQUENCY_IN_HZ: u64 = 1_000;
+
+            type SimulationData = u64;
+            type GuiData = ();
+            type MessageFromGui = u64;
+            type MessageToGui = ();
+
+            fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+                Ok(Self(0))
+            }
+
+            fn update<F>(
+                &mut self,
+                _gui_data: Self::GuiData,
+                messages: Vec<Self::MessageFromGui>,
+                _delta_time: Duration,
+                _send_message_to_gui: F,
+                _cancellation: &CancellationToken,
+            ) -> Result<&Self::SimulationData>
+            where
+                F: Fn(Self::MessageToGui),
+            {
+                self.0 = messages.len() as u64 * 100 + messages.last().copied().unwrap_or(0);
+                Ok(&self.0)
+            }
+        }
+
+        let (sim_sender, _) = MessageChannel::new(1).split();
+        let (sender, sim_receiver) = MessageChannel::new(10).split();
+        let seeds = SeedFactory::new(0);
+        let mut driver = SimulationDriver::new(
+            LastSpeed::new((), seeds).unwrap(),
+            seeds,
+            Shared::new(0),
+            Shared::new(()),
+            sim_receiver,
+            sim_sender,
+        )
+        .with_coalescing(|messages| messages.last().copied().into_iter().collect());
+
+        for speed in 1..=5 {
+            sender.send(speed).unwrap();
+        }
+        driver.tick(Duration::from_millis(1)).unwrap();
+
+        assert_eq!(**driver.simulation_data.load(), 105);
+    }
+
+    #[test]
+    fn test_run_cancels_the_tick_on_stop() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<FailAt>(u64::MAX);
+
+        let thread = {
+            let control = control.clone();
+            thread::spawn(move || {
+                driver.run(Schedule::Variable, Duration::from_millis(1), &control)?;
+                Ok::<bool, Error>(driver.cancellation.is_cancelled())
+            })
+        };
+        thread::sleep(Duration::from_millis(10));
+        control.stop();
+
+        assert!(thread.join().unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_cancelled_tick_is_discarded() {
+        let mut driver = driver::<FailAt>(u64::MAX);
+        driver.tick(Duration::from_millis(1)).unwrap();
+
+        driver.cancellation.cancel();
+        driver.tick(Duration::from_millis(1)).unwrap();
+
+        assert_eq!(**driver.simulation_data.load(), 1);
+        assert_eq!(driver.tick_count(), 1);
+    }
+
+    #[test]
+    fn test_recording_skips_discarded_ticks() {
+        let control = SimulationControl::new();
+        let recorder: Recorder<u64, ()> = Recorder::new();
+        let mut driver = driver::<PanicAt>(2)
+            .with_panic_policy(PanicPolicy::Rollback)
+            .with_recorder(recorder.clone());
+
+        for _ in 0..3 {
+            assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        }
+        assert_eq!(driver.tick_count(), 2);
+        assert_eq!(recorder.recording().len(), 2);
+
+        driver.cancellation.cancel();
+        driver.tick(Duration::ZERO).unwrap();
+        assert_eq!(recorder.recording().len(), 2);
+    }
+
+    #[test]
+    fn test_idle_simulation_waits_for_events() {
+        let (gui_sender, sim_receiver) = MessageChannel::new(10).split();
+        let (sim_sender, _gui_receiver) = MessageChannel::new(10).split();
+        let simulation_data: Shared<u64> = Shared::new(0);
+        let gui_data: Shared<u64> = Shared::new(3);
+        let seeds = SeedFactory::new(0);
+        let mut driver = SimulationDriver::new(
+            IdleAfter::new(3, seeds).unwrap(),
+            seeds,
+            simulation_data.clone(),
+            gui_data.clone(),
+            sim_receiver,
+            sim_sender,
+        );
+
+        let control = SimulationControl::new();
+        let thread = {
+            let control = control.clone();
+            thread::spawn(move || {
+                driver.run(Schedule::Variable, Duration::from_millis(1), &control)
+            })
+        };
+        let wait_for = |expected: u64| {
+            while **simulation_data.load() < expected {
+                thread::sleep(Duration::from_millis(1));
+            }
+            thread::sleep(Duration::from_millis(50));
+            assert_eq!(**simulation_data.load(), expected);
+        };
+
+        wait_for(3);
+        gui_sender.send(()).unwrap();
+        wait_for(4);
+        gui_data.store(3);
+        wait_for(5);
+
+        control.stop();
+        thread.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_stats_are_published() {
+        let stats = Shared::new(RuntimeStats::default());
+        let mut driver = driver::<FailAt>(u64::MAX).with_stats(stats.clone());
+
+        driver.tick(Duration::from_millis(1)).unwrap();
+        driver.tick(Duration::from_millis(1)).unwrap();
+
+        let published: RuntimeStats = **stats.load();
+        assert_eq!(published.tick, 1);
+        assert_eq!(published.overruns, 0);
+        assert_eq!(published.gui_to_simulation.capacity, Some(1));
+        assert_eq!(published.simulation_to_gui.pending, 0);
+    }
+
+    fn wait_for_failure(control: &SimulationControl) -> SimulationFailure {
+        loop {
+            if let Some(failure) = control.failure() {
+                return failure;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_failure_is_reported_and_returned() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<FailAt>(3);
+
+        let simulation_thread = {
+            let control = control.clone();
+            thread::spawn(move || {
+                driver.run(Schedule::fixed(1_000), Duration::from_millis(1), &control)
+            })
+        };
+
+        let failure = wait_for_failure(&control);
+        assert_eq!(failure.tick, 3);
+        assert_eq!(failure.message, "GUI error: boom");
+
+        control.stop();
+        let error = simulation_thread.join().unwrap().unwrap_err();
+        assert!(matches!(error, Error::SimulationFailed { tick: 3, .. }));
+    }
+
+    #[test]
+    fn test_restart_clears_failure() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<FailAt>(0);
+
+        let simulation_thread = {
+            let control = control.clone();
+            thread::spawn(move || {
+                let result = driver.run(Schedule::Variable, Duration::from_millis(1), &control);
+                (result, driver.tick_count())
+            })
+        };
+
+        wait_for_failure(&control);
+        control.pause();
+        control.restart();
+        while control.failure().is_some() {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        control.stop();
+        let (result, tick) = simulation_thread.join().unwrap();
+        assert!(result.is_ok());
+        assert_eq!(tick, 0);
+    }
+
+    #[test]
+    fn test_panic_stops_by_default() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<PanicAt>(2);
+
+        assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        let error = driver.guarded_tick(Duration::ZERO, &control).unwrap();
+
+        assert!(matches!(error, Error::SimulationPanic(_)));
+        let failure = control.failure().unwrap();
+        assert_eq!(failure.tick, 1);
+        assert_eq!(failure.message, "panic at 2");
+        assert!(failure.location.unwrap().starts_with(file!()));
+    }
+
+    #[test]
+    fn test_panic_restart_policy() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<PanicAt>(2).with_panic_policy(PanicPolicy::Restart);
+
+        for _ in 0..3 {
+            assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        }
+
+        assert_eq!(control.failure(), None);
+        assert_eq!(control.recovery_count(), 1);
+        assert_eq!(
+            control.last_recovery().unwrap().action,
+            RecoveryAction::Restarted
+        );
+        assert_eq!(driver.tick_count(), 1);
+    }
+
+    #[test]
+    fn test_panic_during_shutdown_is_caught() {
+        #[derive(Debug)]
+        struct PanicTwice {
+            panicked: bool,
+        }
+
+        impl MultiAgentSimulation for PanicTwice {
+            const FREQUENCY_IN_HZ: u64 = 1_000;
+
+            type SimulationData = u64;
+            type GuiData = u64;
+            type MessageFromGui = ();
+            type MessageToGui = ();
+
+            fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+                Ok(Self { panicked: false })
+            }
+
+            fn update<F>(
+                &mut self,
+                _gui_data: Self::GuiData,
+                _messages: Vec<Self::MessageFromGui>,
+                _delta_time: Duration,
+                _send_message_to_gui: F,
+                _cancellation: &CancellationToken,
+            ) -> Result<&Self::SimulationData>
+            where
+                F: Fn(Self::MessageToGui),
+            {
+                self.panicked = true;
+                panic!("panic in update");
+            }
+
+            fn on_shutdown(&mut self) {
+                assert!(!self.panicked, "panic in on_shutdown");
+            }
+        }
+
+        let control = SimulationControl::new();
+        let mut driver = driver::<PanicTwice>(0).with_panic_policy(PanicPolicy::Restart);
+
+        assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        assert_eq!(control.recovery_count(), 1);
+
+        assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        assert_eq!(control.recovery_count(), 2);
+        drop(driver);
+    }
+
+    #[test]
+    fn test_panic_rollback_policy() {
+        let control = SimulationControl::new();
+        let mut driver = driver::<PanicAt>(3).with_panic_policy(PanicPolicy::Rollback);
+
+        for _ in 0..4 {
+            assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        }
+
+        assert_eq!(control.recovery_count(), 1);
+        assert_eq!(control.last_recovery().unwrap().failure.tick, 2);
+        assert_eq!(driver.tick_count(), 3);
+        assert_eq!(**driver.simulation_data.load(), 3);
+    }
+
+    #[test]
+    fn test_roll_back_without_restore() {
+        let mut driver = driver::<FailAt>(5);
+
+        assert!(matches!(driver.roll_back(), Err(Error::RestoreUnsupported)));
+    }
+
+    #[test]
+    fn test_timeline_rewind() {
+        let control = SimulationControl::new();
+        let timeline = Timeline::new(3);
+        let mut driver = driver::<PanicAt>(u64::MAX).with_timeline(timeline.clone());
+
+        for _ in 0..5 {
+            assert!(driver.guarded_tick(Duration::ZERO, &control).is_none());
+        }
+        assert_eq!(timeline.ticks(), Some(2..=4));
+
+        driver.rewind(3).unwrap();
+        assert_eq!(driver.tick_count(), 4);
+        assert_eq!(**driver.simulation_data.load(), 4);
+        assert_eq!(timeline.ticks(), Some(2..=3));
+
+        driver.tick(Duration::ZERO).unwrap();
+        assert_eq!(*timeline.get(4).unwrap(), 5);
+
+        assert!(matches!(
+            driver.rewind(0),
+            Err(Error::FrameUnavailable { tick: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_rewind_drops_the_replay() {
+        let inputs = TickInputs {
+            delta_time: Duration::from_millis(1),
+            gui_data: None,
+            messages: Vec::new(),
+        };
+        let mut driver = driver::<PanicAt>(u64::MAX)
+            .with_timeline(Timeline::new(3))
+            .with_replay(vec![inputs; 10]);
+
+        for _ in 0..3 {
+            driver.tick(Duration::ZERO).unwrap();
+        }
+        assert!(driver.replay.is_some());
+
+        driver.rewind(1).unwrap();
+        assert!(driver.replay.is_none());
+    }
+
+    #[test]
+    fn test_timeline_of_published_deltas() {
+        let (sim_sender, _) = MessageChannel::new(1).split();
+        let (_, sim_receiver) = MessageChannel::new(1).split();
+        let seeds = SeedFactory::new(0);
+        let simulation_data: Shared<Steps> = Shared::new(Steps::default());
+        let buffer = DiffBuffer::new(simulation_data.clone());
+        let feed = buffer.feed();
+        let timeline = Timeline::new(4);
+        let mut driver = SimulationDriver::new(
+            Stepper::new((), seeds).unwrap(),
+            seeds,
+            simulation_data.clone(),
+            Shared::new(()),
+            sim_receiver,
+            sim_sender,
+        )
+        .with_publisher(Box::new(buffer))
+        .with_timeline(timeline.clone());
+
+        for _ in 0..6 {
+            driver.tick(Duration::ZERO).unwrap();
+        }
+        assert!(matches!(feed.latest(), Some((6, Change::Delta(_)))));
+        assert_eq!(timeline.ticks(), Some(2..=5));
+        assert_eq!(*timeline.get(3).unwrap(), Steps(4));
+        assert_eq!(*timeline.get(5).unwrap(), Steps(6));
+
+        driver.rewind(3).unwrap();
+        assert_eq!(feed.version(), 7);
+        assert_eq!(**simulation_data.load(), Steps(4));
+
+        driver.tick(Duration::ZERO).unwrap();
+        assert_eq!(timeline.ticks(), Some(2..=4));
+        assert_eq!(*timeline.get(4).unwrap(), Steps(5));
+    }
+
+    #[test]
+    fn test_open_session() {
+        let mut saved = driver::<PanicAt>(u64::MAX);
+        for _ in 0..3 {
+            saved.tick(Duration::ZERO).unwrap();
+        }
+        let session = saved.session();
+        assert_eq!(session, Session::new(0, 3, u64::MAX, 3));
+
+        let mut other = driver::<PanicAt>(u64::MAX);
+        other.open_session(Session::new(9, 3, 5, 3)).unwrap();
+        assert_eq!(other.tick_count(), 3);
+        assert_eq!(other.seeds(), SeedFactory::new(9));
+        assert_eq!(**other.gui_data.load(), 5);
+
+        other.tick(Duration::ZERO).unwrap();
+        assert_eq!(**other.simulation_data.load(), 4);
+
+        assert!(matches!(
+            driver::<FailAt>(5).open_session(session),
+            Err(Error::RestoreUnsupported)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_session_requests() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-runtime-test-session-{}.json",
+            std::process::id()
+        ));
+        let control = SimulationControl::new();
+        let mut saved = driver::<PanicAt>(u64::MAX).with_sessions(SessionFiles::json());
+        for _ in 0..3 {
+            saved.tick(Duration::ZERO).unwrap();
+        }
+
+        control.save_session(&path);
+        assert!(!saved.sync_sessions(&control));
+        assert_eq!(
+            control.session_status(),
+            Some(SessionStatus::Saved {
+                path: path.clone(),
+                tick: 3
+            })
+        );
+
+        let mut other = driver::<PanicAt>(u64::MAX).with_sessions(SessionFiles::json());
+        control.open_session(&path);
+        assert!(other.sync_sessions(&control));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(other.tick_count(), 3);
+        assert_eq!(**other.simulation_data.load(), 3);
+        assert_eq!(control.seed(), 0);
+
+        control.open_session(&path);
+        assert!(!other.sync_sessions(&control));
+        assert!(matches!(
+            control.session_status(),
+            Some(SessionStatus::Failed { .. })
+        ));
+    }
+}
@@ -0,0 +1,583 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "scripting")]
+use crate::Scenario;
+use crate::{
+    driver::SimulationDriver, schedule::period_of, Publication, Recorder, Recording, TickStats,
+};
+#[cfg(feature = "http")]
+use crate::{HttpObserver, HttpServer};
+use multi_agent_core::{MultiAgentSimulation, Result, SeedFactory, Session};
+use multi_agent_sync::{
+    message::{MessageChannel, MessageReceiver, MessageSender},
+    RuntimeStats, Shared,
+};
+use std::{
+    collections::BTreeMap,
+    mem,
+    time::{Duration, Instant},
+};
+
+/// Outcome of a headless run.
+#[derive(Debug, Clone)]
+pub struct HeadlessReport<T> {
+    /// Simulation data published by the last executed tick.
+    pub data: T,
+    /// Statistics of every tick executed during the run, in order.
+    pub ticks: Vec<TickStats>,
+    /// Wall-clock duration of the run.
+    pub elapsed: Duration,
+}
+
+/// Runs a multi-agent simulation without any window.
+///
+/// The runner wires the simulation with the same shared state and message
+/// channels as the windowed runtime, but nobody sits on the GUI side: the
+/// caller plays that role. GUI data can be changed through [`gui_data`],
+/// messages can be sent right away through [`sender`] or scheduled for a
+/// given tick with [`schedule_message`], and messages emitted by the
+/// simulation can be read back through [`receiver`].
+///
+/// Ticks are executed back to back with a fixed time step, `1 / FREQUENCY_IN_HZ`
+/// seconds unless set with [`with_time_step`], so a run does not depend on the
+/// speed of the machine.
+///
+/// [`gui_data`]: HeadlessRunner::gui_data
+/// [`sender`]: HeadlessRunner::sender
+/// [`schedule_message`]: HeadlessRunner::schedule_message
+/// [`receiver`]: HeadlessRunner::receiver
+/// [`with_time_step`]: HeadlessRunner::with_time_step
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::HeadlessRunner;
+///
+/// let mut runner = HeadlessRunner::<MySimulation>::new(SimulationConfig::default())?;
+/// runner.schedule_message(10, GuiMessage::Reset);
+///
+/// let report = runner.run_ticks(1_000)?;
+/// println!("{} agents after {:?}", report.data.len(), report.elapsed);
+/// ```
+#[derive(Debug)]
+pub struct HeadlessRunner<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    driver: SimulationDriver<Simulation>,
+    simulation_data: Shared<Simulation::SimulationData>,
+    gui_data: Shared<Simulation::GuiData>,
+    sender: MessageSender<Simulation::MessageFromGui>,
+    receiver: MessageReceiver<Simulation::MessageToGui>,
+    stats: Shared<RuntimeStats>,
+    scheduled_messages: BTreeMap<u64, Vec<Simulation::MessageFromGui>>,
+    time_step: Duration,
+}
+
+impl<Simulation> HeadlessRunner<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    /// Capacity of the message channels in both directions.
+    const CHANNEL_CAPACITY: usize = 100;
+
+    /// Create the simulation with the given GUI data and a random seed, and wire it up.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::new`.
+    #[inline]
+    pub fn new(initial_gui_data: Simulation::GuiData) -> Result<Self> {
+        Self::new_seeded(initial_gui_data, SeedFactory::random().master())
+    }
+
+    /// Create the simulation with the given GUI data and master seed, and wire it up.
+    ///
+    /// Two runners created with the same seed and fed the same inputs produce
+    /// the same data, provided the simulation only draws from the seeds it is given.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::new`.
+    pub fn new_seeded(initial_gui_data: Simulation::GuiData, seed: u64) -> Result<Self> {
+        let simulation_data = Shared::new(Simulation::SimulationData::default());
+        let gui_data = Shared::new(initial_gui_data.clone());
+
+        let (sim_sender, receiver) = MessageChannel::new(Self::CHANNEL_CAPACITY).split();
+        let (sender, sim_receiver) = MessageChannel::new(Self::CHANNEL_CAPACITY).split();
+
+        let stats = Shared::new(RuntimeStats::default());
+
+        let seeds = SeedFactory::new(seed);
+        let simulation = Simulation::new(initial_gui_data, seeds)?;
+
+        Ok(Self::from_parts(
+            SimulationDriver::new(
+                simulation,
+                seeds,
+                simulation_data.clone(),
+                gui_data.clone(),
+                sim_receiver,
+                sim_sender,
+            )
+            .with_stats(stats.clone()),
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            stats,
+        ))
+    }
+
+    /// Take over a driver wired by the runtime builder.
+    pub(crate) const fn from_parts(
+        driver: SimulationDriver<Simulation>,
+        simulation_data: Shared<Simulation::SimulationData>,
+        gui_data: Shared<Simulation::GuiData>,
+        sender: MessageSender<Simulation::MessageFromGui>,
+        receiver: MessageReceiver<Simulation::MessageToGui>,
+        stats: Shared<RuntimeStats>,
+    ) -> Self {
+        Self {
+            driver,
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            stats,
+            scheduled_messages: BTreeMap::new(),
+            time_step: Self::delta_time(),
+        }
+    }
+
+    /// Replay a recording in a fresh simulation and return the outcome of its last tick.
+    ///
+    /// The simulation is created with the seed and initial GUI data of the recording,
+    /// then every recorded tick is executed with its time step, GUI data and messages.
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::new` or the first
+    /// error produced by `MultiAgentSimulation::update`.
+    pub fn replay(
+        recording: Recording<Simulation::GuiData, Simulation::MessageFromGui>,
+    ) -> Result<HeadlessReport<Simulation::SimulationData>> {
+        let ticks: u64 = recording.len();
+        let mut runner = Self::new_seeded(recording.initial_gui_data, recording.seed)?;
+        runner.driver = runner.driver.with_replay(recording.ticks);
+        runner.run_ticks(ticks)
+    }
+
+    /// Record the inputs of every following tick with `recorder`.
+    #[inline]
+    #[must_use]
+    pub fn with_recorder(
+        mut self,
+        recorder: Recorder<Simulation::GuiData, Simulation::MessageFromGui>,
+    ) -> Self {
+        self.driver = self.driver.with_recorder(recorder);
+        self
+    }
+
+    /// Run the scripts of `scenario` before every following tick.
+    #[cfg(feature = "scripting")]
+    #[inline]
+    #[must_use]
+    pub fn with_scenario(
+        mut self,
+        scenario: Scenario<
+            Simulation::SimulationData,
+            Simulation::GuiData,
+            Simulation::MessageFromGui,
+        >,
+    ) -> Self {
+        self.driver = self.driver.with_scenario(scenario);
+        self
+    }
+
+    /// Set how every following tick publishes its data.
+    #[inline]
+    #[must_use]
+    pub fn with_publication(mut self, publication: Publication) -> Self {
+        self.driver = self.driver.with_publication(publication);
+        self
+    }
+
+    /// Pass `time_step` to `update()` on every following tick.
+    ///
+    /// Default: [`delta_time`](Self::delta_time)
+    #[inline]
+    #[must_use]
+    pub const fn with_time_step(mut self, time_step: Duration) -> Self {
+        self.time_step = time_step;
+        self
+    }
+
+    /// Shared GUI data read by the simulation on every tick.
+    #[inline]
+    pub const fn gui_data(&self) -> &Shared<Simulation::GuiData> {
+        &self.gui_data
+    }
+
+    /// Shared simulation data published after every tick.
+    #[inline]
+    pub const fn simulation_data(&self) -> &Shared<Simulation::SimulationData> {
+        &self.simulation_data
+    }
+
+    /// Sender delivering messages to the simulation on its next tick.
+    #[inline]
+    pub const fn sender(&self) -> &MessageSender<Simulation::MessageFromGui> {
+        &self.sender
+    }
+
+    /// Receiver of the messages sent by the simulation to the GUI.
+    ///
+    /// Nobody drains this channel during a run: messages past its capacity are dropped.
+    #[inline]
+    pub const fn receiver(&self) -> &MessageReceiver<Simulation::MessageToGui> {
+        &self.receiver
+    }
+
+    /// Runtime statistics published after every tick.
+    #[inline]
+    pub const fn stats(&self) -> &Shared<RuntimeStats> {
+        &self.stats
+    }
+
+    /// Serve the shared state of this runner with `observer` until the returned server is dropped.
+    ///
+    /// # Errors
+    /// Returns `Error::Http` if the port of `observer` cannot be bound.
+    #[cfg(feature = "http")]
+    #[inline]
+    pub fn observe(
+        &self,
+        observer: HttpObserver<
+            Simulation::SimulationData,
+            Simulation::GuiData,
+            Simulation::MessageFromGui,
+        >,
+    ) -> Result<HttpServer>
+    where
+        Simulation::SimulationData: 'static,
+        Simulation::GuiData: 'static,
+    {
+        observer.start(
+            self.simulation_data.clone(),
+            self.gui_data.clone(),
+            self.sender.clone(),
+            self.stats.clone(),
+        )
+    }
+
+    /// Master seed handed to the simulation.
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.driver.seeds().master()
+    }
+
+    /// Number of ticks executed so far, across all runs.
+    #[inline]
+    pub const fn tick_count(&self) -> u64 {
+        self.driver.tick_count()
+    }
+
+    /// Default time step passed to `update()` on every tick.
+    #[inline]
+    #[must_use]
+    pub const fn delta_time() -> Duration {
+        period_of(Simulation::FREQUENCY_IN_HZ)
+    }
+
+    /// Deliver a message to the simulation at the start of the given tick.
+    ///
+    /// Messages scheduled for a tick that has already run are delivered on the next tick.
+    #[inline]
+    pub fn schedule_message(&mut self, tick: u64, message: Simulation::MessageFromGui) {
+        self.scheduled_messages
+            .entry(tick)
+            .or_default()
+            .push(message);
+    }
+
+    /// Replace the simulation with a new instance created from the current GUI data
+    /// and the same seed.
+    ///
+    /// The tick counter starts again from 0 and messages sent through [`sender`]
+    /// and not delivered yet are discarded. Scheduled messages are kept.
+    ///
+    /// [`sender`]: HeadlessRunner::sender
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::new`.
+    #[inline]
+    pub fn restart(&mut self) -> Result<()> {
+        self.driver.restart(self.driver.seeds())
+    }
+
+    /// Snapshot of the current GUI data, published data, tick count and master seed.
+    ///
+    /// With the `serde` feature, save it with `Session::save` to open it later in a window.
+    #[inline]
+    #[must_use]
+    pub fn session(&self) -> Session<Simulation::GuiData, Simulation::SimulationData> {
+        self.driver.session()
+    }
+
+    /// Replace the simulation with an instance restored from `session`, and continue
+    /// from its tick with its GUI data and master seed.
+    ///
+    /// Messages sent through [`sender`] and not delivered yet are discarded.
+    /// Scheduled messages are kept.
+    ///
+    /// [`sender`]: HeadlessRunner::sender
+    ///
+    /// # Errors
+    /// Returns the error produced by `MultiAgentSimulation::restore_session`.
+    #[inline]
+    pub fn open_session(
+        &mut self,
+        session: Session<Simulation::GuiData, Simulation::SimulationData>,
+    ) -> Result<()> {
+        self.driver.open_session(session)
+    }
+
+    /// Run exactly `ticks` ticks.
+    ///
+    /// # Errors
+    /// Returns the first error produced by `MultiAgentSimulation::update`.
+    pub fn run_ticks(&mut self, ticks: u64) -> Result<HeadlessReport<Simulation::SimulationData>> {
+        let last_tick: u64 = self.tick_count() + ticks;
+        self.run_while(|runner, _| runner.tick_count() < last_tick)
+    }
+
+    /// Run until `predicate` holds for the published simulation data.
+    ///
+    /// The predicate is checked after every tick, so at least one tick is always executed.
+    ///
+    /// # Errors
+    /// Returns the first error produced by `MultiAgentSimulation::update`.
+    pub fn run_until<P>(
+        &mut self,
+        mut predicate: P,
+    ) -> Result<HeadlessReport<Simulation::SimulationData>>
+    where
+        P: FnMut(&Simulation::SimulationData) -> bool,
+    {
+        let mut first_tick: bool = true;
+        self.run_while(|runner, _| {
+            mem::take(&mut first_tick) || !predicate(&runner.simulation_data.load())
+        })
+    }
+
+    /// Run until the given wall-clock budget has been spent.
+    ///
+    /// The budget is checked between ticks, so the last tick may end slightly past it.
+    ///
+    /// # Errors
+    /// Returns the first error produced by `MultiAgentSimulation::update`.
+    pub fn run_for(
+        &mut self,
+        budget: Duration,
+    ) -> Result<HeadlessReport<Simulation::SimulationData>> {
+        self.run_while(|_, elapsed| elapsed < budget)
+    }
+
+    fn run_while<C>(
+        &mut self,
+        mut keep_running: C,
+    ) -> Result<HeadlessReport<Simulation::SimulationData>>
+    where
+        C: FnMut(&Self, Duration) -> bool,
+    {
+        let delta_time: Duration = self.time_step;
+        let mut ticks: Vec<TickStats> = Vec::new();
+
+        let start = Instant::now();
+        while keep_running(self, start.elapsed()) {
+            self.deliver_scheduled_messages();
+            ticks.push(self.driver.tick(delta_time)?);
+        }
+
+        Ok(HeadlessReport {
+            data: (**self.simulation_data.load()).clone(),
+            ticks,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    fn deliver_scheduled_messages(&mut self) {
+        let tick: u64 = self.tick_count();
+        while let Some(entry) = self.scheduled_messages.first_entry() {
+            if *entry.key() > tick {
+                break;
+            }
+            for message in entry.remove() {
+                self.sender.send_lossy(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::CancellationToken;
+
+    #[derive(Debug)]
+    struct Counter {
+        total: u64,
+    }
+
+    impl MultiAgentSimulation for Counter {
+        const FREQUENCY_IN_HZ: u64 = 50;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = u64;
+        type MessageToGui = u64;
+
+        fn new(initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                total: initial_gui_data,
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            gui_data: Self::GuiData,
+            messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.total += gui_data + messages.iter().sum::<u64>();
+            send_message_to_gui(self.total);
+            Ok(&self.total)
+        }
+    }
+
+    #[test]
+    fn test_run_ticks() {
+        let mut runner = HeadlessRunner::<Counter>::new(1).unwrap();
+
+        let report = runner.run_ticks(10).unwrap();
+
+        assert_eq!(report.data, 11);
+        assert_eq!(report.ticks.len(), 10);
+        assert_eq!(report.ticks[9].tick, 9);
+        assert_eq!(report.ticks[0].delta_time, Duration::from_millis(20));
+        assert_eq!(runner.tick_count(), 10);
+        assert_eq!(runner.receiver().drain().len(), 10);
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut runner = HeadlessRunner::<Counter>::new(0).unwrap();
+        runner.gui_data().store(2);
+
+        let report = runner.run_until(|total| *total >= 7).unwrap();
+
+        assert_eq!(report.data, 8);
+        assert_eq!(report.ticks.len(), 4);
+    }
+
+    #[test]
+    fn test_run_for() {
+        let mut runner = HeadlessRunner::<Counter>::new(0).unwrap();
+
+        let report = runner.run_for(Duration::from_millis(10)).unwrap();
+
+        assert!(report.elapsed >= Duration::from_millis(10));
+        assert_eq!(report.ticks.len() as u64, runner.tick_count());
+    }
+
+    #[test]
+    fn test_restart_uses_current_gui_data() {
+        let mut runner = HeadlessRunner::<Counter>::new(1).unwrap();
+        runner.run_ticks(5).unwrap();
+
+        runner.gui_data().store(10);
+        runner.sender().send(1_000).unwrap();
+        runner.restart().unwrap();
+        assert_eq!(runner.tick_count(), 0);
+
+        let report = runner.run_ticks(1).unwrap();
+        assert_eq!(report.data, 20);
+        assert_eq!(report.ticks[0].messages, 0);
+    }
+
+    #[test]
+    fn test_scheduled_messages() {
+        let mut runner = HeadlessRunner::<Counter>::new(0).unwrap();
+        runner.schedule_message(2, 100);
+        runner.schedule_message(2, 10);
+        runner.sender().send(1).unwrap();
+
+        let report = runner.run_ticks(2).unwrap();
+        assert_eq!(report.data, 1);
+        assert_eq!(report.ticks[0].messages, 1);
+
+        let report = runner.run_ticks(1).unwrap();
+        assert_eq!(report.data, 111);
+        assert_eq!(report.ticks[0].messages, 2);
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let recorder = Recorder::new();
+        let mut runner = HeadlessRunner::<Counter>::new(1)
+            .unwrap()
+            .with_recorder(recorder.clone());
+        runner.schedule_message(3, 100);
+        runner.run_ticks(5).unwrap();
+        runner.gui_data().store(7);
+        let report = runner.run_ticks(5).unwrap();
+
+        let recording = recorder.recording();
+        assert_eq!(recording.seed, runner.seed());
+        assert_eq!(recording.len(), 10);
+        assert_eq!(recording.ticks[3].messages, vec![100]);
+        assert_eq!(recording.ticks[5].gui_data, Some(7));
+
+        let replayed = HeadlessRunner::<Counter>::replay(recording).unwrap();
+        assert_eq!(replayed.data, report.data);
+        assert_eq!(replayed.ticks.len(), 10);
+    }
+
+    #[test]
+    fn test_replay_ignores_live_gui_data() {
+        let recorder = Recorder::new();
+        let mut runner = HeadlessRunner::<Counter>::new(1)
+            .unwrap()
+            .with_recorder(recorder.clone());
+        runner.run_ticks(3).unwrap();
+        runner.gui_data().store(7);
+        let report = runner.run_ticks(3).unwrap();
+
+        let recording = recorder.recording();
+        let mut replayed =
+            HeadlessRunner::<Counter>::new_seeded(recording.initial_gui_data, recording.seed)
+                .unwrap();
+        replayed.driver = replayed.driver.with_replay(recording.ticks);
+        replayed.run_ticks(4).unwrap();
+        replayed.gui_data().store(1_000);
+
+        assert_eq!(replayed.run_ticks(2).unwrap().data, report.data);
+    }
+}
@@ -0,0 +1,606 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use multi_agent_core::{Error, Result};
+use multi_agent_sync::{message::MessageSender, ChannelFill, RuntimeStats, Shared};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug, Formatter, Write as _},
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Metric computed from the simulation data on every request.
+type Metric<SimulationData> = Arc<dyn Fn(&SimulationData) -> f64 + Send + Sync>;
+
+/// Local HTTP endpoint to observe a running simulation from dashboards and scripts.
+///
+/// The endpoint only listens on `127.0.0.1` and serves:
+/// - `GET /api/stats`: the latest runtime statistics, as JSON
+/// - `GET /api/gui-data`: the current GUI data, as JSON
+/// - `GET /api/metrics`: the registered metrics, as a JSON object
+/// - `POST /api/messages`: a message for the simulation, as JSON in the body, sent
+///   with `Content-Type: application/json` and without a foreign `Origin`, so web
+///   pages open in a browser cannot post messages
+/// - `GET /metrics`: the runtime statistics and the registered metrics in the
+///   Prometheus text format
+///
+/// Give it to `RuntimeBuilder::observe` for a windowed application, or start it
+/// with `HeadlessRunner::observe` for a headless run.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::{AppLauncher, HttpObserver};
+///
+/// AppLauncher::builder()
+///     .observe(
+///         HttpObserver::new(9100)
+///             .metric("agents", |data: &PhysarumData| data.agents.len() as f64),
+///     )
+///     .run::<Physarum, PhysarumGui>()
+/// ```
+///
+/// ```text
+/// $ curl localhost:9100/api/metrics
+/// {"agents":50000.0}
+/// $ curl -H 'Content-Type: application/json' -d '"Reset"' localhost:9100/api/messages
+/// ```
+pub struct HttpObserver<SimulationData, GuiData, Message> {
+    port: u16,
+    metrics: Vec<(String, Metric<SimulationData>)>,
+    gui_data_to_json: fn(&GuiData) -> serde_json::Result<String>,
+    message_from_json: fn(&[u8]) -> serde_json::Result<Message>,
+}
+
+impl<SimulationData, GuiData, Message> HttpObserver<SimulationData, GuiData, Message> {
+    /// Create an endpoint listening on `127.0.0.1:port`, without any metric.
+    ///
+    /// Use port 0 to let the system pick a free port, then read it back with
+    /// [`HttpServer::port`].
+    #[inline]
+    #[must_use]
+    pub fn new(port: u16) -> Self
+    where
+        GuiData: Serialize,
+        Message: DeserializeOwned,
+    {
+        Self {
+            port,
+            metrics: Vec::new(),
+            gui_data_to_json: |gui_data| serde_json::to_string(gui_data),
+            message_from_json: |body| serde_json::from_slice(body),
+        }
+    }
+
+    /// Expose the metric `name`, computed from the latest simulation data on every request.
+    #[inline]
+    #[must_use]
+    pub fn metric<S, F>(mut self, name: S, metric: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&SimulationData) -> f64 + Send + Sync + 'static,
+    {
+        self.metrics.push((name.into(), Arc::new(metric)));
+        self
+    }
+
+    /// Start serving the given shared state on a background thread.
+    ///
+    /// The endpoint stops when the returned server is dropped.
+    ///
+    /// # Errors
+    /// Returns `Error::Http` if the port cannot be bound.
+    pub fn start(
+        self,
+        simulation_data: Shared<SimulationData>,
+        gui_data: Shared<GuiData>,
+        sender: MessageSender<Message>,
+        stats: Shared<RuntimeStats>,
+    ) -> Result<HttpServer>
+    where
+        SimulationData: Send + Sync + 'static,
+        GuiData: Send + Sync + 'static,
+        Message: Send + 'static,
+    {
+        let server = Server::http(SocketAddr::from((Ipv4Addr::LOCALHOST, self.port)))
+            .map_err(|err| Error::Http(format!("Cannot bind port {}: {err}", self.port)))?;
+        let server = Arc::new(server);
+        let port: u16 = server
+            .server_addr()
+            .to_ip()
+            .map_or(self.port, |address| address.port());
+        log::info!("Observation endpoint listening on http://127.0.0.1:{port}");
+
+        let thread: JoinHandle<()> = {
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let response: Response<_> = self.respond(
+                        &mut request,
+                        port,
+                        &simulation_data,
+                        &gui_data,
+                        &sender,
+                        &stats,
+                    );
+                    if let Err(error) = request.respond(response) {
+                        log::debug!("Cannot answer an HTTP request: {error}");
+                    }
+                }
+            })
+        };
+
+        Ok(HttpServer {
+            port,
+            server,
+            thread: Some(thread),
+        })
+    }
+
+    fn respond(
+        &self,
+        request: &mut Request,
+        port: u16,
+        simulation_data: &Shared<SimulationData>,
+        gui_data: &Shared<GuiData>,
+        sender: &MessageSender<Message>,
+        stats: &Shared<RuntimeStats>,
+    ) -> Response<std::io::Cursor<Vec<u8>>> {
+        let path: &str = request.url().split('?').next().unwrap_or_default();
+        match (request.method(), path) {
+            (Method::Get, "/api/stats") => json(&stats_to_json(&stats.load()).to_string()),
+            (Method::Get, "/api/gui-data") => match (self.gui_data_to_json)(&gui_data.load()) {
+                Ok(body) => json(&body),
+                Err(err) => text(500, &err.to_string()),
+            },
+            (Method::Get, "/api/metrics") => {
+                let data = simulation_data.load();
+                let metrics: serde_json::Map<String, serde_json::Value> = self
+                    .metrics
+                    .iter()
+                    .map(|(name, metric)| (name.clone(), serde_json::Value::from(metric(&data))))
+                    .collect();
+                json(&serde_json::Value::Object(metrics).to_string())
+            }
+            (Method::Get, "/metrics") => {
+                let body: String = self.prometheus(&stats.load(), &simulation_data.load());
+                text(200, &body)
+            }
+            (Method::Post, "/api/messages") => {
+                if let Err(response) = check_local_json(request, port) {
+                    return response;
+                }
+                let mut body: Vec<u8> = Vec::new();
+                if let Err(err) = request.as_reader().read_to_end(&mut body) {
+                    return text(400, &err.to_string());
+                }
+                match (self.message_from_json)(&body) {
+                    Ok(message) => match sender.send(message) {
+                        Ok(()) => text(202, "Accepted"),
+                        Err(err) => text(503, &err.to_string()),
+                    },
+                    Err(err) => text(400, &err.to_string()),
+                }
+            }
+            _ => text(404, "Not Found"),
+        }
+    }
+
+    /// Runtime statistics and registered metrics in the Prometheus text format.
+    fn prometheus(&self, stats: &RuntimeStats, data: &SimulationData) -> String {
+        let mut body = String::new();
+        let mut family = |name: &str, kind: &str, help: &str, samples: &[(&str, f64)]| {
+            let _ = writeln!(body, "# HELP multi_agent_{name} {help}");
+            let _ = writeln!(body, "# TYPE multi_agent_{name} {kind}");
+            for (labels, value) in samples {
+                let _ = writeln!(body, "multi_agent_{name}{labels} {value}");
+            }
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let channel = |fill: ChannelFill| {
+            (
+                fill.pending as f64,
+                fill.capacity
+                    .map_or(f64::INFINITY, |capacity| capacity as f64),
+                fill.dropped as f64,
+            )
+        };
+        let (gui_pending, gui_capacity, gui_dropped) = channel(stats.gui_to_simulation);
+        let (simulation_pending, simulation_capacity, simulation_dropped) =
+            channel(stats.simulation_to_gui);
+
+        #[allow(clippy::cast_precision_loss)]
+        family(
+            "tick",
+            "counter",
+            "Index of the last executed tick.",
+            &[("", stats.tick as f64)],
+        );
+        family(
+            "update_seconds",
+            "gauge",
+            "Time spent inside update() during the last tick.",
+            &[("", stats.update_time.as_secs_f64())],
+        );
+        family(
+            "publish_seconds",
+            "gauge",
+            "Time spent publishing the simulation data during the last tick.",
+            &[("", stats.publish_time.as_secs_f64())],
+        );
+        family(
+            "achieved_hz",
+            "gauge",
+            "Ticks executed per wall-clock second.",
+            &[("", stats.achieved_hz)],
+        );
+        #[allow(clippy::cast_precision_loss)]
+        family(
+            "overruns_total",
+            "counter",
+            "Wake-ups of the simulation thread later than planned.",
+            &[("", stats.overruns as f64)],
+        );
+        family(
+            "channel_pending",
+            "gauge",
+            "Messages waiting in a channel.",
+            &[
+                ("{direction=\"gui_to_simulation\"}", gui_pending),
+                ("{direction=\"simulation_to_gui\"}", simulation_pending),
+            ],
+        );
+        family(
+            "channel_capacity",
+            "gauge",
+            "Capacity of a channel.",
+            &[
+                ("{direction=\"gui_to_simulation\"}", gui_capacity),
+                ("{direction=\"simulation_to_gui\"}", simulation_capacity),
+            ],
+        );
+        family(
+            "channel_dropped_total",
+            "counter",
+            "Messages refused or discarded by a channel.",
+            &[
+                ("{direction=\"gui_to_simulation\"}", gui_dropped),
+                ("{direction=\"simulation_to_gui\"}", simulation_dropped),
+            ],
+        );
+
+        if !self.metrics.is_empty() {
+            let labels: Vec<String> = self
+                .metrics
+                .iter()
+                .map(|(name, _)| format!("{{name=\"{}\"}}", label_value(name)))
+                .collect();
+            let samples: Vec<(&str, f64)> = labels
+                .iter()
+                .zip(&self.metrics)
+                .map(|(labels, (_, metric))| (labels.as_str(), metric(data)))
+                .collect();
+            family(
+                "metric",
+                "gauge",
+                "Metric registered on the observation endpoint.",
+                &samples,
+            );
+        }
+        body
+    }
+}
+
+impl<SimulationData, GuiData, Message> Clone for HttpObserver<SimulationData, GuiData, Message> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            port: self.port,
+            metrics: self.metrics.clone(),
+            gui_data_to_json: self.gui_data_to_json,
+            message_from_json: self.message_from_json,
+        }
+    }
+}
+
+impl<SimulationData, GuiData, Message> Debug for HttpObserver<SimulationData, GuiData, Message> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpObserver")
+            .field("port", &self.port)
+            .field(
+                "metrics",
+                &self
+                    .metrics
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<&String>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Running observation endpoint, stopped when dropped.
+pub struct HttpServer {
+    port: u16,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpServer {
+    /// Port the endpoint listens on.
+    #[inline]
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Debug for HttpServer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpServer")
+            .field("port", &self.port)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for HttpServer {
+    #[inline]
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn stats_to_json(stats: &RuntimeStats) -> serde_json::Value {
+    let channel = |fill: ChannelFill| {
+        serde_json::json!({
+            "pending": fill.pending,
+            "capacity": fill.capacity,
+            "dropped": fill.dropped,
+        })
+    };
+    serde_json::json!({
+        "tick": stats.tick,
+        "update_seconds": stats.update_time.as_secs_f64(),
+        "publish_seconds": stats.publish_time.as_secs_f64(),
+        "achieved_hz": stats.achieved_hz,
+        "overruns": stats.overruns,
+        "gui_to_simulation": channel(stats.gui_to_simulation),
+        "simulation_to_gui": channel(stats.simulation_to_gui),
+    })
+}
+
+/// Escape a Prometheus label value.
+fn label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Refuse a request that a web page could have sent from a browser: simple
+/// cross-origin requests cannot set a JSON content type, and DNS rebinding
+/// attacks come with a foreign `Host`.
+fn check_local_json(
+    request: &Request,
+    port: u16,
+) -> std::result::Result<(), Response<std::io::Cursor<Vec<u8>>>> {
+    let header = |name: &'static str| {
+        request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(name))
+            .map(|header| header.value.as_str())
+    };
+
+    let is_json: bool = header("Content-Type").is_some_and(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+    });
+    if !is_json {
+        return Err(text(415, "Expected Content-Type: application/json"));
+    }
+
+    let local_host: bool = header("Host").is_some_and(|host| is_local(host, port));
+    let local_origin: bool = header("Origin").is_none_or(|origin| {
+        origin
+            .strip_prefix("http://")
+            .is_some_and(|origin| is_local(origin, port))
+    });
+    if !local_host || !local_origin {
+        return Err(text(403, "Forbidden"));
+    }
+    Ok(())
+}
+
+/// Whether `authority`, as `host[:port]`, names this endpoint on the loopback interface.
+fn is_local(authority: &str, port: u16) -> bool {
+    let (host, authority_port): (&str, Option<&str>) = match authority.rsplit_once(':') {
+        Some((host, authority_port)) => (host, Some(authority_port)),
+        None => (authority, None),
+    };
+    matches!(host, "localhost" | "127.0.0.1")
+        && authority_port.is_none_or(|authority_port| authority_port == port.to_string())
+}
+
+fn json(body: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    with_content_type(Response::from_string(body), "application/json")
+}
+
+fn text(status: u16, body: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    with_content_type(
+        Response::from_string(body).with_status_code(status),
+        "text/plain; version=0.0.4; charset=utf-8",
+    )
+}
+
+fn with_content_type(
+    response: Response<std::io::Cursor<Vec<u8>>>,
+    content_type: &str,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    match Header::from_bytes("Content-Type", content_type) {
+        Ok(header) => response.with_header(header),
+        Err(()) => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_sync::message::{MessageChannel, MessageReceiver};
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    fn observe() -> (HttpServer, MessageReceiver<String>) {
+        let (sender, receiver) = MessageChannel::new(1).split();
+        let server = HttpObserver::<Vec<u64>, (u8, bool), String>::new(0)
+            .metric("agents", |data| data.len() as f64)
+            .metric("odd \"ones\"", |data| {
+                data.iter().filter(|value| *value % 2 == 1).count() as f64
+            })
+            .start(
+                Shared::new(vec![1, 2, 3]),
+                Shared::new((7, true)),
+                sender,
+                Shared::new(RuntimeStats {
+                    tick: 42,
+                    ..RuntimeStats::default()
+                }),
+            )
+            .unwrap();
+        (server, receiver)
+    }
+
+    fn request(server: &HttpServer, method: &str, path: &str, body: &str) -> (u16, String) {
+        let headers = format!(
+            "Host: localhost:{}\r\nContent-Type: application/json\r\n",
+            server.port()
+        );
+        request_with(server, method, path, &headers, body)
+    }
+
+    fn request_with(
+        server: &HttpServer,
+        method: &str,
+        path: &str,
+        headers: &str,
+        body: &str,
+    ) -> (u16, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\n{headers}Connection: close\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status: u16 = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, String::from(body))
+    }
+
+    #[test]
+    fn test_http_json_endpoints() {
+        let (server, _receiver) = observe();
+
+        let (code, body) = request(&server, "GET", "/api/stats", "");
+        let stats: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(code, 200);
+        assert_eq!(stats["tick"], 42);
+
+        assert_eq!(
+            request(&server, "GET", "/api/gui-data", ""),
+            (200, String::from("[7,true]"))
+        );
+
+        let (_, metrics) = request(&server, "GET", "/api/metrics?pretty", "");
+        let metrics: serde_json::Value = serde_json::from_str(&metrics).unwrap();
+        assert_eq!(metrics["agents"], 3.0);
+        assert_eq!(metrics["odd \"ones\""], 2.0);
+
+        assert_eq!(request(&server, "GET", "/nowhere", "").0, 404);
+    }
+
+    #[test]
+    fn test_http_prometheus_endpoint() {
+        let (server, _receiver) = observe();
+
+        let (status, body) = request(&server, "GET", "/metrics", "");
+
+        assert_eq!(status, 200);
+        assert!(body.contains("# TYPE multi_agent_tick counter\nmulti_agent_tick 42\n"));
+        assert!(body.contains("multi_agent_metric{name=\"agents\"} 3\n"));
+        assert!(body.contains("multi_agent_metric{name=\"odd \\\"ones\\\"\"} 2\n"));
+    }
+
+    #[test]
+    fn test_http_post_messages() {
+        let (server, receiver) = observe();
+
+        assert_eq!(
+            request(&server, "POST", "/api/messages", "\"reset\"").0,
+            202
+        );
+        assert_eq!(request(&server, "POST", "/api/messages", "reset").0, 400);
+        assert_eq!(
+            request(&server, "POST", "/api/messages", "\"again\"").0,
+            503
+        );
+        assert_eq!(receiver.drain(), vec![String::from("reset")]);
+    }
+
+    #[test]
+    fn test_http_post_messages_from_browsers_are_refused() {
+        let (server, receiver) = observe();
+        let port: u16 = server.port();
+        let send =
+            |headers: &str| request_with(&server, "POST", "/api/messages", headers, "\"x\"").0;
+
+        assert_eq!(send("Host: localhost\r\nContent-Type: text/plain\r\n"), 415);
+        assert_eq!(send("Host: localhost\r\n"), 415);
+        assert_eq!(
+            send("Host: evil.example\r\nContent-Type: application/json\r\n"),
+            403
+        );
+        assert_eq!(
+            send(&format!(
+                "Host: 127.0.0.1:{port}\r\nContent-Type: application/json\r\nOrigin: http://evil.example\r\n"
+            )),
+            403
+        );
+        assert!(receiver.is_empty());
+
+        assert_eq!(
+            send(&format!(
+                "Host: 127.0.0.1:{port}\r\nContent-Type: application/json; charset=utf-8\r\nOrigin: http://localhost:{port}\r\n"
+            )),
+            202
+        );
+    }
+}
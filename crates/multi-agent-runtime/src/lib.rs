@@ -14,6 +14,43 @@
  * limitations under the License.
  */
 
+mod batch;
+mod builder;
+#[cfg(feature = "cli")]
+mod cli;
+mod driver;
+mod headless;
+#[cfg(feature = "http")]
+mod http;
 mod manager;
+mod optimize;
+mod panic;
+mod publication;
+mod record;
+#[cfg(feature = "scripting")]
+mod scenario;
+#[cfg(feature = "remote")]
+mod remote;
+mod schedule;
+#[cfg(feature = "serde")]
+mod session;
+mod stats;
 
+pub use batch::{BatchReport, BatchRun, BatchRunner, MetricSample};
+pub use builder::RuntimeBuilder;
+#[cfg(feature = "cli")]
+pub use cli::{CommandLine, LaunchOptions, NoOptions};
+pub use headless::{HeadlessReport, HeadlessRunner};
+#[cfg(feature = "http")]
+pub use http::{HttpObserver, HttpServer};
 pub use manager::MultiAgentRuntimeManager;
+pub use optimize::{Candidate, GenerationSummary, OptimizationReport, Optimizer, ParameterBounds};
+pub use panic::PanicPolicy;
+pub use publication::Publication;
+pub use record::{Recorder, Recording, TickInputs};
+#[cfg(feature = "scripting")]
+pub use scenario::Scenario;
+#[cfg(feature = "remote")]
+pub use remote::{DeltaCodec, RemoteAddress, RemoteClient, RemoteServer};
+pub use schedule::{CatchUpPolicy, Schedule};
+pub use stats::TickStats;
@@ -14,18 +14,10 @@
  * limitations under the License.
  */
 
-use multi_agent_core::{Error, MultiAgentGui, MultiAgentSimulation, Result};
-use multi_agent_gui::AppGui;
-use multi_agent_sync::message::MessageChannel;
-use multi_agent_sync::Shared;
-use std::{
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-    thread,
-    time::{Duration, Instant},
-};
+use crate::RuntimeBuilder;
+use multi_agent_core::{MultiAgentGui, MultiAgentSimulation, Result};
+#[cfg(feature = "cli")]
+use serde::{de::DeserializeOwned, Serialize};
 
 /// Runtime manager that orchestrates the multi-agent simulation and GUI.
 ///
@@ -54,8 +46,30 @@ use std::{
 pub struct MultiAgentRuntimeManager;
 
 impl MultiAgentRuntimeManager {
+    /// Create a builder to configure the runtime before running it.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use multi_agent::AppLauncher;
+    ///
+    /// fn main() -> multi_agent::Result<()> {
+    ///     AppLauncher::builder()
+    ///         .channel_capacity(1_000)
+    ///         .initial_gui_data(SimulationConfig::with_agents(500))
+    ///         .run::<MySimulation, MyGui>()
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder<GuiData, MessageFromGui, SimulationData, MessageToGui>(
+    ) -> RuntimeBuilder<GuiData, MessageFromGui, SimulationData, MessageToGui> {
+        RuntimeBuilder::new()
+    }
+
     /// Run the multi-agent application with the given simulation and GUI implementations.
     ///
+    /// This is a shortcut for `builder().run()` with the default settings.
+    ///
     /// This method:
     /// 1. Creates shared state for simulation and GUI data
     /// 2. Sets up bidirectional message channels (capacity: 100)
@@ -70,7 +84,10 @@ impl MultiAgentRuntimeManager {
     /// The types must be compatible (same data and message types).
     ///
     /// # Returns
-    /// - `Ok(())` if the application runs and closes successfully
+    /// `Ok(())` if the application runs and closes successfully.
+    ///
+    /// # Errors
+    /// - `Err(Error::SimulationFailed)` if the simulation returned an error and was not restarted
     /// - `Err(Error::SimulationPanic)` if the simulation thread panics
     /// - `Err(Error::ShutdownTimeout)` if the simulation thread doesn't stop within 5 seconds
     /// - `Err(Error::Gui)` if the GUI framework returns an error
@@ -95,79 +112,49 @@ impl MultiAgentRuntimeManager {
             >,
         <Simulation as MultiAgentSimulation>::SimulationData: Send,
     {
-        let simulation_data = Shared::new(Simulation::SimulationData::default());
-        let gui_data = Shared::new(Gui::GuiData::default());
-
-        let (sim_sender, gui_receiver) = MessageChannel::new(100).split();
-        let (gui_sender, sim_receiver) = MessageChannel::new(100).split();
-
-        let gui: AppGui<Gui> = AppGui::new(
-            gui_sender,
-            gui_receiver,
-            gui_data.clone(),
-            simulation_data.clone(),
-        );
-
-        let mut simulation = Simulation::new(Gui::GuiData::default())?;
-
-        let stop_gui = Arc::new(AtomicBool::new(false));
-        let stop_simulator = Arc::clone(&stop_gui);
-
-        let simulation_thread = thread::spawn(move || {
-            let frequency = Duration::from_millis(1000 / Simulation::FREQUENCY_IN_HZ);
-
-            let mut delta = Instant::now();
-            loop {
-                if stop_simulator.load(Ordering::Relaxed) {
-                    break;
-                }
-
-                let now = Instant::now();
-                let delta_time = now.duration_since(delta);
-                delta = now;
-
-                let new_simulation_data = simulation.update(
-                    (**gui_data.load()).clone(),
-                    sim_receiver.drain(),
-                    delta_time,
-                    |message| {
-                        let _ = sim_sender.send(message);
-                    },
-                )?;
-                simulation_data.store(new_simulation_data.clone());
-
-                let now = Instant::now();
-                let duration = now.duration_since(delta);
-                if duration < frequency {
-                    thread::sleep(frequency - duration);
-                }
-            }
-
-            Ok(())
-        });
-
-        gui.run()?;
-        stop_gui.store(true, Ordering::Relaxed);
+        Self::builder().run::<Simulation, Gui>()
+    }
 
-        let timeout = Duration::from_secs(5);
-        let start = Instant::now();
-        loop {
-            if simulation_thread.is_finished() {
-                return simulation_thread.join().map_err(|e| {
-                    Error::SimulationPanic(format!("{:?}", e))
-                })?;
-            }
-            if start.elapsed() >= timeout {
-                return Err(Error::ShutdownTimeout { timeout });
-            }
-            thread::sleep(Duration::from_millis(10));
-        }
+    /// Run the multi-agent application configured from the command line.
+    ///
+    /// This is a shortcut for `builder().run_cli()`: see [`LaunchOptions`](crate::LaunchOptions)
+    /// for the accepted flags, and [`RuntimeBuilder::run_cli_with`] to add your own.
+    ///
+    /// # Errors
+    /// - `Err(Error::Config)` if the `--config` file cannot be loaded
+    /// - `Err(Error::Recording)` if the replay cannot be loaded or the recording cannot be saved
+    /// - Any error of [`run`](Self::run)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use multi_agent::AppLauncher;
+    ///
+    /// fn main() -> multi_agent::Result<()> {
+    ///     AppLauncher::run_cli::<MySimulation, MyGui>()
+    /// }
+    /// ```
+    #[cfg(feature = "cli")]
+    #[inline]
+    pub fn run_cli<Simulation, Gui>() -> Result<()>
+    where
+        Simulation: MultiAgentSimulation,
+        Gui: MultiAgentGui<
+                GuiData = Simulation::GuiData,
+                SimulationData = Simulation::SimulationData,
+                MessageFromSimulation = Simulation::MessageToGui,
+                MessageToSimulation = Simulation::MessageFromGui,
+            >,
+        Simulation::GuiData: Serialize + DeserializeOwned,
+        Simulation::MessageFromGui: Serialize + DeserializeOwned,
+    {
+        Self::builder().run_cli::<Simulation, Gui>()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::Schedule;
+    use std::time::Duration;
 
     #[test]
     fn test_frequency_calculation() {
@@ -184,4 +171,14 @@ mod tests {
         let expected_duration_10hz: Duration = Duration::from_millis(1000 / freq_10hz);
         assert_eq!(expected_duration_10hz, Duration::from_millis(100));
     }
+
+    #[test]
+    fn test_schedule_period() {
+        // The variable schedule does not truncate the period to milliseconds
+        let schedule: Schedule = Schedule::Variable;
+
+        assert_eq!(schedule.period(30), Duration::from_nanos(33_333_333));
+        assert_eq!(schedule.period(60), Duration::from_nanos(16_666_666));
+        assert_eq!(schedule.period(10), Duration::from_millis(100));
+    }
 }
@@ -0,0 +1,588 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::BatchRunner;
+#[cfg(feature = "serde")]
+use multi_agent_core::{save_json_pretty, Error, Result};
+use multi_agent_core::{MultiAgentSimulation, SeedFactory};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::{f64::consts::TAU, ops::RangeInclusive};
+
+/// Numeric parameter searched by the [`Optimizer`], with its bounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterBounds {
+    /// Name of the parameter, used in the report.
+    pub name: String,
+    /// Smallest value tried.
+    pub min: f64,
+    /// Largest value tried.
+    pub max: f64,
+}
+
+impl ParameterBounds {
+    /// Bounds of the parameter `name`.
+    ///
+    /// A reversed range is flipped, and a NaN bound is replaced by the other one,
+    /// or by 0 if both are NaN.
+    #[inline]
+    #[must_use]
+    pub fn new<S: Into<String>>(name: S, range: RangeInclusive<f64>) -> Self {
+        let (start, end): (f64, f64) = range.into_inner();
+        let (min, max): (f64, f64) = match (start.min(end), start.max(end)) {
+            (min, _) if min.is_nan() => (0.0, 0.0),
+            bounds => bounds,
+        };
+        Self {
+            name: name.into(),
+            min,
+            max,
+        }
+    }
+
+    #[inline]
+    const fn clamp(&self, value: f64) -> f64 {
+        value.clamp(self.min, self.max)
+    }
+
+    #[inline]
+    fn span(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+/// A configuration evaluated by the [`Optimizer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate<GuiData> {
+    /// Value of every searched parameter, by name.
+    pub parameters: Vec<(String, f64)>,
+    /// GUI data built from the base configuration and the parameters.
+    pub gui_data: GuiData,
+    /// Mean fitness over the evaluation seeds, `f64::NEG_INFINITY` if a run failed.
+    pub fitness: f64,
+}
+
+#[cfg(feature = "serde")]
+impl<GuiData: Serialize> Candidate<GuiData> {
+    /// Save the GUI data of the candidate to a JSON file, to load it back as
+    /// the initial GUI data of a run.
+    ///
+    /// # Errors
+    /// Returns `Error::Batch` if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save_json_pretty(path, &self.gui_data, Error::Batch)
+    }
+}
+
+/// Fitness of the population at the end of a generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationSummary {
+    /// Index of the generation, starting at 0.
+    pub generation: usize,
+    /// Best fitness of the generation.
+    pub best_fitness: f64,
+    /// Mean fitness of the candidates whose runs did not fail.
+    pub mean_fitness: f64,
+}
+
+/// Outcome of an optimization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizationReport<GuiData> {
+    /// Best candidate found over all generations.
+    pub best: Candidate<GuiData>,
+    /// Summary of every generation, in order.
+    pub generations: Vec<GenerationSummary>,
+}
+
+/// Genetic algorithm tuning numeric parameters of the GUI data to maximize a fitness.
+///
+/// Every candidate is run headless for a fixed number of ticks with each
+/// evaluation seed, in parallel through a [`BatchRunner`], and scored by a
+/// fitness computed from the final simulation data. The best candidates of a
+/// generation are kept as is, the others are bred from tournaments with blend
+/// crossover and gaussian mutation, within the bounds of each parameter.
+///
+/// The search is reproducible: it only draws from the seed of the optimizer.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::Optimizer;
+///
+/// let report = Optimizer::<Physarum>::new(500)
+///     .parameter("sensor_angle", 0.1..=1.5)
+///     .parameter("deposit", 0.5..=10.0)
+///     .population(32)
+///     .generations(20)
+///     .run(
+///         PhysarumConfig::default(),
+///         |config, values| {
+///             config.sensor_angle = values[0] as f32;
+///             config.deposit = values[1] as f32;
+///         },
+///         |trail_map| network_score(trail_map),
+///     );
+///
+/// println!("Best score {} with {:?}", report.best.fitness, report.best.parameters);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Optimizer<Simulation> {
+    batch: BatchRunner<Simulation>,
+    parameters: Vec<ParameterBounds>,
+    population: usize,
+    generations: usize,
+    elites: usize,
+    mutation: f64,
+    seed: u64,
+}
+
+impl<Simulation> Optimizer<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    /// Candidates drawn for each tournament of the selection.
+    const TOURNAMENT_SIZE: usize = 3;
+
+    /// How far outside of its parents a child may land, as a fraction of their distance.
+    const BLEND_ALPHA: f64 = 0.25;
+
+    /// Create an optimizer scoring every candidate after `ticks` ticks.
+    ///
+    /// By default, the population has 20 candidates evolved over 10 generations,
+    /// the 2 best of each are kept, mutations have a standard deviation of 10 %
+    /// of the bounds, and candidates are evaluated with seed 0 only.
+    #[inline]
+    #[must_use]
+    pub fn new(ticks: u64) -> Self {
+        Self {
+            batch: BatchRunner::new(ticks),
+            parameters: Vec::new(),
+            population: 20,
+            generations: 10,
+            elites: 2,
+            mutation: 0.1,
+            seed: 0,
+        }
+    }
+
+    /// Search the parameter `name` within `range`.
+    ///
+    /// Parameters are passed to the `apply` function of [`run`](Self::run) in
+    /// the order they are added.
+    #[inline]
+    #[must_use]
+    pub fn parameter<S: Into<String>>(mut self, name: S, range: RangeInclusive<f64>) -> Self {
+        self.parameters.push(ParameterBounds::new(name, range));
+        self
+    }
+
+    /// Number of candidates of each generation.
+    ///
+    /// Default: 20
+    #[inline]
+    #[must_use]
+    pub fn population(mut self, population: usize) -> Self {
+        self.population = population.max(1);
+        self
+    }
+
+    /// Number of generations to evolve.
+    ///
+    /// Default: 10
+    #[inline]
+    #[must_use]
+    pub fn generations(mut self, generations: usize) -> Self {
+        self.generations = generations.max(1);
+        self
+    }
+
+    /// Number of best candidates carried over unchanged to the next generation.
+    ///
+    /// Default: 2
+    #[inline]
+    #[must_use]
+    pub const fn elites(mut self, elites: usize) -> Self {
+        self.elites = elites;
+        self
+    }
+
+    /// Standard deviation of the mutations, as a fraction of the bounds of each parameter.
+    ///
+    /// Default: 0.1
+    #[inline]
+    #[must_use]
+    pub const fn mutation(mut self, mutation: f64) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// Evaluate every candidate once per seed and average the fitness.
+    ///
+    /// An empty list is ignored, since candidates could not be scored.
+    ///
+    /// Default: `[0]`
+    #[inline]
+    #[must_use]
+    pub fn evaluation_seeds<I: IntoIterator<Item = u64>>(mut self, seeds: I) -> Self {
+        let seeds: Vec<u64> = seeds.into_iter().collect();
+        if !seeds.is_empty() {
+            self.batch = self.batch.seeds(seeds);
+        }
+        self
+    }
+
+    /// Run at most `threads` evaluations at the same time.
+    ///
+    /// Default: the number of available cores
+    #[inline]
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.batch = self.batch.threads(threads);
+        self
+    }
+
+    /// Seed of the random choices of the search itself.
+    ///
+    /// Default: 0
+    #[inline]
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Evolve the parameters and return the best candidate found.
+    ///
+    /// # Arguments
+    /// * `base` - GUI data holding the values of everything that is not searched
+    /// * `apply` - Writes the parameter values, in the order they were added, into the GUI data
+    /// * `fitness` - Score of the final simulation data, higher is better
+    pub fn run<A, F>(
+        &self,
+        base: Simulation::GuiData,
+        apply: A,
+        fitness: F,
+    ) -> OptimizationReport<Simulation::GuiData>
+    where
+        A: Fn(&mut Simulation::GuiData, &[f64]),
+        F: Fn(&Simulation::SimulationData) -> f64 + Sync,
+    {
+        let mut random = Random::new(self.seed);
+        let mut population: Vec<Vec<f64>> = (0..self.population)
+            .map(|_| {
+                self.parameters
+                    .iter()
+                    .map(|bounds| random.uniform().mul_add(bounds.span(), bounds.min))
+                    .collect()
+            })
+            .collect();
+
+        let mut best: Option<Candidate<Simulation::GuiData>> = None;
+        let mut generations: Vec<GenerationSummary> = Vec::with_capacity(self.generations);
+        for generation in 0..self.generations {
+            let mut candidates: Vec<Candidate<Simulation::GuiData>> =
+                self.evaluate(&population, &base, &apply, &fitness);
+            candidates.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+            generations.push(GenerationSummary {
+                generation,
+                best_fitness: candidates[0].fitness,
+                mean_fitness: mean_fitness(&candidates),
+            });
+            log::info!(
+                "Generation {generation}: best fitness {}",
+                candidates[0].fitness
+            );
+
+            if best
+                .as_ref()
+                .is_none_or(|best| candidates[0].fitness > best.fitness)
+            {
+                best = Some(candidates[0].clone());
+            }
+
+            let values: Vec<Vec<f64>> = candidates
+                .iter()
+                .map(|candidate| {
+                    candidate
+                        .parameters
+                        .iter()
+                        .map(|(_, value)| *value)
+                        .collect()
+                })
+                .collect();
+            population = self.breed(&values, &mut random);
+        }
+
+        OptimizationReport {
+            best: best.unwrap_or_else(|| Candidate {
+                parameters: Vec::new(),
+                gui_data: base,
+                fitness: f64::NEG_INFINITY,
+            }),
+            generations,
+        }
+    }
+
+    fn evaluate<A, F>(
+        &self,
+        population: &[Vec<f64>],
+        base: &Simulation::GuiData,
+        apply: &A,
+        fitness: &F,
+    ) -> Vec<Candidate<Simulation::GuiData>>
+    where
+        A: Fn(&mut Simulation::GuiData, &[f64]),
+        F: Fn(&Simulation::SimulationData) -> f64 + Sync,
+    {
+        let configurations: Vec<Simulation::GuiData> = population
+            .iter()
+            .map(|values| {
+                let mut gui_data: Simulation::GuiData = base.clone();
+                apply(&mut gui_data, values);
+                gui_data
+            })
+            .collect();
+
+        let report = self.batch.run(
+            configurations.iter().cloned(),
+            |_| Vec::new(),
+            |data| vec![(String::from("fitness"), fitness(data))],
+        );
+        let runs_per_candidate: usize = report.runs.len() / population.len().max(1);
+
+        population
+            .iter()
+            .zip(configurations)
+            .zip(report.runs.chunks(runs_per_candidate.max(1)))
+            .map(|((values, gui_data), runs)| {
+                let scores: Option<Vec<f64>> = runs
+                    .iter()
+                    .map(|run| match run.error {
+                        Some(_) => None,
+                        None => run.samples.last().map(|sample| sample.value),
+                    })
+                    .collect();
+                Candidate {
+                    parameters: self
+                        .parameters
+                        .iter()
+                        .map(|bounds| bounds.name.clone())
+                        .zip(values.iter().copied())
+                        .collect(),
+                    gui_data,
+                    fitness: scores.map_or(f64::NEG_INFINITY, |scores| mean(&scores)),
+                }
+            })
+            .collect()
+    }
+
+    /// Next generation from the parameter values of `ranked`, sorted best first.
+    fn breed(&self, ranked: &[Vec<f64>], random: &mut Random) -> Vec<Vec<f64>> {
+        let mut next: Vec<Vec<f64>> = ranked.iter().take(self.elites).cloned().collect();
+        while next.len() < self.population {
+            let first: &[f64] = Self::tournament(ranked, random);
+            let second: &[f64] = Self::tournament(ranked, random);
+            let child: Vec<f64> = self
+                .parameters
+                .iter()
+                .zip(first.iter().zip(second))
+                .map(|(bounds, (first, second))| {
+                    let blend: f64 = random
+                        .uniform()
+                        .mul_add(Self::BLEND_ALPHA.mul_add(2.0, 1.0), -Self::BLEND_ALPHA);
+                    let value: f64 = blend.mul_add(second - first, *first);
+                    let noise: f64 = random.gaussian() * self.mutation * bounds.span();
+                    bounds.clamp(value + noise)
+                })
+                .collect();
+            next.push(child);
+        }
+        next
+    }
+
+    /// Best of a few candidates drawn at random from `ranked`, sorted best first.
+    fn tournament<'a>(ranked: &'a [Vec<f64>], random: &mut Random) -> &'a [f64] {
+        let winner: usize = (0..Self::TOURNAMENT_SIZE)
+            .map(|_| random.below(ranked.len()))
+            .min()
+            .unwrap_or_default();
+        &ranked[winner]
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn mean_fitness<GuiData>(candidates: &[Candidate<GuiData>]) -> f64 {
+    let finite: Vec<f64> = candidates
+        .iter()
+        .map(|candidate| candidate.fitness)
+        .filter(|fitness| fitness.is_finite())
+        .collect();
+    mean(&finite)
+}
+
+/// Deterministic source of random numbers drawn from the streams of a [`SeedFactory`].
+#[derive(Debug)]
+struct Random {
+    seeds: SeedFactory,
+    index: u64,
+}
+
+impl Random {
+    const fn new(seed: u64) -> Self {
+        Self {
+            seeds: SeedFactory::new(seed),
+            index: 0,
+        }
+    }
+
+    const fn next_u64(&mut self) -> u64 {
+        self.index += 1;
+        self.seeds.stream(self.index)
+    }
+
+    /// Uniform value in `[0, 1)`.
+    #[allow(clippy::cast_precision_loss)]
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Value drawn from the standard normal distribution.
+    fn gaussian(&mut self) -> f64 {
+        let radius: f64 = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+        radius * (TAU * self.uniform()).cos()
+    }
+
+    /// Uniform index in `0..count`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn below(&mut self, count: usize) -> usize {
+        ((self.uniform() * count as f64) as usize).min(count.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::{CancellationToken, Result};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Peak {
+        height: f64,
+    }
+
+    impl MultiAgentSimulation for Peak {
+        type SimulationData = f64;
+        type GuiData = (f64, f64);
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            let (x, y): (f64, f64) = initial_gui_data;
+            Ok(Self {
+                height: (x - 3.0).mul_add(-(x - 3.0), -(y + 1.0) * (y + 1.0)),
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            Ok(&self.height)
+        }
+    }
+
+    fn optimize(seed: u64) -> OptimizationReport<(f64, f64)> {
+        Optimizer::<Peak>::new(1)
+            .parameter("x", -10.0..=10.0)
+            .parameter("y", 0.0..=10.0)
+            .population(24)
+            .generations(25)
+            .threads(4)
+            .seed(seed)
+            .run(
+                (0.0, 0.0),
+                |gui_data, values| *gui_data = (values[0], values[1]),
+                |height| *height,
+            )
+    }
+
+    #[test]
+    fn test_optimizer_finds_the_peak_within_bounds() {
+        let report = optimize(7);
+
+        let (x, y): (f64, f64) = report.best.gui_data;
+        assert!((x - 3.0).abs() < 0.2, "x = {x}");
+        assert!(y.abs() < f64::EPSILON, "y = {y}");
+        assert_eq!(report.best.parameters[0].0, "x");
+        assert_eq!(report.generations.len(), 25);
+        assert!(report.generations[24].best_fitness >= report.generations[0].best_fitness);
+    }
+
+    #[test]
+    fn test_optimizer_is_reproducible() {
+        assert_eq!(optimize(1), optimize(1));
+    }
+
+    #[test]
+    fn test_parameter_bounds_are_normalised() {
+        let reversed = ParameterBounds::new("w", 3.0..=0.5);
+        let half_nan = ParameterBounds::new("w", f64::NAN..=2.0);
+        let nan = ParameterBounds::new("w", f64::NAN..=f64::NAN);
+
+        assert_eq!((reversed.min, reversed.max), (0.5, 3.0));
+        assert_eq!((half_nan.min, half_nan.max), (2.0, 2.0));
+        assert_eq!((nan.min, nan.max), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_optimizer_survives_degenerate_settings() {
+        let report = Optimizer::<Peak>::new(1)
+            .parameter("x", 10.0..=-10.0)
+            .evaluation_seeds([])
+            .population(4)
+            .generations(2)
+            .threads(1)
+            .run(
+                (0.0, 0.0),
+                |gui_data, values| gui_data.0 = values[0],
+                |height| *height,
+            );
+
+        assert!(report.best.fitness.is_finite());
+        assert!((-10.0..=10.0).contains(&report.best.gui_data.0));
+    }
+}
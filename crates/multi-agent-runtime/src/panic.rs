@@ -0,0 +1,119 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// What the runtime does when the simulation panics during a tick.
+///
+/// Panics are only caught when the binary is built with `panic = "unwind"`,
+/// which every profile of this workspace uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Halt the simulation and report the panic until it is restarted by hand.
+    #[default]
+    Stop,
+    /// Recreate the simulation with `MultiAgentSimulation::new` and keep running.
+    Restart,
+    /// Recreate the simulation with `MultiAgentSimulation::restore` from the data
+    /// published by the last good tick and keep running.
+    ///
+    /// Falls back to `Stop` if the simulation cannot be restored.
+    Rollback,
+}
+
+/// A panic caught while running simulation code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaughtPanic {
+    /// Message the code panicked with.
+    pub message: String,
+    /// Source location of the panic, as `file:line:column`.
+    pub location: Option<String>,
+}
+
+thread_local! {
+    static LAST_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, turning a panic into a [`CaughtPanic`].
+///
+/// The panic is still printed by the panic hook in place.
+pub fn catch<T, F>(f: F) -> Result<T, CaughtPanic>
+where
+    F: FnOnce() -> T,
+{
+    install_location_hook();
+    LAST_LOCATION.with_borrow_mut(Option::take);
+
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| CaughtPanic {
+        message: message_of(payload.as_ref()),
+        location: LAST_LOCATION.with_borrow_mut(Option::take),
+    })
+}
+
+/// Wrap the current panic hook so that it records the location of the panic.
+fn install_location_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location: Option<String> = info.location().map(ToString::to_string);
+            LAST_LOCATION.with_borrow_mut(|last| *last = location);
+            previous_hook(info);
+        }));
+    });
+}
+
+fn message_of(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("Box<dyn Any>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_returns_value() {
+        assert_eq!(catch(|| 42), Ok(42));
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn test_catch_extracts_message_and_location() {
+        let caught = catch(|| -> u32 { panic!("boom {}", 42) }).unwrap_err();
+
+        assert_eq!(caught.message, "boom 42");
+        assert!(caught.location.unwrap().starts_with(file!()));
+    }
+
+    #[test]
+    fn test_catch_static_message() {
+        let values: Vec<u32> = Vec::new();
+
+        let caught = catch(|| values.first().copied().unwrap()).unwrap_err();
+
+        assert!(caught.message.contains("None"));
+        assert!(caught.location.is_some());
+    }
+}
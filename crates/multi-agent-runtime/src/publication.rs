@@ -0,0 +1,33 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// How the runtime hands the data of every tick to the GUI.
+///
+/// Both publish the same values: the choice only affects how much the simulation
+/// thread allocates, which matters when `SimulationData` holds large grids or
+/// many agents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Publication {
+    /// Clone the data into a new allocation on every tick.
+    #[default]
+    Allocate,
+    /// Copy the data into one of three recycled buffers with `Clone::clone_from`,
+    /// so ticks do not allocate once the buffers have grown to size.
+    ///
+    /// A buffer still read by the GUI, or kept by the timeline, is not recycled,
+    /// so a timeline brings back one allocation per tick.
+    TripleBuffer,
+}
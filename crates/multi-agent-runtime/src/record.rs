@@ -0,0 +1,274 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use multi_agent_core::{save_json, Error, Result};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::{fs::File, io::BufReader, path::Path};
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+/// Inputs received by the simulation during a single tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TickInputs<GuiData, Message> {
+    /// Time step passed to `update()`.
+    pub delta_time: Duration,
+    /// New GUI data, if it changed since the previous tick.
+    pub gui_data: Option<GuiData>,
+    /// Messages drained from the GUI channel.
+    pub messages: Vec<Message>,
+}
+
+/// Every input a simulation received since it was created, tick by tick.
+///
+/// Feeding a recording back into a fresh simulation created with the same seed
+/// reproduces the run exactly, provided the simulation draws its randomness from
+/// the seeds handed out by the runtime.
+///
+/// With the `serde` feature, recordings can be saved to and loaded from JSON files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording<GuiData, Message> {
+    /// Master seed the simulation was created with.
+    pub seed: u64,
+    /// GUI data passed to `MultiAgentSimulation::new`.
+    pub initial_gui_data: GuiData,
+    /// Inputs of every tick, the index being the tick number.
+    pub ticks: Vec<TickInputs<GuiData, Message>>,
+}
+
+impl<GuiData, Message> Recording<GuiData, Message> {
+    /// Create an empty recording for a simulation created with `seed` and `initial_gui_data`.
+    #[inline]
+    #[must_use]
+    pub const fn new(seed: u64, initial_gui_data: GuiData) -> Self {
+        Self {
+            seed,
+            initial_gui_data,
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Number of recorded ticks.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.ticks.len() as u64
+    }
+
+    /// Whether no tick has been recorded.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<GuiData, Message> Recording<GuiData, Message>
+where
+    GuiData: Serialize + DeserializeOwned,
+    Message: Serialize + DeserializeOwned,
+{
+    /// Save the recording to a JSON file.
+    ///
+    /// # Errors
+    /// Returns `Error::Recording` if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        save_json(path, self, Error::Recording)
+    }
+
+    /// Load a recording from a JSON file written by [`save`](Self::save).
+    ///
+    /// # Errors
+    /// Returns `Error::Recording` if the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path).map_err(|err| Error::Recording(err.to_string()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|err| Error::Recording(err.to_string()))
+    }
+}
+
+/// Handle recording the inputs of a running simulation.
+///
+/// Give a clone to `RuntimeBuilder::recorder` and read the [`recording`]
+/// back once the application is closed. The recording starts over when the
+/// simulation is restarted. Resuming from a past frame or rolling back after a
+/// panic restores a state that the recording cannot reproduce.
+///
+/// [`recording`]: Recorder::recording
+///
+/// # Example
+/// ```rust,ignore
+/// use multi_agent::{AppLauncher, Recorder};
+///
+/// let recorder = Recorder::new();
+/// AppLauncher::builder()
+///     .recorder(recorder.clone())
+///     .run::<MySimulation, MyGui>()?;
+///
+/// recorder.recording().save("session.json")?;
+/// ```
+#[derive(Debug)]
+pub struct Recorder<GuiData, Message> {
+    inner: Arc<Mutex<RecorderState<GuiData, Message>>>,
+}
+
+#[derive(Debug)]
+struct RecorderState<GuiData, Message> {
+    recording: Recording<GuiData, Message>,
+    last_gui_data: Option<Arc<GuiData>>,
+}
+
+impl<GuiData, Message> Clone for Recorder<GuiData, Message> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<GuiData: Default, Message> Default for Recorder<GuiData, Message> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RecorderState {
+                recording: Recording::new(0, GuiData::default()),
+                last_gui_data: None,
+            })),
+        }
+    }
+}
+
+impl<GuiData: Default, Message> Recorder<GuiData, Message> {
+    /// Create a recorder with an empty recording.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<GuiData: Clone, Message: Clone> Recorder<GuiData, Message> {
+    /// Copy of everything recorded so far.
+    #[inline]
+    #[must_use]
+    pub fn recording(&self) -> Recording<GuiData, Message> {
+        self.lock().recording.clone()
+    }
+
+    /// Start a new recording for a simulation created with `seed` and `initial_gui_data`.
+    ///
+    /// Called by the runtime when the simulation is created or restarted.
+    #[inline]
+    pub fn start(&self, seed: u64, initial_gui_data: Arc<GuiData>) {
+        let mut state = self.lock();
+        state.recording = Recording::new(seed, (*initial_gui_data).clone());
+        state.last_gui_data = Some(initial_gui_data);
+    }
+
+    /// Record the inputs of the next tick.
+    ///
+    /// The GUI data is only recorded when it is not the snapshot seen on the previous tick.
+    #[inline]
+    pub fn record(&self, delta_time: Duration, gui_data: &Arc<GuiData>, messages: &[Message]) {
+        let mut state = self.lock();
+        let changed: bool = state
+            .last_gui_data
+            .as_ref()
+            .is_none_or(|last| !Arc::ptr_eq(last, gui_data));
+        if changed {
+            state.last_gui_data = Some(Arc::clone(gui_data));
+        }
+        state.recording.ticks.push(TickInputs {
+            delta_time,
+            gui_data: changed.then(|| (**gui_data).clone()),
+            messages: messages.to_vec(),
+        });
+    }
+}
+
+impl<GuiData, Message> Recorder<GuiData, Message> {
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, RecorderState<GuiData, Message>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_only_keeps_gui_data_changes() {
+        let recorder: Recorder<u32, &str> = Recorder::new();
+        let initial = Arc::new(1);
+        let changed = Arc::new(2);
+        let delta_time = Duration::from_millis(10);
+
+        recorder.start(9, Arc::clone(&initial));
+        recorder.record(delta_time, &initial, &["a"]);
+        recorder.record(delta_time, &changed, &[]);
+        recorder.record(delta_time, &changed, &["b", "c"]);
+
+        let recording = recorder.recording();
+        assert_eq!(recording.seed, 9);
+        assert_eq!(recording.initial_gui_data, 1);
+        assert_eq!(recording.len(), 3);
+        assert_eq!(recording.ticks[0].gui_data, None);
+        assert_eq!(recording.ticks[0].messages, vec!["a"]);
+        assert_eq!(recording.ticks[1].gui_data, Some(2));
+        assert_eq!(recording.ticks[2].gui_data, None);
+    }
+
+    #[test]
+    fn test_recorder_start_resets() {
+        let recorder: Recorder<u32, ()> = Recorder::new();
+
+        recorder.start(1, Arc::new(1));
+        recorder.record(Duration::ZERO, &Arc::new(1), &[]);
+        recorder.start(2, Arc::new(5));
+
+        assert_eq!(recorder.recording(), Recording::new(2, 5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_recording_save_and_load() {
+        let path = std::env::temp_dir().join(format!(
+            "multi-agent-runtime-test-recording-{}.json",
+            std::process::id()
+        ));
+        let mut recording: Recording<u32, String> = Recording::new(7, 3);
+        recording.ticks.push(TickInputs {
+            delta_time: Duration::from_millis(20),
+            gui_data: Some(4),
+            messages: vec![String::from("hello")],
+        });
+
+        recording.save(&path).unwrap();
+        let loaded: Recording<u32, String> = Recording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, recording);
+    }
+}
@@ -0,0 +1,1114 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use multi_agent_core::{Error, MultiAgentSimulation, Result};
+use multi_agent_sync::{
+    message::{MessageChannel, MessageReceiver, MessageSender},
+    Change, DeltaFeed, Diffable, Patch, Shared, SimulationControl,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    io::{self, BufReader, ErrorKind, Read, Write},
+    mem,
+    net::{Shutdown, TcpListener, TcpStream},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Longest time a bridge takes to notice a disconnection, a stop or a new input.
+const POLL_PERIOD: Duration = Duration::from_millis(10);
+
+/// Largest payload accepted in a frame, to reject corrupted length prefixes.
+const MAX_FRAME_LEN: usize = 64 << 20;
+
+/// Capacity of the message channels on the GUI side of a [`RemoteClient`].
+const CHANNEL_CAPACITY: usize = 100;
+
+/// Address a remote simulation listens on.
+///
+/// Parsed from `unix:<path>` for a Unix domain socket, and from `host:port`
+/// for a TCP socket otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteAddress {
+    /// TCP socket, as `host:port`.
+    Tcp(String),
+    /// Unix domain socket at the given path.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteAddress {
+    type Err = Error;
+
+    fn from_str(address: &str) -> Result<Self> {
+        #[cfg(unix)]
+        if let Some(path) = address.strip_prefix("unix:") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        #[cfg(not(unix))]
+        if address.starts_with("unix:") {
+            return Err(Error::Remote(String::from(
+                "Unix domain sockets are not supported on this platform",
+            )));
+        }
+        Ok(Self::Tcp(String::from(address)))
+    }
+}
+
+impl Display for RemoteAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "{address}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Frame sent by the simulation side to the GUI side.
+#[derive(Debug, Serialize, Deserialize)]
+enum ToGui<SimulationData, Message> {
+    /// Data published by the last tick.
+    Snapshot(SimulationData),
+    /// Messages sent by the simulation since the previous frame.
+    Messages(Vec<Message>),
+    /// Deltas encoded by a [`DeltaCodec`], to apply in order to the previous data.
+    Deltas(Vec<Vec<u8>>),
+}
+
+/// Frame sent by the GUI side to the simulation side.
+#[derive(Debug, Serialize, Deserialize)]
+enum ToSimulation<GuiData, Message> {
+    /// New GUI data.
+    GuiData(GuiData),
+    /// Messages sent by the GUI since the previous frame.
+    Messages(Vec<Message>),
+    /// New pause state and time scale of the transport toolbar.
+    Control { paused: bool, time_scale: f64 },
+    /// Ticks to execute while paused.
+    Step(u64),
+    /// Restart the simulation with the given master seed.
+    Restart { seed: u64 },
+    /// Send the changes of the data rather than the whole data, if published as deltas.
+    AcceptDeltas,
+}
+
+/// Encoding of the deltas of a [`Diffable`] `SimulationData` sent over a remote link.
+///
+/// Lets a served simulation stream the changes recorded by a
+/// [`DiffBuffer`](multi_agent_sync::DiffBuffer) instead of the whole data on
+/// every tick, and a [`RemoteClient`] rebuild the data from them. Both sides
+/// need one, see `RuntimeBuilder::delta_streaming`.
+pub struct DeltaCodec<T> {
+    encode: fn(&dyn Patch<T>) -> Result<Vec<u8>>,
+    apply: fn(&mut T, &[u8]) -> Result<()>,
+}
+
+impl<T> DeltaCodec<T>
+where
+    T: Diffable,
+    T::Delta: Serialize + DeserializeOwned,
+{
+    /// Create a codec encoding the deltas of `T` with bincode.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            encode: |patch| {
+                let delta: &T::Delta = patch
+                    .delta()
+                    .downcast_ref()
+                    .ok_or_else(|| Error::Remote(String::from("Unexpected type of delta")))?;
+                bincode::serialize(delta).map_err(|err| Error::Remote(err.to_string()))
+            },
+            apply: |data, bytes| {
+                let delta: T::Delta =
+                    bincode::deserialize(bytes).map_err(|err| Error::Remote(err.to_string()))?;
+                data.apply(&delta);
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<T> Default for DeltaCodec<T>
+where
+    T: Diffable,
+    T::Delta: Serialize + DeserializeOwned,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for DeltaCodec<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DeltaCodec<T> {}
+
+impl<T> Debug for DeltaCodec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeltaCodec").finish_non_exhaustive()
+    }
+}
+
+/// Write `frame` as a little-endian `u32` length followed by its bincode encoding.
+fn write_frame<W: Write, T: Serialize>(writer: &mut W, frame: &T) -> Result<()> {
+    let mut bytes: Vec<u8> = vec![0; 4];
+    bincode::serialize_into(&mut bytes, frame).map_err(|err| Error::Remote(err.to_string()))?;
+    let len = u32::try_from(bytes.len() - 4)
+        .ok()
+        .filter(|len| *len as usize <= MAX_FRAME_LEN)
+        .ok_or_else(|| Error::Remote(format!("Frame of {} bytes is too large", bytes.len())))?;
+    bytes[..4].copy_from_slice(&len.to_le_bytes());
+    writer
+        .write_all(&bytes)
+        .and_then(|()| writer.flush())
+        .map_err(|err| Error::Remote(err.to_string()))
+}
+
+/// Read a frame written by [`write_frame`], or `None` if the peer closed the connection.
+fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> Result<Option<T>> {
+    let mut len: [u8; 4] = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(Error::Remote(err.to_string())),
+    }
+
+    let len: usize = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(Error::Remote(format!("Frame of {len} bytes is too large")));
+    }
+    // Grow with the bytes actually received rather than trusting the prefix.
+    let mut bytes: Vec<u8> = Vec::new();
+    reader
+        .take(len as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|err| Error::Remote(err.to_string()))?;
+    if bytes.len() < len {
+        return Err(Error::Remote(format!(
+            "Frame truncated after {} of {len} bytes",
+            bytes.len()
+        )));
+    }
+    bincode::deserialize(&bytes)
+        .map(Some)
+        .map_err(|err| Error::Remote(err.to_string()))
+}
+
+/// Connected socket of either kind.
+#[derive(Debug)]
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn connect(address: &RemoteAddress) -> io::Result<Self> {
+        match address {
+            RemoteAddress::Tcp(address) => Self::from_tcp(TcpStream::connect(address.as_str())?),
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => Ok(Self::Unix(UnixStream::connect(path)?)),
+        }
+    }
+
+    fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(false)?;
+        stream.set_nodelay(true)?;
+        Ok(Self::Tcp(stream))
+    }
+
+    fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            Self::Tcp(stream) => stream.try_clone().map(Self::Tcp),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.try_clone().map(Self::Unix),
+        }
+    }
+
+    /// Close both directions, waking up the threads blocked on the socket.
+    fn shutdown(&self) {
+        let _ = match self {
+            Self::Tcp(stream) => stream.shutdown(Shutdown::Both),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.shutdown(Shutdown::Both),
+        };
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Listening socket of either kind.
+#[derive(Debug)]
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+/// Socket a simulation is served on, waiting for a GUI to connect.
+///
+/// Pass it to `RuntimeBuilder::serve` to run a simulation that a GUI started
+/// with `RuntimeBuilder::connect` can drive from another process or machine.
+/// GUIs are served one at a time, each new one taking over from the previous.
+///
+/// The socket file of a Unix domain socket is removed when the server is dropped.
+#[derive(Debug)]
+pub struct RemoteServer {
+    listener: Listener,
+}
+
+impl RemoteServer {
+    /// Listen on `address`.
+    ///
+    /// Bind a TCP server to port 0 to let the system pick a free port, then read
+    /// it back with [`local_address`](Self::local_address).
+    ///
+    /// # Errors
+    /// Returns `Error::Remote` if the socket cannot be bound.
+    pub fn bind(address: &RemoteAddress) -> Result<Self> {
+        let listener: Listener = match address {
+            RemoteAddress::Tcp(address) => {
+                let listener = TcpListener::bind(address.as_str())
+                    .map_err(|err| Error::Remote(format!("Cannot bind {address}: {err}")))?;
+                listener
+                    .set_nonblocking(true)
+                    .map_err(|err| Error::Remote(err.to_string()))?;
+                Listener::Tcp(listener)
+            }
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => {
+                let listener = UnixListener::bind(path).map_err(|err| {
+                    Error::Remote(format!("Cannot bind {}: {err}", path.display()))
+                })?;
+                listener
+                    .set_nonblocking(true)
+                    .map_err(|err| Error::Remote(err.to_string()))?;
+                Listener::Unix(listener, path.clone())
+            }
+        };
+        Ok(Self { listener })
+    }
+
+    /// Address the server actually listens on.
+    ///
+    /// # Errors
+    /// Returns `Error::Remote` if the address of the socket cannot be read.
+    pub fn local_address(&self) -> Result<RemoteAddress> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener
+                .local_addr()
+                .map(|address| RemoteAddress::Tcp(address.to_string()))
+                .map_err(|err| Error::Remote(err.to_string())),
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Ok(RemoteAddress::Unix(path.clone())),
+        }
+    }
+
+    /// Serve the simulation side of the shared state and channels to the GUIs
+    /// connecting to this server, one at a time, until `control` is stopped.
+    ///
+    /// With `deltas`, the GUIs asking for them get the changes recorded in the
+    /// feed instead of the whole data on every tick.
+    ///
+    /// # Errors
+    /// Returns `Error::Remote` if the server stops accepting connections.
+    pub(crate) fn serve<Simulation>(
+        &self,
+        simulation_data: &Shared<Simulation::SimulationData>,
+        gui_data: &Shared<Simulation::GuiData>,
+        sender: &MessageSender<Simulation::MessageFromGui>,
+        receiver: &MessageReceiver<Simulation::MessageToGui>,
+        control: &SimulationControl,
+        deltas: Option<&Deltas<Simulation::SimulationData>>,
+    ) -> Result<()>
+    where
+        Simulation: MultiAgentSimulation,
+        Simulation::SimulationData: Serialize,
+        Simulation::GuiData: DeserializeOwned,
+        Simulation::MessageFromGui: DeserializeOwned,
+        Simulation::MessageToGui: Serialize,
+    {
+        while !control.is_stopped() {
+            let stream: Stream = match self.accept() {
+                Ok(stream) => stream,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(POLL_PERIOD);
+                    continue;
+                }
+                Err(err) => return Err(Error::Remote(err.to_string())),
+            };
+
+            log::info!("GUI connected");
+            match serve_gui::<Simulation>(
+                &stream,
+                simulation_data,
+                gui_data,
+                sender,
+                receiver,
+                control,
+                deltas,
+            ) {
+                Ok(()) => log::info!("GUI disconnected"),
+                Err(error) => log::warn!("GUI disconnected: {error}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn accept(&self) -> io::Result<Stream> {
+        match &self.listener {
+            Listener::Tcp(listener) => Stream::from_tcp(listener.accept()?.0),
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nonblocking(false)?;
+                Ok(Stream::Unix(stream))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RemoteServer {
+    #[inline]
+    fn drop(&mut self) {
+        if let Listener::Unix(_, path) = &self.listener {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Feed of the changes published by a simulation, with the codec of its deltas.
+type Deltas<SimulationData> = (DeltaFeed<SimulationData>, DeltaCodec<SimulationData>);
+
+/// Exchange frames with a connected GUI until it disconnects or `control` is stopped.
+fn serve_gui<Simulation>(
+    stream: &Stream,
+    simulation_data: &Shared<Simulation::SimulationData>,
+    gui_data: &Shared<Simulation::GuiData>,
+    sender: &MessageSender<Simulation::MessageFromGui>,
+    receiver: &MessageReceiver<Simulation::MessageToGui>,
+    control: &SimulationControl,
+    deltas: Option<&Deltas<Simulation::SimulationData>>,
+) -> Result<()>
+where
+    Simulation: MultiAgentSimulation,
+    Simulation::SimulationData: Serialize,
+    Simulation::GuiData: DeserializeOwned,
+    Simulation::MessageFromGui: DeserializeOwned,
+    Simulation::MessageToGui: Serialize,
+{
+    let mut reader = BufReader::new(stream.try_clone().map_err(|err| Error::Remote(err.to_string()))?);
+    let mut writer: Stream = stream.try_clone().map_err(|err| Error::Remote(err.to_string()))?;
+    let connected = AtomicBool::new(true);
+    let accepts_deltas = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            loop {
+                match read_frame::<_, ToSimulation<Simulation::GuiData, Simulation::MessageFromGui>>(
+                    &mut reader,
+                ) {
+                    Ok(Some(ToSimulation::AcceptDeltas)) => {
+                        accepts_deltas.store(true, Ordering::Release);
+                    }
+                    Ok(Some(frame)) => apply_gui_frame(frame, gui_data, sender, control),
+                    Ok(None) => break,
+                    Err(error) => {
+                        log::warn!("Invalid frame from the GUI: {error}");
+                        break;
+                    }
+                }
+            }
+            connected.store(false, Ordering::Release);
+        });
+
+        let mut result: Result<()> = Ok(());
+        let mut last_generation: Option<u64> = None;
+        let mut last_version: u64 = 0;
+        while connected.load(Ordering::Acquire) && !control.is_stopped() {
+            let generation: u64 = last_generation.map_or_else(
+                || simulation_data.generation(),
+                |generation| simulation_data.wait_for_change(generation, POLL_PERIOD),
+            );
+            if last_generation != Some(generation) {
+                last_generation = Some(generation);
+                result = match deltas {
+                    Some((feed, codec))
+                        if accepts_deltas.load(Ordering::Acquire) && feed.version() > 0 =>
+                    {
+                        write_changes::<_, _, Simulation::MessageToGui>(
+                            &mut writer,
+                            feed.since(last_version),
+                            codec,
+                        )
+                        .map(|version| last_version = version.unwrap_or(last_version))
+                    }
+                    _ => {
+                        let frame = ToGui::<_, Simulation::MessageToGui>::Snapshot(
+                            &**simulation_data.load(),
+                        );
+                        write_frame(&mut writer, &frame)
+                    }
+                };
+            }
+
+            let messages: Vec<Simulation::MessageToGui> = receiver.drain();
+            if result.is_ok() && !messages.is_empty() {
+                let frame = ToGui::<&Simulation::SimulationData, _>::Messages(messages);
+                result = write_frame(&mut writer, &frame);
+            }
+            if result.is_err() {
+                break;
+            }
+        }
+
+        stream.shutdown();
+        result
+    })
+}
+
+/// Write `changes` as snapshots for the keyframes and batches of encoded deltas
+/// in between, and return the version of the last one.
+fn write_changes<W, T, Message>(
+    writer: &mut W,
+    changes: Vec<(u64, Change<T>)>,
+    codec: &DeltaCodec<T>,
+) -> Result<Option<u64>>
+where
+    W: Write,
+    T: Serialize,
+    Message: Serialize,
+{
+    let last_version: Option<u64> = changes.last().map(|(version, _)| *version);
+    let mut deltas: Vec<Vec<u8>> = Vec::new();
+    for (_, change) in changes {
+        match change {
+            Change::Keyframe(data) => {
+                if !deltas.is_empty() {
+                    write_frame(
+                        writer,
+                        &ToGui::<&T, Message>::Deltas(mem::take(&mut deltas)),
+                    )?;
+                }
+                write_frame(writer, &ToGui::<_, Message>::Snapshot(&*data))?;
+            }
+            Change::Delta(patch) => deltas.push((codec.encode)(&*patch)?),
+        }
+    }
+    if !deltas.is_empty() {
+        write_frame(writer, &ToGui::<&T, Message>::Deltas(deltas))?;
+    }
+    Ok(last_version)
+}
+
+fn apply_gui_frame<GuiData, Message>(
+    frame: ToSimulation<GuiData, Message>,
+    gui_data: &Shared<GuiData>,
+    sender: &MessageSender<Message>,
+    control: &SimulationControl,
+) where
+    GuiData: Clone,
+{
+    match frame {
+        ToSimulation::GuiData(data) => gui_data.store(data),
+        ToSimulation::Messages(messages) => {
+            for message in messages {
+                sender.send_lossy(message);
+            }
+        }
+        ToSimulation::Control { paused, time_scale } => {
+            if paused {
+                control.pause();
+            } else {
+                control.resume();
+            }
+            control.set_time_scale(time_scale);
+        }
+        ToSimulation::Step(ticks) => control.step(ticks),
+        ToSimulation::Restart { seed } => {
+            control.set_seed(seed);
+            control.restart();
+        }
+        ToSimulation::AcceptDeltas => {}
+    }
+}
+
+/// GUI side of a simulation served by another process, possibly on another machine.
+///
+/// The client mirrors the shared state and channels of the simulation: the
+/// data published by the simulation lands in [`simulation_data`], its messages
+/// in [`receiver`], while the GUI data written to [`gui_data`] and the messages
+/// sent through [`sender`] are forwarded to it. Pausing, stepping, rescaling
+/// time and restarting through [`control`] are forwarded too.
+///
+/// The GUI data is only sent when it changes, so the simulation keeps its own
+/// until the GUI writes one. The connection is closed when the client is dropped.
+///
+/// [`simulation_data`]: RemoteClient::simulation_data
+/// [`receiver`]: RemoteClient::receiver
+/// [`gui_data`]: RemoteClient::gui_data
+/// [`sender`]: RemoteClient::sender
+/// [`control`]: RemoteClient::control
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use multi_agent::{RemoteAddress, RemoteClient};
+///
+/// let address: RemoteAddress = "127.0.0.1:7878".parse()?;
+/// let client = RemoteClient::<Physarum>::connect(&address)?;
+/// client.gui_data().store(PhysarumConfig::default());
+/// println!("{} agents", client.simulation_data().load().agents.len());
+/// ```
+#[derive(Debug)]
+pub struct RemoteClient<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    simulation_data: Shared<Simulation::SimulationData>,
+    gui_data: Shared<Simulation::GuiData>,
+    sender: MessageSender<Simulation::MessageFromGui>,
+    receiver: MessageReceiver<Simulation::MessageToGui>,
+    control: SimulationControl,
+    stream: Stream,
+    connected: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl<Simulation> RemoteClient<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+    Simulation::SimulationData: DeserializeOwned,
+    Simulation::GuiData: Serialize,
+    Simulation::MessageFromGui: Serialize,
+    Simulation::MessageToGui: DeserializeOwned,
+{
+    /// Connect to a simulation served on `address`.
+    ///
+    /// # Errors
+    /// Returns `Error::Remote` if the server cannot be reached.
+    #[inline]
+    pub fn connect(address: &RemoteAddress) -> Result<Self> {
+        Self::open(address, None)
+    }
+
+    /// Connect to a simulation served on `address`, asking for the changes of
+    /// its data rather than the whole data on every tick.
+    ///
+    /// The data is rebuilt by applying the deltas decoded by `codec` to a copy of
+    /// the previous one. Simulations publishing whole data keep sending it.
+    ///
+    /// # Errors
+    /// Returns `Error::Remote` if the server cannot be reached.
+    #[inline]
+    pub fn connect_with_deltas(
+        address: &RemoteAddress,
+        codec: DeltaCodec<Simulation::SimulationData>,
+    ) -> Result<Self> {
+        Self::open(address, Some(codec))
+    }
+
+    fn open(
+        address: &RemoteAddress,
+        codec: Option<DeltaCodec<Simulation::SimulationData>>,
+    ) -> Result<Self> {
+        let stream = Stream::connect(address)
+            .map_err(|err| Error::Remote(format!("Cannot connect to {address}: {err}")))?;
+        let reader: Stream = stream.try_clone().map_err(|err| Error::Remote(err.to_string()))?;
+        let mut writer: Stream =
+            stream.try_clone().map_err(|err| Error::Remote(err.to_string()))?;
+        if codec.is_some() {
+            write_frame(
+                &mut writer,
+                &ToSimulation::<&Simulation::GuiData, Simulation::MessageFromGui>::AcceptDeltas,
+            )?;
+        }
+
+        let simulation_data = Shared::new(Simulation::SimulationData::default());
+        let gui_data = Shared::new(Simulation::GuiData::default());
+        let (bridge_sender, receiver) = MessageChannel::new(CHANNEL_CAPACITY).split();
+        let (sender, bridge_receiver) = MessageChannel::new(CHANNEL_CAPACITY).split();
+        let control = SimulationControl::new();
+        let connected = Arc::new(AtomicBool::new(true));
+
+        let reader_thread: JoinHandle<()> = {
+            let simulation_data = simulation_data.clone();
+            let connected = Arc::clone(&connected);
+            thread::spawn(move || {
+                let mut reader = BufReader::new(reader);
+                loop {
+                    match read_frame(&mut reader) {
+                        Ok(Some(ToGui::Snapshot(data))) => simulation_data.store(data),
+                        Ok(Some(ToGui::Deltas(deltas))) => {
+                            if let Err(error) = apply_deltas(&simulation_data, &deltas, codec) {
+                                log::warn!("Invalid deltas from the simulation: {error}");
+                                break;
+                            }
+                        }
+                        Ok(Some(ToGui::Messages(messages))) => {
+                            for message in messages {
+                                bridge_sender.send_lossy(message);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(error) => {
+                            log::warn!("Invalid frame from the simulation: {error}");
+                            break;
+                        }
+                    }
+                }
+                log::info!("Disconnected from the simulation");
+                connected.store(false, Ordering::Release);
+            })
+        };
+
+        let writer_thread: JoinHandle<()> = {
+            let gui_data = gui_data.clone();
+            let control = control.clone();
+            let connected = Arc::clone(&connected);
+            let known: (u64, bool, f64) = (
+                gui_data.generation(),
+                control.is_paused(),
+                control.time_scale(),
+            );
+            thread::spawn(move || {
+                if let Err(error) = forward_gui(
+                    writer,
+                    &gui_data,
+                    known,
+                    &bridge_receiver,
+                    &control,
+                    &connected,
+                ) {
+                    log::warn!("Cannot reach the simulation: {error}");
+                }
+                connected.store(false, Ordering::Release);
+            })
+        };
+
+        Ok(Self {
+            simulation_data,
+            gui_data,
+            sender,
+            receiver,
+            control,
+            stream,
+            connected,
+            threads: vec![reader_thread, writer_thread],
+        })
+    }
+}
+
+impl<Simulation> RemoteClient<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    /// Latest data published by the remote simulation.
+    #[inline]
+    #[must_use]
+    pub const fn simulation_data(&self) -> &Shared<Simulation::SimulationData> {
+        &self.simulation_data
+    }
+
+    /// GUI data forwarded to the remote simulation whenever it is written.
+    #[inline]
+    #[must_use]
+    pub const fn gui_data(&self) -> &Shared<Simulation::GuiData> {
+        &self.gui_data
+    }
+
+    /// Sender forwarding messages to the remote simulation.
+    #[inline]
+    #[must_use]
+    pub const fn sender(&self) -> &MessageSender<Simulation::MessageFromGui> {
+        &self.sender
+    }
+
+    /// Receiver of the messages sent by the remote simulation.
+    #[inline]
+    #[must_use]
+    pub const fn receiver(&self) -> &MessageReceiver<Simulation::MessageToGui> {
+        &self.receiver
+    }
+
+    /// Transport controls forwarded to the remote simulation.
+    #[inline]
+    #[must_use]
+    pub const fn control(&self) -> &SimulationControl {
+        &self.control
+    }
+
+    /// Whether the connection to the simulation is still open.
+    #[inline]
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Acquire)
+    }
+}
+
+impl<Simulation> Drop for RemoteClient<Simulation>
+where
+    Simulation: MultiAgentSimulation,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.connected.store(false, Ordering::Release);
+        self.stream.shutdown();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Apply `deltas` to a copy of the current data and store it.
+fn apply_deltas<T: Clone>(
+    simulation_data: &Shared<T>,
+    deltas: &[Vec<u8>],
+    codec: Option<DeltaCodec<T>>,
+) -> Result<()> {
+    let codec: DeltaCodec<T> =
+        codec.ok_or_else(|| Error::Remote(String::from("Deltas were not asked for")))?;
+    let mut data: T = T::clone(&simulation_data.load());
+    for delta in deltas {
+        (codec.apply)(&mut data, delta)?;
+    }
+    simulation_data.store(data);
+    Ok(())
+}
+
+/// Send the messages, the changes of the GUI data and the transport requests of the
+/// GUI side until disconnected.
+///
+/// `known` holds the generation of the GUI data, the pause state and the time scale
+/// already known to the simulation.
+fn forward_gui<GuiData, Message>(
+    mut writer: Stream,
+    gui_data: &Shared<GuiData>,
+    known: (u64, bool, f64),
+    receiver: &MessageReceiver<Message>,
+    control: &SimulationControl,
+    connected: &AtomicBool,
+) -> Result<()>
+where
+    GuiData: Serialize,
+    Message: Serialize,
+{
+    let (mut last_generation, paused, time_scale) = known;
+    let mut last_control: (bool, f64) = (paused, time_scale);
+    while connected.load(Ordering::Acquire) {
+        let mut messages: Vec<Message> = receiver.recv_timeout(POLL_PERIOD).into_iter().collect();
+        messages.extend(receiver.drain());
+        if !messages.is_empty() {
+            write_frame(&mut writer, &ToSimulation::<&GuiData, _>::Messages(messages))?;
+        }
+
+        let generation: u64 = gui_data.generation();
+        if generation != last_generation {
+            last_generation = generation;
+            write_frame(
+                &mut writer,
+                &ToSimulation::<_, Message>::GuiData(&**gui_data.load()),
+            )?;
+        }
+
+        let state: (bool, f64) = (control.is_paused(), control.time_scale());
+        if state != last_control {
+            last_control = state;
+            let (paused, time_scale) = state;
+            write_frame(
+                &mut writer,
+                &ToSimulation::<&GuiData, Message>::Control { paused, time_scale },
+            )?;
+        }
+
+        let mut steps: u64 = 0;
+        while control.take_step() {
+            steps += 1;
+        }
+        if steps > 0 {
+            write_frame(&mut writer, &ToSimulation::<&GuiData, Message>::Step(steps))?;
+        }
+
+        if control.take_restart() {
+            let seed: u64 = control.seed();
+            write_frame(&mut writer, &ToSimulation::<&GuiData, Message>::Restart { seed })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuntimeBuilder;
+    use multi_agent_core::{CancellationToken, SeedFactory};
+    use std::time::Instant;
+
+    /// Adds the GUI data to a total on every tick and echoes every message.
+    #[derive(Debug)]
+    struct Echo {
+        total: u64,
+    }
+
+    impl MultiAgentSimulation for Echo {
+        const FREQUENCY_IN_HZ: u64 = 200;
+
+        type SimulationData = u64;
+        type GuiData = u64;
+        type MessageFromGui = String;
+        type MessageToGui = String;
+
+        fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self { total: 0 })
+        }
+
+        fn update<F>(
+            &mut self,
+            gui_data: Self::GuiData,
+            messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.total += gui_data;
+            for message in messages {
+                send_message_to_gui(message);
+            }
+            Ok(&self.total)
+        }
+    }
+
+    /// Total of the GUI data, and the tick it was published by, left out of the deltas.
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct Tally {
+        total: u64,
+        tick: u64,
+    }
+
+    impl Diffable for Tally {
+        type Delta = u64;
+
+        fn diff(&self, previous: &Self) -> Self::Delta {
+            self.total.wrapping_sub(previous.total)
+        }
+
+        fn apply(&mut self, delta: &Self::Delta) {
+            self.total = self.total.wrapping_add(*delta);
+        }
+    }
+
+    /// Adds the GUI data to a [`Tally`] on every tick.
+    #[derive(Debug)]
+    struct Tallier {
+        tally: Tally,
+    }
+
+    impl MultiAgentSimulation for Tallier {
+        const FREQUENCY_IN_HZ: u64 = 200;
+
+        type SimulationData = Tally;
+        type GuiData = u64;
+        type MessageFromGui = ();
+        type MessageToGui = ();
+
+        fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self {
+                tally: Tally::default(),
+            })
+        }
+
+        fn update<F>(
+            &mut self,
+            gui_data: Self::GuiData,
+            _messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            self.tally.total += gui_data;
+            self.tally.tick += 1;
+            Ok(&self.tally)
+        }
+    }
+
+    fn eventually<C: FnMut() -> bool>(mut condition: C) -> bool {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    fn exchange(address: &RemoteAddress) {
+        let server = RemoteServer::bind(address).unwrap();
+        let address: RemoteAddress = server.local_address().unwrap();
+        let builder: RuntimeBuilder<u64, String, u64, String> = RuntimeBuilder::new();
+        let control: SimulationControl = builder.control();
+        let serving = thread::spawn(move || builder.serve::<Echo>(&server));
+
+        let client = RemoteClient::<Echo>::connect(&address).unwrap();
+        client.gui_data().store(1);
+        assert!(eventually(|| **client.simulation_data().load() > 0));
+
+        client.sender().send(String::from("ping")).unwrap();
+        assert!(eventually(|| client.receiver().try_recv().as_deref() == Some("ping")));
+
+        client.control().pause();
+        assert!(eventually(|| control.is_paused()));
+
+        drop(client);
+        control.stop();
+        serving.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_remote_address_parse() {
+        assert_eq!(
+            "127.0.0.1:7878".parse::<RemoteAddress>().unwrap(),
+            RemoteAddress::Tcp(String::from("127.0.0.1:7878"))
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            "unix:/tmp/simulation.sock"
+                .parse::<RemoteAddress>()
+                .unwrap()
+                .to_string(),
+            "unix:/tmp/simulation.sock"
+        );
+    }
+
+    #[test]
+    fn test_remote_frame_round_trip() {
+        let mut bytes: Vec<u8> = Vec::new();
+        write_frame(&mut bytes, &ToGui::<u64, String>::Snapshot(42)).unwrap();
+        write_frame(&mut bytes, &ToGui::<u64, String>::Messages(vec![String::from("hi")])).unwrap();
+
+        let mut reader: &[u8] = &bytes;
+        assert!(matches!(
+            read_frame(&mut reader).unwrap(),
+            Some(ToGui::<u64, String>::Snapshot(42))
+        ));
+        assert!(matches!(
+            read_frame(&mut reader).unwrap(),
+            Some(ToGui::<u64, String>::Messages(messages)) if messages == ["hi"]
+        ));
+        assert!(read_frame::<_, ToGui<u64, String>>(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_remote_frame_rejects_bad_lengths() {
+        let too_large: [u8; 4] = u32::try_from(MAX_FRAME_LEN + 1).unwrap().to_le_bytes();
+        let mut reader: &[u8] = &too_large;
+        assert!(read_frame::<_, ToGui<u64, String>>(&mut reader).is_err());
+
+        let truncated: Vec<u8> = [&1_000_u32.to_le_bytes()[..], &[0; 10]].concat();
+        let mut reader: &[u8] = &truncated;
+        assert!(read_frame::<_, ToGui<u64, String>>(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_remote_over_tcp() {
+        exchange(&RemoteAddress::Tcp(String::from("127.0.0.1:0")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_over_unix_socket() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("multi-agent-{}.sock", std::process::id()));
+        exchange(&RemoteAddress::Unix(path.clone()));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_remote_deltas() {
+        let server = RemoteServer::bind(&RemoteAddress::Tcp(String::from("127.0.0.1:0"))).unwrap();
+        let address: RemoteAddress = server.local_address().unwrap();
+        let builder: RuntimeBuilder<u64, (), Tally> =
+            RuntimeBuilder::new().initial_gui_data(2).delta_streaming();
+        let control: SimulationControl = builder.control();
+        let serving = thread::spawn(move || builder.serve::<Tallier>(&server));
+
+        let client =
+            RemoteClient::<Tallier>::connect_with_deltas(&address, DeltaCodec::new()).unwrap();
+        assert!(eventually(|| {
+            let tally: Arc<Tally> = client.simulation_data().load_full();
+            tally.total > 2 * tally.tick
+        }));
+        assert_eq!(client.simulation_data().load().total % 2, 0);
+
+        drop(client);
+        control.stop();
+        serving.join().unwrap().unwrap();
+    }
+}
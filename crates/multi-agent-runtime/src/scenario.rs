@@ -0,0 +1,488 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use multi_agent_sync::{ScriptRequest, ScriptStatus, Shared};
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug, Formatter},
+    mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Metric computed from the simulation data before every scripted tick.
+type Metric<SimulationData> = Arc<dyn Fn(&SimulationData) -> f64 + Send + Sync>;
+
+/// Outcome of a conversion between Rust values and script values.
+type Conversion<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+/// Scenario scripts controlling a simulation without recompiling it.
+///
+/// A scenario script is written in [Rhai](https://rhai.rs) and runs on the
+/// simulation thread before every tick, with:
+/// - `tick`: the number of the tick about to run
+/// - `gui`: the GUI data, as an object map; changes made to it are published
+///   before the tick, as if they were made in the GUI
+/// - `metrics`: the registered metrics, computed from the latest simulation data
+/// - `send(message)`: sends a message to the simulation, delivered on this tick
+///
+/// Messages are written the way serde represents their enum: `send("Reset")`
+/// for a unit variant, `send(#{ Spawn: 500 })` for a newtype variant and
+/// `send(#{ Spawn: #{ count: 500 } })` for a struct variant.
+///
+/// Give the scenario to `RuntimeBuilder::scenario` or `HeadlessRunner::with_scenario`,
+/// then load scripts with `SimulationControl::load_script`, from the toolbar
+/// of the GUI, or upfront with [`file`](Self::file).
+///
+/// # Example
+///
+/// ```rhai
+/// if tick == 100 { send(#{ Spawn: 500 }); }
+/// if tick == 300 { gui.separation = 3.0; }
+/// if tick == 600 || metrics.alive < 10.0 { send("Reset"); }
+/// ```
+///
+/// ```rust,ignore
+/// use multi_agent::{AppLauncher, Scenario};
+///
+/// AppLauncher::builder()
+///     .scenario(
+///         Scenario::new()
+///             .metric("alive", |data: &BoidsData| data.boids.len() as f64)
+///             .file("scenarios/lesson-1.rhai"),
+///     )
+///     .run::<Boids, BoidsGui>()
+/// ```
+pub struct Scenario<SimulationData, GuiData, Message> {
+    metrics: Vec<(String, Metric<SimulationData>)>,
+    script: Option<Source>,
+    gui_data_to_dynamic: fn(&GuiData) -> Conversion<Dynamic>,
+    gui_data_from_dynamic: fn(&Dynamic) -> Conversion<GuiData>,
+    message_from_dynamic: fn(&Dynamic) -> Conversion<Message>,
+}
+
+/// Script given to a scenario before the simulation starts.
+#[derive(Debug, Clone)]
+enum Source {
+    File(PathBuf),
+    Inline { name: String, code: String },
+}
+
+impl<SimulationData, GuiData, Message> Scenario<SimulationData, GuiData, Message> {
+    /// Create a scenario without any metric or script.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self
+    where
+        GuiData: Serialize + DeserializeOwned,
+        Message: DeserializeOwned,
+    {
+        Self {
+            metrics: Vec::new(),
+            script: None,
+            gui_data_to_dynamic: |gui_data| rhai::serde::to_dynamic(gui_data),
+            gui_data_from_dynamic: |value| rhai::serde::from_dynamic(value),
+            message_from_dynamic: |value| rhai::serde::from_dynamic(value),
+        }
+    }
+
+    /// Expose the metric `name` to scripts, computed from the latest simulation data.
+    #[inline]
+    #[must_use]
+    pub fn metric<S, F>(mut self, name: S, metric: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&SimulationData) -> f64 + Send + Sync + 'static,
+    {
+        self.metrics.push((name.into(), Arc::new(metric)));
+        self
+    }
+
+    /// Load the script stored at `path` when the simulation starts.
+    ///
+    /// Default: no script
+    #[inline]
+    #[must_use]
+    pub fn file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.script = Some(Source::File(path.into()));
+        self
+    }
+
+    /// Load the script `code`, reported under `name`, when the simulation starts.
+    ///
+    /// Default: no script
+    #[inline]
+    #[must_use]
+    pub fn source<N, C>(mut self, name: N, code: C) -> Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        self.script = Some(Source::Inline {
+            name: name.into(),
+            code: code.into(),
+        });
+        self
+    }
+}
+
+impl<SimulationData, GuiData, Message> Default for Scenario<SimulationData, GuiData, Message>
+where
+    GuiData: Serialize + DeserializeOwned,
+    Message: DeserializeOwned,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<SimulationData, GuiData, Message> Clone for Scenario<SimulationData, GuiData, Message> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            metrics: self.metrics.clone(),
+            script: self.script.clone(),
+            gui_data_to_dynamic: self.gui_data_to_dynamic,
+            gui_data_from_dynamic: self.gui_data_from_dynamic,
+            message_from_dynamic: self.message_from_dynamic,
+        }
+    }
+}
+
+impl<SimulationData, GuiData, Message> Debug for Scenario<SimulationData, GuiData, Message> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scenario")
+            .field(
+                "metrics",
+                &self
+                    .metrics
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<&String>>(),
+            )
+            .field("script", &self.script)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs the scripts of a [`Scenario`] on the simulation thread.
+pub struct ScenarioRunner<SimulationData, GuiData, Message> {
+    scenario: Scenario<SimulationData, GuiData, Message>,
+    engine: Engine,
+    outbox: Arc<Mutex<Vec<Dynamic>>>,
+    script: Option<(String, AST)>,
+    status: ScriptStatus,
+    status_changed: bool,
+}
+
+impl<SimulationData, GuiData, Message> ScenarioRunner<SimulationData, GuiData, Message>
+where
+    GuiData: Clone,
+{
+    /// Most operations a script may execute on a single tick, so that an
+    /// endless loop fails instead of freezing the simulation.
+    const MAX_OPERATIONS: u64 = 10_000_000;
+
+    /// Set up the script engine and load the script given to `scenario`, if any.
+    pub fn new(mut scenario: Scenario<SimulationData, GuiData, Message>) -> Self {
+        let outbox: Arc<Mutex<Vec<Dynamic>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(Self::MAX_OPERATIONS);
+        engine.on_print(|text| log::info!("Scenario: {text}"));
+        engine.on_debug(|text, _, position| log::debug!("Scenario at {position}: {text}"));
+        {
+            let outbox = Arc::clone(&outbox);
+            engine.register_fn("send", move |message: Dynamic| {
+                outbox
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(message);
+            });
+        }
+
+        let source: Option<Source> = scenario.script.take();
+        let mut runner = Self {
+            scenario,
+            engine,
+            outbox,
+            script: None,
+            status: ScriptStatus::Idle,
+            status_changed: true,
+        };
+        match source {
+            Some(Source::File(path)) => runner.load(&path),
+            Some(Source::Inline { name, code }) => runner.compile(name, &code),
+            None => {}
+        }
+        runner
+    }
+
+    /// Load or unload the script as asked for by `request`.
+    pub fn handle(&mut self, request: &ScriptRequest) {
+        match request {
+            ScriptRequest::Load(path) => self.load(path),
+            ScriptRequest::Unload => {
+                if let Some((name, _)) = self.script.take() {
+                    log::info!("Scenario script '{name}' unloaded");
+                }
+                self.set_status(ScriptStatus::Idle);
+            }
+        }
+    }
+
+    /// State of the script, if it changed since the last call.
+    pub fn take_status_change(&mut self) -> Option<ScriptStatus> {
+        mem::take(&mut self.status_changed).then(|| self.status.clone())
+    }
+
+    /// Run the script for `tick` and return the messages it sent.
+    ///
+    /// Changes made to the GUI data are stored in `gui_data`. A script that
+    /// fails is unloaded, and the messages it sent before failing are dropped.
+    pub fn run(
+        &mut self,
+        tick: u64,
+        simulation_data: &SimulationData,
+        gui_data: &Shared<GuiData>,
+    ) -> Vec<Message> {
+        let Some((name, _)) = &self.script else {
+            return Vec::new();
+        };
+        let name: String = name.clone();
+
+        match self.evaluate(tick, simulation_data, gui_data) {
+            Ok(messages) => messages,
+            Err(error) => {
+                log::warn!("Scenario script '{name}' failed at tick {tick}: {error}");
+                self.script = None;
+                self.set_status(ScriptStatus::Failed {
+                    name,
+                    message: error.to_string(),
+                });
+                Vec::new()
+            }
+        }
+    }
+
+    fn evaluate(
+        &self,
+        tick: u64,
+        simulation_data: &SimulationData,
+        gui_data: &Shared<GuiData>,
+    ) -> Conversion<Vec<Message>> {
+        let Some((_, ast)) = &self.script else {
+            return Ok(Vec::new());
+        };
+
+        let gui: Dynamic = (self.scenario.gui_data_to_dynamic)(&gui_data.load())?;
+        let before: String = format!("{gui:?}");
+        let metrics: Map = self
+            .scenario
+            .metrics
+            .iter()
+            .map(|(name, metric)| {
+                (
+                    name.as_str().into(),
+                    Dynamic::from_float(metric(simulation_data)),
+                )
+            })
+            .collect();
+
+        let mut scope = Scope::new();
+        scope.push("tick", i64::try_from(tick).unwrap_or(i64::MAX));
+        scope.push_dynamic("gui", gui);
+        scope.push("metrics", metrics);
+
+        self.outbox_mut().clear();
+        self.engine.run_ast_with_scope(&mut scope, ast)?;
+        let sent: Vec<Dynamic> = mem::take(&mut *self.outbox_mut());
+
+        if let Some(gui) = scope.get("gui")
+            && format!("{gui:?}") != before
+        {
+            gui_data.store((self.scenario.gui_data_from_dynamic)(gui)?);
+        }
+        sent.iter()
+            .map(|message| (self.scenario.message_from_dynamic)(message))
+            .collect()
+    }
+
+    fn load(&mut self, path: &Path) {
+        let name: String = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        match self.engine.compile_file(path.to_path_buf()) {
+            Ok(ast) => self.install(name, ast),
+            Err(error) => self.reject(name, &error.to_string()),
+        }
+    }
+
+    fn compile(&mut self, name: String, code: &str) {
+        match self.engine.compile(code) {
+            Ok(ast) => self.install(name, ast),
+            Err(error) => self.reject(name, &error.to_string()),
+        }
+    }
+
+    fn install(&mut self, name: String, ast: AST) {
+        log::info!("Scenario script '{name}' loaded");
+        self.script = Some((name.clone(), ast));
+        self.set_status(ScriptStatus::Running { name });
+    }
+
+    fn reject(&mut self, name: String, message: &str) {
+        log::warn!("Cannot load the scenario script '{name}': {message}");
+        self.script = None;
+        self.set_status(ScriptStatus::Failed {
+            name,
+            message: String::from(message),
+        });
+    }
+
+    fn set_status(&mut self, status: ScriptStatus) {
+        self.status = status;
+        self.status_changed = true;
+    }
+
+    fn outbox_mut(&self) -> MutexGuard<'_, Vec<Dynamic>> {
+        self.outbox.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<SimulationData, GuiData, Message> Debug for ScenarioRunner<SimulationData, GuiData, Message> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScenarioRunner")
+            .field("scenario", &self.scenario)
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessRunner;
+    use multi_agent_core::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
+    use serde::Deserialize;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        speed: f64,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    enum Command {
+        Spawn(u64),
+        Reset,
+    }
+
+    #[derive(Debug)]
+    struct Population {
+        agents: u64,
+    }
+
+    impl MultiAgentSimulation for Population {
+        const FREQUENCY_IN_HZ: u64 = 10;
+
+        type SimulationData = u64;
+        type GuiData = Settings;
+        type MessageFromGui = Command;
+        type MessageToGui = ();
+
+        fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
+            Ok(Self { agents: 0 })
+        }
+
+        fn update<F>(
+            &mut self,
+            _gui_data: Self::GuiData,
+            messages: Vec<Self::MessageFromGui>,
+            _delta_time: Duration,
+            _send_message_to_gui: F,
+            _cancellation: &CancellationToken,
+        ) -> Result<&Self::SimulationData>
+        where
+            F: Fn(Self::MessageToGui),
+        {
+            for message in messages {
+                match message {
+                    Command::Spawn(count) => self.agents += count,
+                    Command::Reset => self.agents = 0,
+                }
+            }
+            Ok(&self.agents)
+        }
+    }
+
+    fn scenario() -> Scenario<u64, Settings, Command> {
+        Scenario::new().metric("agents", |agents: &u64| {
+            f64::from(u32::try_from(*agents).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_scenario_drives_the_simulation() {
+        let script = r#"
+            if tick == 2 { send(#{ Spawn: 5 }); }
+            if tick == 4 { gui.speed = 3.0; }
+            if metrics.agents >= 5.0 && tick == 6 { send("Reset"); }
+        "#;
+        let mut runner = HeadlessRunner::<Population>::new(Settings::default())
+            .unwrap()
+            .with_scenario(scenario().source("lesson", script));
+
+        assert_eq!(runner.run_ticks(4).unwrap().data, 5);
+        assert!(runner.gui_data().load().speed.abs() < f64::EPSILON);
+
+        runner.run_ticks(1).unwrap();
+        assert_eq!(**runner.gui_data().load(), Settings { speed: 3.0 });
+
+        assert_eq!(runner.run_ticks(2).unwrap().data, 0);
+    }
+
+    #[test]
+    fn test_scenario_unloads_failing_scripts() {
+        let mut runner = ScenarioRunner::new(scenario().source("broken", "send(#{ Explode: 1 });"));
+        let gui_data = Shared::new(Settings::default());
+        assert_eq!(
+            runner.take_status_change(),
+            Some(ScriptStatus::Running {
+                name: String::from("broken")
+            })
+        );
+
+        assert!(runner.run(0, &0, &gui_data).is_empty());
+        assert!(matches!(
+            runner.take_status_change(),
+            Some(ScriptStatus::Failed { name, .. }) if name == "broken"
+        ));
+        assert_eq!(runner.take_status_change(), None);
+
+        runner.handle(&ScriptRequest::Load(PathBuf::from("missing.rhai")));
+        assert!(matches!(
+            runner.take_status_change(),
+            Some(ScriptStatus::Failed { name, .. }) if name == "missing.rhai"
+        ));
+
+        runner.handle(&ScriptRequest::Unload);
+        assert_eq!(runner.take_status_change(), Some(ScriptStatus::Idle));
+    }
+}
@@ -0,0 +1,255 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+/// How the runtime paces the calls to `MultiAgentSimulation::update`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Schedule {
+    /// Call `update()` once per period with the wall-clock time elapsed since the previous call.
+    ///
+    /// Simple and always up to date, but results depend on the speed of the machine.
+    #[default]
+    Variable,
+
+    /// Call `update()` with a constant simulated time step.
+    ///
+    /// Elapsed wall-clock time is accumulated and consumed in steps of `delta_time`,
+    /// so two runs receive exactly the same sequence of time steps.
+    Fixed {
+        /// Simulated time step passed to every `update()` call.
+        delta_time: Duration,
+        /// Maximum number of steps executed in a row to catch up with the wall clock.
+        max_catch_up_steps: u32,
+        /// What to do when the simulation cannot keep up with the wall clock.
+        when_late: CatchUpPolicy,
+    },
+}
+
+/// Policy applied by a [`Schedule::Fixed`] when steps take longer than `delta_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CatchUpPolicy {
+    /// Run up to `max_catch_up_steps` steps, then forget the time that is still owed.
+    ///
+    /// The simulation stays responsive but falls behind the wall clock.
+    #[default]
+    DropTime,
+
+    /// Never run more than one step per period.
+    ///
+    /// Simulated time simply flows slower than the wall clock while the machine is overloaded.
+    SlowDown,
+
+    /// Run up to `max_catch_up_steps` steps and carry the rest over to the next period.
+    ///
+    /// No simulated time is ever lost, but a simulation that is persistently too slow
+    /// accumulates an ever-growing backlog.
+    Spiral,
+}
+
+impl Schedule {
+    /// Default number of catch-up steps of [`Schedule::fixed`].
+    pub const DEFAULT_MAX_CATCH_UP_STEPS: u32 = 5;
+
+    /// Fixed schedule running at `frequency_in_hz` steps per second with the default policy.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_runtime::Schedule;
+    /// use std::time::Duration;
+    ///
+    /// let schedule = Schedule::fixed(50);
+    /// assert_eq!(schedule.period(30), Duration::from_millis(20));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn fixed(frequency_in_hz: u64) -> Self {
+        Self::Fixed {
+            delta_time: period_of(frequency_in_hz),
+            max_catch_up_steps: Self::DEFAULT_MAX_CATCH_UP_STEPS,
+            when_late: CatchUpPolicy::DropTime,
+        }
+    }
+
+    /// Wall-clock period between two wake-ups of the simulation thread.
+    ///
+    /// A fixed schedule wakes up once per `delta_time`; a variable one once per
+    /// `1 / frequency_in_hz` seconds.
+    #[inline]
+    #[must_use]
+    pub const fn period(&self, frequency_in_hz: u64) -> Duration {
+        match self {
+            Self::Variable => period_of(frequency_in_hz),
+            Self::Fixed { delta_time, .. } => *delta_time,
+        }
+    }
+}
+
+/// Duration of one period at the given frequency, without truncating to milliseconds.
+#[inline]
+pub const fn period_of(frequency_in_hz: u64) -> Duration {
+    let frequency_in_hz: u64 = if frequency_in_hz == 0 {
+        1
+    } else {
+        frequency_in_hz
+    };
+    Duration::from_nanos(1_000_000_000 / frequency_in_hz)
+}
+
+/// Steps to execute after a wake-up of the simulation thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Steps {
+    /// Number of `update()` calls to make.
+    pub count: u32,
+    /// Time step passed to each of them.
+    pub delta_time: Duration,
+}
+
+/// Turns elapsed wall-clock time into simulation steps according to a [`Schedule`].
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    schedule: Schedule,
+    accumulator: Duration,
+}
+
+impl Scheduler {
+    #[inline]
+    pub const fn new(schedule: Schedule) -> Self {
+        Self {
+            schedule,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Account for `elapsed` wall-clock time and return the steps to execute.
+    pub fn advance(&mut self, elapsed: Duration) -> Steps {
+        let Schedule::Fixed {
+            delta_time,
+            max_catch_up_steps,
+            when_late,
+        } = self.schedule
+        else {
+            return Steps {
+                count: 1,
+                delta_time: elapsed,
+            };
+        };
+
+        if delta_time.is_zero() {
+            return Steps {
+                count: 0,
+                delta_time,
+            };
+        }
+
+        self.accumulator = self.accumulator.saturating_add(elapsed);
+        let owed: u128 = self.accumulator.as_nanos() / delta_time.as_nanos();
+        let owed: u32 = u32::try_from(owed).unwrap_or(u32::MAX);
+
+        let count: u32 = match when_late {
+            CatchUpPolicy::DropTime | CatchUpPolicy::Spiral => owed.min(max_catch_up_steps.max(1)),
+            CatchUpPolicy::SlowDown => owed.min(1),
+        };
+        self.accumulator = self.accumulator.saturating_sub(delta_time * count);
+
+        if owed > count && when_late != CatchUpPolicy::Spiral {
+            // Forget the backlog but keep the fraction of a step already elapsed.
+            self.accumulator = Duration::from_nanos(
+                u64::try_from(self.accumulator.as_nanos() % delta_time.as_nanos())
+                    .unwrap_or_default(),
+            );
+        }
+
+        Steps { count, delta_time }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: Duration = Duration::from_millis(10);
+
+    fn fixed(max_catch_up_steps: u32, when_late: CatchUpPolicy) -> Scheduler {
+        Scheduler::new(Schedule::Fixed {
+            delta_time: DT,
+            max_catch_up_steps,
+            when_late,
+        })
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(period_of(0), Duration::from_secs(1));
+        assert_eq!(Schedule::fixed(50).period(30), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_variable_schedule() {
+        let mut scheduler = Scheduler::new(Schedule::Variable);
+
+        let steps = scheduler.advance(Duration::from_millis(42));
+        assert_eq!(steps.count, 1);
+        assert_eq!(steps.delta_time, Duration::from_millis(42));
+    }
+
+    #[test]
+    fn test_fixed_schedule_accumulates() {
+        let mut scheduler = fixed(5, CatchUpPolicy::DropTime);
+
+        assert_eq!(scheduler.advance(Duration::from_millis(6)).count, 0);
+        assert_eq!(scheduler.advance(Duration::from_millis(6)).count, 1);
+        assert_eq!(scheduler.advance(Duration::from_millis(28)).count, 3);
+        assert_eq!(scheduler.advance(Duration::ZERO).delta_time, DT);
+    }
+
+    #[test]
+    fn test_drop_time_policy() {
+        let mut scheduler = fixed(3, CatchUpPolicy::DropTime);
+
+        assert_eq!(scheduler.advance(Duration::from_millis(105)).count, 3);
+        // Only the fraction of a step survives.
+        assert_eq!(scheduler.advance(Duration::from_millis(5)).count, 1);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 0);
+    }
+
+    #[test]
+    fn test_slow_down_policy() {
+        let mut scheduler = fixed(3, CatchUpPolicy::SlowDown);
+
+        assert_eq!(scheduler.advance(Duration::from_millis(100)).count, 1);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 0);
+    }
+
+    #[test]
+    fn test_spiral_policy() {
+        let mut scheduler = fixed(3, CatchUpPolicy::Spiral);
+
+        assert_eq!(scheduler.advance(Duration::from_millis(100)).count, 3);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 3);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 3);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 1);
+        assert_eq!(scheduler.advance(Duration::ZERO).count, 0);
+    }
+
+    #[test]
+    fn test_huge_elapsed_time_saturates() {
+        let mut scheduler = fixed(3, CatchUpPolicy::Spiral);
+
+        assert_eq!(scheduler.advance(Duration::MAX).count, 3);
+        assert_eq!(scheduler.advance(Duration::MAX).count, 3);
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use multi_agent_core::{Result, Session};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug, Formatter},
+    path::Path,
+};
+
+/// Saves and loads the session files of a simulation on the simulation thread.
+///
+/// Captures the serialization of `GuiData` and `SimulationData` where their
+/// `Serialize` and `Deserialize` implementations are known, so that the driver
+/// does not need them.
+pub struct SessionFiles<GuiData, SimulationData> {
+    save: fn(&Session<GuiData, SimulationData>, &Path) -> Result<()>,
+    load: fn(&Path) -> Result<Session<GuiData, SimulationData>>,
+}
+
+impl<GuiData, SimulationData> SessionFiles<GuiData, SimulationData>
+where
+    GuiData: Serialize + DeserializeOwned,
+    SimulationData: Serialize + DeserializeOwned,
+{
+    /// Save and load sessions as JSON files.
+    #[inline]
+    pub fn json() -> Self {
+        Self {
+            save: |session, path| session.save(path),
+            load: |path| Session::load(path),
+        }
+    }
+}
+
+impl<GuiData, SimulationData> SessionFiles<GuiData, SimulationData> {
+    /// Save `session` to the file `path`.
+    ///
+    /// # Errors
+    /// Returns `Error::Session` if the file cannot be written.
+    #[inline]
+    pub fn save(&self, session: &Session<GuiData, SimulationData>, path: &Path) -> Result<()> {
+        (self.save)(session, path)
+    }
+
+    /// Load the session stored in the file `path`.
+    ///
+    /// # Errors
+    /// Returns `Error::Session` if the file cannot be read, parsed, or was written
+    /// with another version of the format.
+    #[inline]
+    pub fn load(&self, path: &Path) -> Result<Session<GuiData, SimulationData>> {
+        (self.load)(path)
+    }
+}
+
+impl<GuiData, SimulationData> Clone for SessionFiles<GuiData, SimulationData> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            save: self.save,
+            load: self.load,
+        }
+    }
+}
+
+impl<GuiData, SimulationData> Debug for SessionFiles<GuiData, SimulationData> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionFiles").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+/// Measurements taken for a single simulation tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickStats {
+    /// Index of the tick, starting at 0.
+    pub tick: u64,
+    /// Time step passed to `update()`.
+    pub delta_time: Duration,
+    /// Wall-clock time spent inside `update()`.
+    pub update_time: Duration,
+    /// Wall-clock time spent cloning and publishing the data returned by `update()`.
+    pub publish_time: Duration,
+    /// Number of GUI messages delivered to the simulation during this tick.
+    pub messages: usize,
+}
+
+/// Measures how many ticks are executed per wall-clock second.
+#[derive(Debug, Clone, Copy)]
+pub struct RateMeter {
+    window_start: Instant,
+    ticks: u32,
+    rate: f64,
+}
+
+impl RateMeter {
+    /// Duration over which ticks are counted before the rate is updated.
+    const WINDOW: Duration = Duration::from_millis(500);
+
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            ticks: 0,
+            rate: 0.0,
+        }
+    }
+
+    /// Count a tick executed at `now` and return the rate measured over the last window.
+    #[inline]
+    pub fn tick(&mut self, now: Instant) -> f64 {
+        self.ticks += 1;
+        let elapsed: Duration = now.duration_since(self.window_start);
+        if elapsed >= Self::WINDOW {
+            self.rate = f64::from(self.ticks) / elapsed.as_secs_f64();
+            self.window_start = now;
+            self.ticks = 0;
+        }
+        self.rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_meter() {
+        let start = Instant::now();
+        let mut meter = RateMeter::new();
+        meter.window_start = start;
+
+        for tick in 1..50_u64 {
+            assert!(meter.tick(start + Duration::from_millis(tick * 10)) < f64::EPSILON);
+        }
+
+        let rate: f64 = meter.tick(start + Duration::from_millis(500));
+        assert!((rate - 100.0).abs() < f64::EPSILON);
+    }
+}
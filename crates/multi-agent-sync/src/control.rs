@@ -0,0 +1,799 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::listeners::Listeners;
+use multi_agent_core::{CancellationToken, SeedFactory};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+/// Transport controls shared between the GUI, the runtime and the simulation thread.
+///
+/// The runtime owns play/pause, single-stepping, the time-scale multiplier and restarts, so
+/// simulations do not need to implement them. Every clone refers to the same state:
+/// the GUI toolbar, the launcher and any other thread can drive the simulation
+/// through their own copy.
+///
+/// While paused, the simulation thread blocks instead of calling `update()`.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::SimulationControl;
+///
+/// let control = SimulationControl::new();
+/// control.pause();
+/// control.step(3);
+/// assert!(control.is_paused());
+/// assert_eq!(control.pending_steps(), 3);
+///
+/// control.set_time_scale(2.0);
+/// control.resume();
+/// assert!(!control.is_paused());
+/// assert_eq!(control.time_scale(), 2.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SimulationControl {
+    inner: Arc<ControlInner>,
+}
+
+/// Error reported by the simulation thread, kept until the simulation is restarted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulationFailure {
+    /// Tick during which the simulation failed.
+    pub tick: u64,
+    /// Human-readable description of the error.
+    pub message: String,
+    /// Source location of the panic, if the simulation panicked.
+    pub location: Option<String>,
+}
+
+/// How the runtime recovered from a failure without halting the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecoveryAction {
+    /// The simulation was recreated with `MultiAgentSimulation::new`.
+    Restarted,
+    /// The simulation was restored from the data published by the last good tick.
+    RolledBack,
+}
+
+/// A failure the runtime recovered from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recovery {
+    /// The failure that triggered the recovery.
+    pub failure: SimulationFailure,
+    /// What the runtime did about it.
+    pub action: RecoveryAction,
+}
+
+/// Change of the scenario script asked for through [`SimulationControl`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptRequest {
+    /// Load the script stored in this file, replacing the current one.
+    Load(PathBuf),
+    /// Stop running the current script.
+    Unload,
+}
+
+/// State of the scenario script, as reported by the simulation thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptStatus {
+    /// No script is loaded.
+    Idle,
+    /// The script runs before every tick.
+    Running {
+        /// Name of the script, usually its file name.
+        name: String,
+    },
+    /// The script could not be loaded or failed while running, and was unloaded.
+    Failed {
+        /// Name of the script, usually its file name.
+        name: String,
+        /// Human-readable description of the error.
+        message: String,
+    },
+}
+
+/// Session file operation asked for through [`SimulationControl`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionRequest {
+    /// Save the running simulation to this file.
+    Save(PathBuf),
+    /// Replace the running simulation with the session stored in this file.
+    Open(PathBuf),
+}
+
+/// Outcome of the last session file operation, as reported by the simulation thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// No session was saved or opened yet.
+    Idle,
+    /// The simulation was saved to a file.
+    Saved {
+        /// Path of the session file.
+        path: PathBuf,
+        /// Number of ticks executed when the session was saved.
+        tick: u64,
+    },
+    /// The simulation was replaced with the session stored in a file.
+    Opened {
+        /// Path of the session file.
+        path: PathBuf,
+        /// Number of ticks executed when the session was saved.
+        tick: u64,
+    },
+    /// The session could not be saved or opened.
+    Failed {
+        /// Path of the session file.
+        path: PathBuf,
+        /// Human-readable description of the error.
+        message: String,
+    },
+}
+
+#[derive(Debug, Default)]
+struct ControlInner {
+    state: Mutex<ControlState>,
+    changed: Condvar,
+    listeners: Listeners,
+    cancellation: CancellationToken,
+}
+
+#[derive(Debug)]
+struct ControlState {
+    paused: bool,
+    pending_steps: u64,
+    time_scale: f64,
+    seed: u64,
+    stopped: bool,
+    failure: Option<SimulationFailure>,
+    restart_requested: bool,
+    rewind_requested: Option<u64>,
+    last_recovery: Option<Recovery>,
+    recovery_count: u64,
+    script_requested: Option<ScriptRequest>,
+    script_status: Option<ScriptStatus>,
+    session_requested: Option<SessionRequest>,
+    session_status: Option<SessionStatus>,
+}
+
+impl Default for ControlState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            paused: false,
+            pending_steps: 0,
+            time_scale: 1.0,
+            seed: SeedFactory::random().master(),
+            stopped: false,
+            failure: None,
+            restart_requested: false,
+            rewind_requested: None,
+            last_recovery: None,
+            recovery_count: 0,
+            script_requested: None,
+            script_status: None,
+            session_requested: None,
+            session_status: None,
+        }
+    }
+}
+
+impl ControlState {
+    #[inline]
+    const fn paused_or_failed(&self) -> bool {
+        self.paused || self.failure.is_some()
+    }
+}
+
+impl SimulationControl {
+    /// Largest multiplier accepted by [`set_time_scale`](Self::set_time_scale).
+    pub const MAX_TIME_SCALE: f64 = 1_000.0;
+
+    /// Create a new control in the running state with a time scale of 1.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop calling `update()` until [`resume`](Self::resume) is called.
+    #[inline]
+    pub fn pause(&self) {
+        self.modify(|state| state.paused = true);
+    }
+
+    /// Resume a paused simulation and forget steps that were not executed yet.
+    #[inline]
+    pub fn resume(&self) {
+        self.modify(|state| {
+            state.paused = false;
+            state.pending_steps = 0;
+        });
+    }
+
+    /// Pause a running simulation or resume a paused one.
+    #[inline]
+    pub fn toggle_pause(&self) {
+        if self.is_paused() {
+            self.resume();
+        } else {
+            self.pause();
+        }
+    }
+
+    /// Whether the simulation is paused.
+    #[inline]
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused
+    }
+
+    /// Pause the simulation and execute exactly `ticks` more ticks.
+    ///
+    /// Calls accumulate: stepping twice by 1 executes 2 ticks.
+    #[inline]
+    pub fn step(&self, ticks: u64) {
+        self.modify(|state| {
+            state.paused = true;
+            state.pending_steps = state.pending_steps.saturating_add(ticks);
+        });
+    }
+
+    /// Number of ticks requested with [`step`](Self::step) and not executed yet.
+    #[inline]
+    #[must_use]
+    pub fn pending_steps(&self) -> u64 {
+        self.lock().pending_steps
+    }
+
+    /// Set the multiplier applied to the flow of simulated time.
+    ///
+    /// `2.0` runs the simulation twice as fast, `0.5` at half speed.
+    /// Negative and non-finite values are treated as `0.0`, and values above
+    /// [`MAX_TIME_SCALE`](Self::MAX_TIME_SCALE) as that maximum.
+    #[inline]
+    pub fn set_time_scale(&self, time_scale: f64) {
+        let time_scale: f64 = if time_scale.is_finite() {
+            time_scale.clamp(0.0, Self::MAX_TIME_SCALE)
+        } else {
+            0.0
+        };
+        self.modify(|state| state.time_scale = time_scale);
+    }
+
+    /// Multiplier applied to the flow of simulated time.
+    #[inline]
+    #[must_use]
+    pub fn time_scale(&self) -> f64 {
+        self.lock().time_scale
+    }
+
+    /// Set the master seed used the next time the simulation is created or restarted.
+    #[inline]
+    pub fn set_seed(&self, seed: u64) {
+        self.modify(|state| state.seed = seed);
+    }
+
+    /// Master seed of the simulation.
+    ///
+    /// Defaults to a random seed, so that every launch differs unless a seed is set.
+    #[inline]
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.lock().seed
+    }
+
+    /// Seed factory derived from the master seed, handed to the simulation.
+    #[inline]
+    #[must_use]
+    pub fn seeds(&self) -> SeedFactory {
+        SeedFactory::new(self.seed())
+    }
+
+    /// Ask the simulation thread to stop, waking it up if it is paused and
+    /// cancelling the tick in progress.
+    #[inline]
+    pub fn stop(&self) {
+        self.inner.cancellation.cancel();
+        self.modify(|state| state.stopped = true);
+    }
+
+    /// Token passed to `MultiAgentSimulation::update`, cancelled by [`stop`](Self::stop).
+    #[inline]
+    #[must_use]
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.inner.cancellation.clone()
+    }
+
+    /// Whether [`stop`](Self::stop) has been called.
+    #[inline]
+    #[must_use]
+    pub fn is_stopped(&self) -> bool {
+        self.lock().stopped
+    }
+
+    /// Report that the simulation failed, halting it until it is restarted.
+    ///
+    /// Used by the simulation thread when `update()` returns an error.
+    #[inline]
+    pub fn fail(&self, failure: SimulationFailure) {
+        self.modify(|state| state.failure = Some(failure));
+    }
+
+    /// Error that halted the simulation, if any.
+    #[inline]
+    #[must_use]
+    pub fn failure(&self) -> Option<SimulationFailure> {
+        self.lock().failure.clone()
+    }
+
+    /// Report that the runtime recovered from a failure and kept the simulation running.
+    #[inline]
+    pub fn recovered(&self, recovery: Recovery) {
+        self.modify(|state| {
+            state.last_recovery = Some(recovery);
+            state.recovery_count = state.recovery_count.saturating_add(1);
+        });
+    }
+
+    /// Last failure the runtime recovered from, if any.
+    #[inline]
+    #[must_use]
+    pub fn last_recovery(&self) -> Option<Recovery> {
+        self.lock().last_recovery.clone()
+    }
+
+    /// Number of failures the runtime recovered from.
+    #[inline]
+    #[must_use]
+    pub fn recovery_count(&self) -> u64 {
+        self.lock().recovery_count
+    }
+
+    /// Ask the simulation thread to recreate the simulation.
+    ///
+    /// The current instance is dropped and `MultiAgentSimulation::new` is called
+    /// again with the current GUI data, without closing the window. The pause
+    /// state and the time scale are kept.
+    #[inline]
+    pub fn restart(&self) {
+        self.modify(|state| state.restart_requested = true);
+    }
+
+    /// Consume a restart request, if any, and clear the reported failure.
+    ///
+    /// Used by the simulation thread before recreating the simulation.
+    #[inline]
+    #[must_use]
+    pub fn take_restart(&self) -> bool {
+        let mut state = self.lock();
+        if !state.restart_requested {
+            return false;
+        }
+        state.restart_requested = false;
+        state.failure = None;
+        true
+    }
+
+    /// Ask the simulation thread to resume the simulation from a past tick.
+    ///
+    /// The simulation is recreated with `MultiAgentSimulation::restore` from the
+    /// frame of that tick kept in the timeline, and the following frames are dropped.
+    #[inline]
+    pub fn rewind_to(&self, tick: u64) {
+        self.modify(|state| state.rewind_requested = Some(tick));
+    }
+
+    /// Consume a rewind request, if any.
+    ///
+    /// Used by the simulation thread before restoring the simulation.
+    #[inline]
+    #[must_use]
+    pub fn take_rewind(&self) -> Option<u64> {
+        self.lock().rewind_requested.take()
+    }
+
+    /// Ask the simulation thread to run the scenario script stored at `path` before every tick.
+    ///
+    /// Only honored when the runtime was given a scenario engine, see
+    /// [`script_status`](Self::script_status).
+    #[inline]
+    pub fn load_script<P: Into<PathBuf>>(&self, path: P) {
+        self.modify(|state| state.script_requested = Some(ScriptRequest::Load(path.into())));
+    }
+
+    /// Ask the simulation thread to stop running the current scenario script.
+    #[inline]
+    pub fn unload_script(&self) {
+        self.modify(|state| state.script_requested = Some(ScriptRequest::Unload));
+    }
+
+    /// Consume a script request, if any.
+    ///
+    /// Used by the simulation thread before loading or unloading the script.
+    #[inline]
+    #[must_use]
+    pub fn take_script_request(&self) -> Option<ScriptRequest> {
+        self.lock().script_requested.take()
+    }
+
+    /// Report the state of the scenario script.
+    ///
+    /// Used by the simulation thread when it has a scenario engine.
+    #[inline]
+    pub fn set_script_status(&self, status: ScriptStatus) {
+        self.modify(|state| state.script_status = Some(status));
+    }
+
+    /// State of the scenario script, or `None` if the runtime cannot run scripts.
+    #[inline]
+    #[must_use]
+    pub fn script_status(&self) -> Option<ScriptStatus> {
+        self.lock().script_status.clone()
+    }
+
+    /// Ask the simulation thread to save the running simulation to the session file `path`.
+    ///
+    /// Only honored when the runtime can save sessions, see
+    /// [`session_status`](Self::session_status).
+    #[inline]
+    pub fn save_session<P: Into<PathBuf>>(&self, path: P) {
+        self.modify(|state| state.session_requested = Some(SessionRequest::Save(path.into())));
+    }
+
+    /// Ask the simulation thread to replace the running simulation with the session
+    /// stored at `path`.
+    ///
+    /// The simulation is recreated with `MultiAgentSimulation::restore_session`
+    /// and continues from the tick of the session, with its seed and GUI data.
+    #[inline]
+    pub fn open_session<P: Into<PathBuf>>(&self, path: P) {
+        self.modify(|state| state.session_requested = Some(SessionRequest::Open(path.into())));
+    }
+
+    /// Consume a session request, if any.
+    ///
+    /// Used by the simulation thread before saving or opening the session.
+    #[inline]
+    #[must_use]
+    pub fn take_session_request(&self) -> Option<SessionRequest> {
+        self.lock().session_requested.take()
+    }
+
+    /// Report the outcome of the last session file operation.
+    ///
+    /// Used by the simulation thread when it can save and open sessions.
+    #[inline]
+    pub fn set_session_status(&self, status: SessionStatus) {
+        self.modify(|state| state.session_status = Some(status));
+    }
+
+    /// Outcome of the last session file operation, or `None` if the runtime
+    /// cannot save and open sessions.
+    #[inline]
+    #[must_use]
+    pub fn session_status(&self) -> Option<SessionStatus> {
+        self.lock().session_status.clone()
+    }
+
+    /// Whether a restart, a rewind, a script change, a session operation or the
+    /// stop was requested and not handled yet.
+    ///
+    /// Used by the simulation thread to cut short its waits.
+    #[inline]
+    #[must_use]
+    pub fn has_pending_request(&self) -> bool {
+        let state = self.lock();
+        state.restart_requested
+            || state.rewind_requested.is_some()
+            || state.script_requested.is_some()
+            || state.session_requested.is_some()
+            || state.stopped
+    }
+
+    /// Whether the simulation thread should not call `update()` on its own.
+    ///
+    /// A simulation is halted while it is paused or after it failed.
+    #[inline]
+    #[must_use]
+    pub fn is_halted(&self) -> bool {
+        self.lock().paused_or_failed()
+    }
+
+    /// Consume one pending step, if any and if no failure is reported.
+    ///
+    /// Used by the simulation thread while paused.
+    #[inline]
+    #[must_use]
+    pub fn take_step(&self) -> bool {
+        let mut state = self.lock();
+        if state.pending_steps == 0 || state.failure.is_some() {
+            return false;
+        }
+        state.pending_steps -= 1;
+        true
+    }
+
+    /// Run `callback` on the calling thread after every following request or report
+    /// made through this control or any of its clones.
+    ///
+    /// Meant for cheap notifications, such as waking up a GUI to redraw the
+    /// toolbar. The callback must not register other callbacks on this control, and
+    /// it lives as long as the control.
+    #[inline]
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.inner.listeners.add(callback);
+    }
+
+    /// Block the calling thread while the simulation is halted with nothing to do.
+    ///
+    /// Returns as soon as the simulation is resumed, stepped, restarted, rewound or stopped,
+    /// or a script change or a session operation is requested.
+    /// Steps are ignored while a failure is reported.
+    #[inline]
+    pub fn wait_while_halted(&self) {
+        let state = self.lock();
+        let _state = self
+            .inner
+            .changed
+            .wait_while(state, |state| {
+                let idle: bool = state.failure.is_some() || state.pending_steps == 0;
+                let requested: bool = state.restart_requested
+                    || state.rewind_requested.is_some()
+                    || state.script_requested.is_some()
+                    || state.session_requested.is_some();
+                state.paused_or_failed() && idle && !requested && !state.stopped
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, ControlState> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(&mut ControlState)>(&self, f: F) {
+        f(&mut self.lock());
+        self.inner.changed.notify_all();
+        self.inner.listeners.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Recovery, RecoveryAction, ScriptRequest, ScriptStatus, SessionRequest, SessionStatus,
+        SimulationControl, SimulationFailure,
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_control_defaults() {
+        let control = SimulationControl::new();
+
+        assert!(!control.is_paused());
+        assert!(!control.is_stopped());
+        assert!(!control.cancellation_token().is_cancelled());
+        assert_eq!(control.pending_steps(), 0);
+        assert!((control.time_scale() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_control_step() {
+        let control = SimulationControl::new();
+
+        control.step(1);
+        control.step(1);
+        assert!(control.is_paused());
+        assert!(control.take_step());
+        assert!(control.take_step());
+        assert!(!control.take_step());
+
+        control.step(5);
+        control.resume();
+        assert_eq!(control.pending_steps(), 0);
+    }
+
+    #[test]
+    fn test_control_time_scale_is_clamped() {
+        let control = SimulationControl::new();
+
+        control.set_time_scale(-3.0);
+        assert!(control.time_scale().abs() < f64::EPSILON);
+
+        control.set_time_scale(f64::NAN);
+        assert!(control.time_scale().abs() < f64::EPSILON);
+
+        control.set_time_scale(f64::MAX);
+        assert!((control.time_scale() - SimulationControl::MAX_TIME_SCALE).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_control_failure_and_restart() {
+        let control = SimulationControl::new();
+
+        control.fail(SimulationFailure {
+            tick: 42,
+            message: String::from("boom"),
+            location: None,
+        });
+        assert!(control.is_halted());
+        assert_eq!(control.failure().unwrap().tick, 42);
+        assert!(!control.take_restart());
+
+        control.restart();
+        assert!(control.take_restart());
+        assert!(!control.take_restart());
+        assert!(!control.is_halted());
+        assert_eq!(control.failure(), None);
+    }
+
+    #[test]
+    fn test_control_seed() {
+        let control = SimulationControl::new();
+
+        control.set_seed(42);
+
+        assert_eq!(control.seed(), 42);
+        assert_eq!(control.seeds().master(), 42);
+    }
+
+    #[test]
+    fn test_control_rewind() {
+        let control = SimulationControl::new();
+
+        control.rewind_to(3);
+        control.rewind_to(5);
+
+        assert_eq!(control.take_rewind(), Some(5));
+        assert_eq!(control.take_rewind(), None);
+    }
+
+    #[test]
+    fn test_control_script() {
+        let control = SimulationControl::new();
+        assert_eq!(control.script_status(), None);
+
+        control.load_script("scenario.rhai");
+        assert!(control.has_pending_request());
+        assert_eq!(
+            control.take_script_request(),
+            Some(ScriptRequest::Load("scenario.rhai".into()))
+        );
+        assert!(!control.has_pending_request());
+
+        control.unload_script();
+        assert_eq!(control.take_script_request(), Some(ScriptRequest::Unload));
+
+        control.set_script_status(ScriptStatus::Idle);
+        assert_eq!(control.script_status(), Some(ScriptStatus::Idle));
+    }
+
+    #[test]
+    fn test_control_session() {
+        let control = SimulationControl::new();
+        assert_eq!(control.session_status(), None);
+
+        control.pause();
+        control.save_session("spirals.json");
+        control.wait_while_halted();
+        assert_eq!(
+            control.take_session_request(),
+            Some(SessionRequest::Save("spirals.json".into()))
+        );
+        assert!(!control.has_pending_request());
+
+        control.open_session("spirals.json");
+        assert_eq!(
+            control.take_session_request(),
+            Some(SessionRequest::Open("spirals.json".into()))
+        );
+
+        control.set_session_status(SessionStatus::Idle);
+        assert_eq!(control.session_status(), Some(SessionStatus::Idle));
+    }
+
+    #[test]
+    fn test_control_recoveries_do_not_halt() {
+        let control = SimulationControl::new();
+        let failure = SimulationFailure {
+            tick: 7,
+            message: String::from("boom"),
+            location: Some(String::from("src/lib.rs:1:1")),
+        };
+
+        control.recovered(Recovery {
+            failure: failure.clone(),
+            action: RecoveryAction::Restarted,
+        });
+        control.recovered(Recovery {
+            failure,
+            action: RecoveryAction::RolledBack,
+        });
+
+        assert!(!control.is_halted());
+        assert_eq!(control.recovery_count(), 2);
+        assert_eq!(
+            control.last_recovery().unwrap().action,
+            RecoveryAction::RolledBack
+        );
+    }
+
+    #[test]
+    fn test_wait_while_halted_wakes_up() {
+        let control = SimulationControl::new();
+        control.pause();
+
+        let waiter = {
+            let control = control.clone();
+            thread::spawn(move || control.wait_while_halted())
+        };
+
+        thread::sleep(Duration::from_millis(10));
+        assert!(!waiter.is_finished());
+
+        control.stop();
+        waiter.join().unwrap();
+    }
+
+    #[test]
+    fn test_control_stop_cancels_the_tick() {
+        let control = SimulationControl::new();
+        let token = control.cancellation_token();
+
+        control.stop();
+
+        assert!(control.is_stopped());
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_control_on_change() {
+        let control = SimulationControl::new();
+        let changes = Arc::new(AtomicU64::new(0));
+
+        let counter = Arc::clone(&changes);
+        control.on_change(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        let gui = control.clone();
+        gui.pause();
+        let _ = control.is_paused();
+        control.step(1);
+
+        assert_eq!(changes.load(Ordering::Relaxed), 2);
+    }
+}
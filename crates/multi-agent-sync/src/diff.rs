@@ -0,0 +1,570 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{Publisher, Shared};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Data able to describe how it changed since a previous value.
+///
+/// Implement it for a `SimulationData` that changes little from one tick to the
+/// next, such as the live cells of a game of life, and publish it with a
+/// [`DiffBuffer`]: the buffers read by the GUI, and the copies kept by remote GUIs
+/// and the timeline, are then brought up to date by applying the changes, instead
+/// of being cloned in full on every tick.
+///
+/// Applying `new.diff(&old)` to a copy of `old` must produce a copy of `new`.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::Diffable;
+/// use std::collections::BTreeSet;
+///
+/// #[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// struct Cells(BTreeSet<(i32, i32)>);
+///
+/// /// Cells born and cells dead since the previous value.
+/// struct CellChanges {
+///     born: Vec<(i32, i32)>,
+///     dead: Vec<(i32, i32)>,
+/// }
+///
+/// impl Diffable for Cells {
+///     type Delta = CellChanges;
+///
+///     fn diff(&self, previous: &Self) -> Self::Delta {
+///         CellChanges {
+///             born: self.0.difference(&previous.0).copied().collect(),
+///             dead: previous.0.difference(&self.0).copied().collect(),
+///         }
+///     }
+///
+///     fn apply(&mut self, delta: &Self::Delta) {
+///         self.0.extend(&delta.born);
+///         for cell in &delta.dead {
+///             self.0.remove(cell);
+///         }
+///     }
+/// }
+///
+/// let old = Cells(BTreeSet::from([(0, 0), (0, 1)]));
+/// let new = Cells(BTreeSet::from([(0, 1), (1, 1)]));
+///
+/// let mut copy = old.clone();
+/// copy.apply(&new.diff(&old));
+/// assert_eq!(copy, new);
+/// ```
+pub trait Diffable: Clone {
+    /// Changes turning a previous value into a newer one.
+    type Delta: Send + Sync + 'static;
+
+    /// Changes turning `previous` into `self`.
+    fn diff(&self, previous: &Self) -> Self::Delta;
+
+    /// Apply changes computed by [`diff`](Self::diff) to `self`.
+    fn apply(&mut self, delta: &Self::Delta);
+}
+
+/// Changes bringing a copy of the previously published value up to date.
+///
+/// Lets readers hold the changes of any [`Diffable`] type without naming it.
+pub trait Patch<T>: Send + Sync {
+    /// Apply the changes to `value`.
+    fn apply_to(&self, value: &mut T);
+
+    /// The [`Diffable::Delta`] itself, for readers that know its type, such as a
+    /// remote link serializing it.
+    fn delta(&self) -> &dyn Any;
+}
+
+/// [`Patch`] made of a [`Diffable::Delta`].
+struct DeltaPatch<T: Diffable>(T::Delta);
+
+impl<T: Diffable> Patch<T> for DeltaPatch<T> {
+    #[inline]
+    fn apply_to(&self, value: &mut T) {
+        value.apply(&self.0);
+    }
+
+    #[inline]
+    fn delta(&self) -> &dyn Any {
+        &self.0
+    }
+}
+
+/// Publication recorded in a [`DeltaFeed`].
+pub enum Change<T> {
+    /// The whole value.
+    Keyframe(Arc<T>),
+    /// Changes since the previous publication.
+    Delta(Arc<dyn Patch<T>>),
+}
+
+impl<T: Clone> Change<T> {
+    /// Bring `value`, a copy of the previous publication, up to date.
+    #[inline]
+    pub fn apply_to(&self, value: &mut T) {
+        match self {
+            Self::Keyframe(data) => value.clone_from(data),
+            Self::Delta(patch) => patch.apply_to(value),
+        }
+    }
+}
+
+impl<T> Clone for Change<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Self::Keyframe(data) => Self::Keyframe(Arc::clone(data)),
+            Self::Delta(patch) => Self::Delta(Arc::clone(patch)),
+        }
+    }
+}
+
+impl<T> Debug for Change<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyframe(_) => f.write_str("Keyframe"),
+            Self::Delta(_) => f.write_str("Delta"),
+        }
+    }
+}
+
+/// Publications of a [`DiffBuffer`], in order.
+///
+/// For readers keeping their own copy of the value, such as a remote GUI or the
+/// [`Timeline`](crate::Timeline), and bringing it up to date with the changes
+/// instead of copying every value.
+///
+/// Every publication gets the next version, starting from 1. The feed keeps the
+/// latest keyframe and the deltas published since, so a reader that fell behind
+/// starts over from the keyframe. Every clone refers to the same feed.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::{DiffBuffer, Diffable, Shared};
+///
+/// #[derive(Debug, Clone, Default, PartialEq)]
+/// struct Counter(u64);
+///
+/// impl Diffable for Counter {
+///     type Delta = u64;
+///
+///     fn diff(&self, previous: &Self) -> u64 {
+///         self.0 - previous.0
+///     }
+///
+///     fn apply(&mut self, delta: &u64) {
+///         self.0 += delta;
+///     }
+/// }
+///
+/// let mut buffer = DiffBuffer::new(Shared::new(Counter(0)));
+/// let feed = buffer.feed();
+///
+/// let mut copy = Counter::default();
+/// let mut version = 0;
+/// for count in 1..=5 {
+///     buffer.publish(&Counter(count));
+///     for (published, change) in feed.since(version) {
+///         change.apply_to(&mut copy);
+///         version = published;
+///     }
+/// }
+/// assert_eq!(copy, Counter(5));
+/// ```
+pub struct DeltaFeed<T> {
+    changes: Arc<Mutex<Changes<T>>>,
+}
+
+/// Publications kept by a [`DeltaFeed`], with their version.
+type Changes<T> = VecDeque<(u64, Change<T>)>;
+
+impl<T> DeltaFeed<T> {
+    fn new() -> Self {
+        Self {
+            changes: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Version of the latest publication, 0 before the first one.
+    #[inline]
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.lock().back().map_or(0, |(version, _)| *version)
+    }
+
+    /// Latest publication and its version, if any.
+    #[inline]
+    #[must_use]
+    pub fn latest(&self) -> Option<(u64, Change<T>)> {
+        self.lock().back().cloned()
+    }
+
+    /// Publications made after `version`, oldest first.
+    ///
+    /// Starts from the latest keyframe instead if some of them are no longer kept.
+    #[inline]
+    #[must_use]
+    pub fn since(&self, version: u64) -> Vec<(u64, Change<T>)> {
+        let changes = self.lock();
+        let first: u64 = changes.front().map_or(0, |(first, _)| *first);
+        let skipped: usize = if first <= version.saturating_add(1) {
+            changes.partition_point(|(published, _)| *published <= version)
+        } else {
+            0
+        };
+        changes.iter().skip(skipped).cloned().collect()
+    }
+
+    fn push(&self, version: u64, change: Change<T>) {
+        let mut changes = self.lock();
+        if matches!(change, Change::Keyframe(_)) {
+            changes.clear();
+        }
+        changes.push_back((version, change));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Changes<T>> {
+        self.changes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Clone for DeltaFeed<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            changes: Arc::clone(&self.changes),
+        }
+    }
+}
+
+impl<T> Debug for DeltaFeed<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let changes = self.lock();
+        f.debug_struct("DeltaFeed")
+            .field(
+                "version",
+                &changes.back().map_or(0, |(version, _)| *version),
+            )
+            .field("changes", &changes.len())
+            .finish()
+    }
+}
+
+/// Writer publishing values to a [`Shared`] by applying their changes to recycled
+/// buffers.
+///
+/// Works like a [`TripleBuffer`](crate::TripleBuffer), except that a recycled
+/// buffer is brought up to date with the deltas published since it was last the
+/// front buffer, rather than copied over. A full copy, the keyframe, is still made:
+/// - every `keyframe_interval` publications, so that a lossy `diff` cannot drift
+///   for long,
+/// - when a buffer is too old for the deltas kept,
+/// - after the [`Shared`] was written by someone else.
+///
+/// Readers of the [`Shared`] are unchanged: they keep calling [`Shared::load`] and
+/// get the buffers rebuilt here. Readers keeping their own copy of the value
+/// subscribe to the [`feed`](Self::feed) instead, which holds the deltas and the
+/// keyframes, and rebuild it on their side.
+pub struct DiffBuffer<T: Diffable> {
+    shared: Shared<T>,
+    back: Vec<(u64, Arc<T>)>,
+    deltas: VecDeque<(u64, Arc<DeltaPatch<T>>)>,
+    feed: DeltaFeed<T>,
+    version: u64,
+    generation: u64,
+    keyframe_interval: u64,
+}
+
+impl<T: Diffable + 'static> DiffBuffer<T> {
+    /// Number of buffers kept besides the front one.
+    const BACK_BUFFERS: usize = 2;
+
+    /// Publications between two keyframes by default.
+    pub const DEFAULT_KEYFRAME_INTERVAL: u64 = 300;
+
+    /// Create a diff buffer publishing to `shared`.
+    #[inline]
+    #[must_use]
+    pub fn new(shared: Shared<T>) -> Self {
+        Self {
+            generation: shared.generation(),
+            shared,
+            back: Vec::with_capacity(Self::BACK_BUFFERS + 1),
+            deltas: VecDeque::with_capacity(Self::BACK_BUFFERS + 1),
+            feed: DeltaFeed::new(),
+            version: 0,
+            keyframe_interval: Self::DEFAULT_KEYFRAME_INTERVAL,
+        }
+    }
+
+    /// Copy the value in full every `keyframe_interval` publications.
+    ///
+    /// Default: [`DEFAULT_KEYFRAME_INTERVAL`](Self::DEFAULT_KEYFRAME_INTERVAL)
+    #[inline]
+    #[must_use]
+    pub fn with_keyframe_interval(mut self, keyframe_interval: u64) -> Self {
+        self.keyframe_interval = keyframe_interval.max(1);
+        self
+    }
+
+    /// Shared container the values are published to.
+    #[inline]
+    #[must_use]
+    pub const fn shared(&self) -> &Shared<T> {
+        &self.shared
+    }
+
+    /// Feed of the deltas and keyframes published from now on.
+    #[inline]
+    #[must_use]
+    pub fn feed(&self) -> DeltaFeed<T> {
+        self.feed.clone()
+    }
+
+    /// Diff `value` against the front buffer, apply the changes to a free back
+    /// buffer, or copy `value` into a new one if none is free, and make it the
+    /// front buffer.
+    ///
+    /// The changes are recorded in the [`feed`](Self::feed) before the front
+    /// buffer is replaced, so a reader woken up by the [`Shared`] finds them there.
+    pub fn publish(&mut self, value: &T) {
+        let overwritten: bool = self.shared.generation() != self.generation;
+        if overwritten {
+            self.deltas.clear();
+        }
+
+        self.version += 1;
+        let delta: Arc<DeltaPatch<T>> = Arc::new(DeltaPatch(value.diff(&self.shared.load())));
+        self.deltas.push_back((self.version, Arc::clone(&delta)));
+        let keyframe: bool = self.version.is_multiple_of(self.keyframe_interval);
+
+        let free: Option<usize> = self
+            .back
+            .iter_mut()
+            .position(|(_, buffer)| Arc::get_mut(buffer).is_some());
+
+        let front: Arc<T> = free.map_or_else(
+            || Arc::new(value.clone()),
+            |index| {
+                let (version, mut buffer): (u64, Arc<T>) = self.back.remove(index);
+                if let Some(data) = Arc::get_mut(&mut buffer) {
+                    let first: u64 = self.deltas.front().map_or(u64::MAX, |(first, _)| *first);
+                    if keyframe || first > version + 1 {
+                        data.clone_from(value);
+                    } else {
+                        self.deltas
+                            .iter()
+                            .filter(|(delta_version, _)| *delta_version > version)
+                            .for_each(|(_, delta)| data.apply(&delta.0));
+                    }
+                }
+                buffer
+            },
+        );
+
+        let change: Change<T> = if keyframe || overwritten || self.version == 1 {
+            Change::Keyframe(Arc::clone(&front))
+        } else {
+            Change::Delta(delta)
+        };
+        self.feed.push(self.version, change);
+
+        let previous: Arc<T> = self.shared.swap(front);
+        self.generation = self.shared.generation();
+        self.back.push((self.version - 1, previous));
+        if self.back.len() > Self::BACK_BUFFERS {
+            self.back.remove(0);
+        }
+
+        let oldest: u64 = self
+            .back
+            .iter()
+            .map(|(version, _)| *version)
+            .min()
+            .unwrap_or(0);
+        while self
+            .deltas
+            .front()
+            .is_some_and(|(delta_version, _)| *delta_version <= oldest)
+        {
+            self.deltas.pop_front();
+        }
+    }
+}
+
+impl<T> Publisher<T> for DiffBuffer<T>
+where
+    T: Diffable + Send + Sync + 'static,
+{
+    #[inline]
+    fn publish(&mut self, value: &T) {
+        Self::publish(self, value);
+    }
+
+    #[inline]
+    fn feed(&self) -> Option<DeltaFeed<T>> {
+        Some(self.feed.clone())
+    }
+}
+
+impl<T: Diffable> Debug for DiffBuffer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiffBuffer")
+            .field("version", &self.version)
+            .field("back_buffers", &self.back.len())
+            .field("deltas", &self.deltas.len())
+            .field("keyframe_interval", &self.keyframe_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, DiffBuffer, Diffable};
+    use crate::Shared;
+    use std::{collections::BTreeSet, sync::Arc};
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    struct Cells {
+        alive: BTreeSet<u32>,
+        label: u32,
+    }
+
+    impl Cells {
+        fn new(alive: impl IntoIterator<Item = u32>, label: u32) -> Self {
+            Self {
+                alive: alive.into_iter().collect(),
+                label,
+            }
+        }
+    }
+
+    /// Leaves the label out, to tell the keyframes from the deltas.
+    impl Diffable for Cells {
+        type Delta = (Vec<u32>, Vec<u32>);
+
+        fn diff(&self, previous: &Self) -> Self::Delta {
+            (
+                self.alive.difference(&previous.alive).copied().collect(),
+                previous.alive.difference(&self.alive).copied().collect(),
+            )
+        }
+
+        fn apply(&mut self, (born, dead): &Self::Delta) {
+            self.alive.extend(born);
+            for cell in dead {
+                self.alive.remove(cell);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deltas_rebuild_the_value() {
+        let shared: Shared<Cells> = Shared::new(Cells::default());
+        let mut buffer = DiffBuffer::new(shared.clone());
+
+        for tick in 0..20 {
+            let value = Cells::new(tick..tick + 5, 0);
+            let held: Option<Arc<Cells>> = (tick % 3 == 0).then(|| shared.load_full());
+            buffer.publish(&value);
+
+            assert_eq!(**shared.load(), value);
+            drop(held);
+        }
+    }
+
+    #[test]
+    fn test_keyframes_copy_the_value() {
+        let shared: Shared<Cells> = Shared::new(Cells::default());
+        let mut buffer = DiffBuffer::new(shared.clone()).with_keyframe_interval(4);
+
+        for label in 1..=4 {
+            buffer.publish(&Cells::new([1, 2], label));
+        }
+        assert_eq!(shared.load().label, 4);
+
+        for label in 5..=7 {
+            buffer.publish(&Cells::new([label], label));
+            assert_eq!(shared.load().alive, BTreeSet::from([label]));
+            assert_ne!(shared.load().label, label);
+        }
+        buffer.publish(&Cells::new([8], 8));
+        assert_eq!(**shared.load(), Cells::new([8], 8));
+    }
+
+    #[test]
+    fn test_external_writes_are_not_diffed() {
+        let shared: Shared<Cells> = Shared::new(Cells::default());
+        let mut buffer = DiffBuffer::new(shared.clone());
+
+        for tick in 0..5 {
+            buffer.publish(&Cells::new([tick], 0));
+        }
+        shared.store(Cells::new([100, 101], 0));
+        for tick in 5..10 {
+            buffer.publish(&Cells::new([tick, 100], 0));
+            assert_eq!(**shared.load(), Cells::new([tick, 100], 0));
+        }
+    }
+
+    #[test]
+    fn test_feed_brings_copies_up_to_date() {
+        let shared: Shared<Cells> = Shared::new(Cells::default());
+        let mut buffer = DiffBuffer::new(shared.clone()).with_keyframe_interval(4);
+        let feed = buffer.feed();
+
+        let mut copy = Cells::default();
+        let mut version: u64 = 0;
+        for tick in 0..10 {
+            buffer.publish(&Cells::new(tick..tick + 3, 0));
+            for (published, change) in feed.since(version) {
+                change.apply_to(&mut copy);
+                version = published;
+            }
+            assert_eq!(copy, **shared.load());
+        }
+        assert_eq!(feed.version(), 10);
+    }
+
+    #[test]
+    fn test_feed_restarts_from_keyframes() {
+        let shared: Shared<Cells> = Shared::new(Cells::default());
+        let mut buffer = DiffBuffer::new(shared.clone()).with_keyframe_interval(4);
+        let feed = buffer.feed();
+
+        for tick in 0..6 {
+            buffer.publish(&Cells::new([tick], 0));
+        }
+        let versions: Vec<u64> = feed.since(1).iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, [4, 5, 6]);
+        assert!(matches!(feed.since(1)[0].1, Change::Keyframe(_)));
+        assert_eq!(feed.since(5).len(), 1);
+
+        shared.store(Cells::new([100], 0));
+        buffer.publish(&Cells::new([100, 7], 0));
+        assert!(matches!(feed.latest(), Some((7, Change::Keyframe(_)))));
+        assert_eq!(feed.since(6).len(), 1);
+    }
+}
@@ -14,8 +14,26 @@
  * limitations under the License.
  */
 
+mod control;
+mod diff;
+mod listeners;
+mod publisher;
 mod shared;
+mod signal;
+mod stats;
+mod timeline;
+mod triple_buffer;
 
 pub mod message;
 
+pub use control::{
+    Recovery, RecoveryAction, ScriptRequest, ScriptStatus, SessionRequest, SessionStatus,
+    SimulationControl, SimulationFailure,
+};
+pub use diff::{Change, DeltaFeed, DiffBuffer, Diffable, Patch};
+pub use publisher::Publisher;
 pub use shared::Shared;
+pub use signal::Signal;
+pub use stats::{ChannelFill, RuntimeStats};
+pub use timeline::Timeline;
+pub use triple_buffer::TripleBuffer;
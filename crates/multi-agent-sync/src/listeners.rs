@@ -0,0 +1,65 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use arc_swap::ArcSwap;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Callback run after every change of a shared value.
+type Listener = Arc<dyn Fn() + Send + Sync>;
+
+/// Callbacks registered to be told about changes.
+///
+/// Notifying is lock-free, so it costs next to nothing when no callback is registered.
+#[derive(Default)]
+pub struct Listeners {
+    listeners: ArcSwap<Vec<Listener>>,
+}
+
+impl Listeners {
+    /// Run `callback` after every following change.
+    #[inline]
+    pub fn add<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let callback: Listener = Arc::new(callback);
+        self.listeners.rcu(|listeners| {
+            let mut listeners: Vec<Listener> = Vec::clone(listeners);
+            listeners.push(Arc::clone(&callback));
+            listeners
+        });
+    }
+
+    /// Run every registered callback on the calling thread.
+    #[inline]
+    pub fn notify(&self) {
+        for listener in self.listeners.load().iter() {
+            listener();
+        }
+    }
+}
+
+impl Debug for Listeners {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listeners")
+            .field("count", &self.listeners.load().len())
+            .finish()
+    }
+}
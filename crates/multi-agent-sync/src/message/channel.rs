@@ -14,8 +14,8 @@
  * limitations under the License.
  */
 
-use super::{MessageReceiver, MessageSender};
-use crossbeam_channel::{self as channel, Receiver, Sender};
+use super::{queue::Queue, ChannelPolicy, MessageReceiver, MessageSender};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct MessageChannel<T> {
@@ -24,13 +24,26 @@ pub struct MessageChannel<T> {
 }
 
 impl<T> MessageChannel<T> {
+    /// Create a channel holding up to `capacity` messages, refusing new ones when full.
+    ///
+    /// A channel never hands a message straight to a waiting receiver, so a
+    /// `capacity` of zero refuses every message.
     #[inline]
     pub fn new(capacity: usize) -> Self {
-        let (sender, receiver): (Sender<T>, Receiver<T>) = channel::bounded(capacity);
+        Self::with_policy(capacity, ChannelPolicy::DropNewest)
+    }
+
+    /// Create a channel holding up to `capacity` messages, applying `policy` when full.
+    ///
+    /// With a `capacity` of zero the channel is always full, so every message is
+    /// refused unless `policy` is [`ChannelPolicy::Unbounded`].
+    #[inline]
+    pub fn with_policy(capacity: usize, policy: ChannelPolicy<T>) -> Self {
+        let queue = Arc::new(Queue::new(capacity, policy));
 
         Self {
-            sender: MessageSender::new(sender),
-            receiver: MessageReceiver::new(receiver),
+            sender: MessageSender::new(Arc::clone(&queue)),
+            receiver: MessageReceiver::new(queue),
         }
     }
 
@@ -43,6 +56,16 @@ impl<T> MessageChannel<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::message::Coalesce;
+    use multi_agent_core::Error;
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
 
     #[test]
     fn test_channel_send_receive() {
@@ -146,6 +169,22 @@ mod tests {
         assert_eq!(receiver.try_recv(), None);
     }
 
+    #[test]
+    fn test_channel_recv_timeout() {
+        let (sender, receiver) = MessageChannel::new(10).split();
+
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(5)), None);
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            sender.send(42).unwrap();
+        });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Some(42));
+
+        handle.join().unwrap();
+        assert_eq!(receiver.recv(), None);
+    }
+
     #[test]
     fn test_channel_drain_limit() {
         let (sender, receiver) = MessageChannel::new(10).split();
@@ -161,4 +200,221 @@ mod tests {
         let remaining = receiver.drain();
         assert_eq!(remaining, vec![3, 4]);
     }
+
+    #[test]
+    fn test_channel_drop_newest() {
+        let (sender, receiver) = MessageChannel::new(2).split();
+
+        sender.send_lossy(1);
+        sender.send_lossy(2);
+        sender.send_lossy(3);
+
+        assert_eq!(receiver.drain(), vec![1, 2]);
+        assert_eq!(sender.dropped(), 1);
+        assert_eq!(receiver.dropped(), 1);
+    }
+
+    #[test]
+    fn test_channel_drop_oldest() {
+        let (sender, receiver) = MessageChannel::with_policy(2, ChannelPolicy::DropOldest).split();
+
+        for i in 1..=5 {
+            sender.send(i).unwrap();
+        }
+
+        assert_eq!(receiver.drain(), vec![4, 5]);
+        assert_eq!(sender.dropped(), 3);
+    }
+
+    #[test]
+    fn test_channel_zero_capacity() {
+        let policies = [
+            ChannelPolicy::DropNewest,
+            ChannelPolicy::DropOldest,
+            ChannelPolicy::Block(Duration::from_millis(1)),
+            ChannelPolicy::coalesce_by_key(),
+        ];
+        for policy in policies {
+            let (sender, receiver) = MessageChannel::with_policy(0, policy).split();
+
+            assert!(sender.is_full());
+            assert!(matches!(
+                sender.send(('a', 1)),
+                Err(Error::MessageChannelFull { capacity: 0 })
+            ));
+            assert!(receiver.drain().is_empty());
+            assert_eq!(receiver.dropped(), 1);
+        }
+
+        let (sender, receiver) = MessageChannel::with_policy(0, ChannelPolicy::Unbounded).split();
+        sender.send(('a', 1)).unwrap();
+        assert_eq!(receiver.drain(), vec![('a', 1)]);
+    }
+
+    #[test]
+    fn test_channel_block() {
+        let policy = ChannelPolicy::Block(Duration::from_secs(5));
+        let (sender, receiver) = MessageChannel::with_policy(1, policy).split();
+        sender.send(1).unwrap();
+
+        let handle = {
+            let receiver = receiver.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                receiver.drain()
+            })
+        };
+        sender.send(2).unwrap();
+
+        assert_eq!(handle.join().unwrap(), vec![1]);
+        assert_eq!(receiver.drain(), vec![2]);
+        assert_eq!(receiver.dropped(), 0);
+    }
+
+    #[test]
+    fn test_channel_block_many_senders() {
+        let policy = ChannelPolicy::Block(Duration::from_secs(5));
+        let (sender, receiver) = MessageChannel::with_policy(2, policy).split();
+
+        let handles: Vec<_> = (0..4)
+            .map(|id| {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for i in 0..25 {
+                        sender.send((id, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(sender);
+
+        let mut messages: Vec<(u32, u32)> = Vec::new();
+        while let Some(message) = receiver.recv() {
+            messages.push(message);
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(messages.len(), 100);
+        assert_eq!(receiver.dropped(), 0);
+        for id in 0..4 {
+            let order: Vec<u32> = messages
+                .iter()
+                .filter(|(from, _)| *from == id)
+                .map(|(_, i)| *i)
+                .collect();
+            assert_eq!(order, (0..25).collect::<Vec<u32>>());
+        }
+    }
+
+    #[test]
+    fn test_channel_disconnect_while_waiting() {
+        let policy = ChannelPolicy::Block(Duration::from_secs(5));
+        let (sender, receiver) = MessageChannel::with_policy(1, policy).split();
+        sender.send(1).unwrap();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            drop(receiver);
+        });
+        assert!(matches!(
+            sender.send(2),
+            Err(Error::MessageChannelDisconnected)
+        ));
+        handle.join().unwrap();
+
+        let (sender, receiver) = MessageChannel::<u32>::new(1).split();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            drop(sender);
+        });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), None);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_channel_block_timeout() {
+        let policy = ChannelPolicy::Block(Duration::from_millis(5));
+        let (sender, receiver) = MessageChannel::with_policy(1, policy).split();
+
+        sender.send(1).unwrap();
+
+        assert!(sender.send(2).is_err());
+        assert_eq!(receiver.drain(), vec![1]);
+        assert_eq!(receiver.dropped(), 1);
+    }
+
+    #[test]
+    fn test_channel_unbounded() {
+        let (sender, receiver) = MessageChannel::with_policy(1, ChannelPolicy::Unbounded).split();
+
+        for i in 0..100 {
+            sender.send(i).unwrap();
+        }
+
+        assert_eq!(sender.capacity(), None);
+        assert!(!sender.is_full());
+        assert_eq!(receiver.drain().len(), 100);
+        assert_eq!(receiver.dropped(), 0);
+    }
+
+    impl Coalesce for (char, u32) {
+        type Key = char;
+
+        fn coalescing_key(&self) -> Option<Self::Key> {
+            (self.0 != 'x').then_some(self.0)
+        }
+    }
+
+    #[test]
+    fn test_channel_coalesce_by_key() {
+        let policy = ChannelPolicy::coalesce_by_key();
+        let (sender, receiver) = MessageChannel::with_policy(3, policy).split();
+
+        for message in [('a', 1), ('x', 1), ('a', 2), ('b', 1), ('x', 2), ('b', 2)] {
+            sender.send_lossy(message);
+        }
+
+        assert_eq!(receiver.drain(), vec![('a', 2), ('x', 1), ('b', 2)]);
+        assert_eq!(receiver.dropped(), 1);
+    }
+
+    #[test]
+    fn test_channel_coalesce_by_key_keeps_order() {
+        let policy = ChannelPolicy::coalesce_by_key();
+        let (sender, receiver) = MessageChannel::with_policy(10, policy).split();
+
+        for message in [('a', 1), ('b', 1), ('c', 1), ('a', 2), ('b', 2)] {
+            sender.send(message).unwrap();
+        }
+
+        assert_eq!(receiver.drain(), vec![('a', 2), ('b', 2), ('c', 1)]);
+    }
+
+    #[test]
+    fn test_channel_on_message() {
+        let (sender, receiver) = MessageChannel::<u32>::new(1).split();
+        let queued = Arc::new(AtomicU64::new(0));
+
+        let counter = Arc::clone(&queued);
+        receiver.on_message(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        sender.send_lossy(1);
+        sender.send_lossy(2);
+
+        assert_eq!(queued.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_channel_disconnected() {
+        let (sender, receiver) = MessageChannel::<u32>::new(10).split();
+        let other = receiver.clone();
+
+        drop(receiver);
+        assert!(sender.send(1).is_ok());
+        drop(other);
+        assert!(sender.send(2).is_err());
+    }
 }
@@ -0,0 +1,249 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::MessageReceiver;
+use std::{
+    collections::HashSet,
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+};
+
+/// Message that may be superseded by a later message with the same key.
+///
+/// A slider dragged in the GUI sends a new value on every frame, but only the
+/// last one matters to the simulation. Giving those messages a key lets a
+/// [`CoalescingReceiver`] deliver only the latest message per key, while messages
+/// without a key are all delivered.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::message::{Coalesce, MessageChannel};
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum GuiMessage {
+///     SetAgentCount(usize),
+///     SetSpeed(u32),
+///     SpawnAt(i32, i32),
+/// }
+///
+/// impl Coalesce for GuiMessage {
+///     type Key = u8;
+///
+///     fn coalescing_key(&self) -> Option<Self::Key> {
+///         match self {
+///             Self::SetAgentCount(_) => Some(0),
+///             Self::SetSpeed(_) => Some(1),
+///             Self::SpawnAt(..) => None,
+///         }
+///     }
+/// }
+///
+/// let (sender, receiver) = MessageChannel::new(10).split();
+/// let receiver = receiver.coalescing();
+/// sender.send(GuiMessage::SetAgentCount(10)).unwrap();
+/// sender.send(GuiMessage::SpawnAt(1, 2)).unwrap();
+/// sender.send(GuiMessage::SetAgentCount(20)).unwrap();
+/// sender.send(GuiMessage::SpawnAt(3, 4)).unwrap();
+///
+/// assert_eq!(
+///     receiver.drain(),
+///     vec![
+///         GuiMessage::SpawnAt(1, 2),
+///         GuiMessage::SetAgentCount(20),
+///         GuiMessage::SpawnAt(3, 4),
+///     ]
+/// );
+/// ```
+pub trait Coalesce {
+    /// Identifies the messages superseding each other.
+    type Key: Eq + Hash;
+
+    /// Key of the message, or `None` if it must always be delivered.
+    fn coalescing_key(&self) -> Option<Self::Key>;
+}
+
+/// Keep only the latest of the messages superseding each other, such as [`coalesce`].
+pub type Coalescing<T> = fn(Vec<T>) -> Vec<T>;
+
+/// Keep only the latest message per key, in the order they were sent.
+///
+/// A message with a key takes the place of the latest message with that key,
+/// so it is still delivered after the messages sent before it.
+#[must_use]
+pub fn coalesce<T: Coalesce>(messages: Vec<T>) -> Vec<T> {
+    let mut keys: HashSet<T::Key> = HashSet::new();
+    let mut latest: Vec<T> = messages
+        .into_iter()
+        .rev()
+        .filter(|message| message.coalescing_key().is_none_or(|key| keys.insert(key)))
+        .collect();
+    latest.reverse();
+    latest
+}
+
+/// Whether `message` and `other` have the same key, so one supersedes the other.
+pub(super) fn same_key<T: Coalesce>(message: &T, other: &T) -> bool {
+    message
+        .coalescing_key()
+        .is_some_and(|key| other.coalescing_key().is_some_and(|other| key == other))
+}
+
+/// Receiver delivering only the latest message per key, see [`Coalesce`].
+///
+/// Usually created with [`MessageReceiver::coalescing`].
+pub struct CoalescingReceiver<T> {
+    inner: MessageReceiver<T>,
+    coalesce: Coalescing<T>,
+}
+
+impl<T> CoalescingReceiver<T> {
+    /// Wrap `receiver` to pass the messages it takes through `coalesce`. The
+    /// receiver can still be used through [`inner`](Self::inner).
+    #[inline]
+    #[must_use]
+    pub const fn new(receiver: MessageReceiver<T>, coalesce: Coalescing<T>) -> Self {
+        Self {
+            inner: receiver,
+            coalesce,
+        }
+    }
+
+    /// Take every pending message, keeping only the latest one per key.
+    #[inline]
+    #[must_use]
+    pub fn drain(&self) -> Vec<T> {
+        (self.coalesce)(self.inner.drain())
+    }
+
+    /// Take up to `limit` pending messages, keeping only the latest one per key among them.
+    #[inline]
+    #[must_use]
+    pub fn drain_limit(&self, limit: usize) -> Vec<T> {
+        (self.coalesce)(self.inner.drain_limit(limit))
+    }
+
+    /// Receiver delivering every message.
+    #[inline]
+    #[must_use]
+    pub const fn inner(&self) -> &MessageReceiver<T> {
+        &self.inner
+    }
+
+    /// Unwrap the receiver delivering every message.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> MessageReceiver<T> {
+        self.inner
+    }
+}
+
+impl<T> Clone for CoalescingReceiver<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.coalesce)
+    }
+}
+
+impl<T> Debug for CoalescingReceiver<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalescingReceiver")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Coalesce> MessageReceiver<T> {
+    /// Wrap this receiver to deliver only the latest message per key.
+    #[inline]
+    #[must_use]
+    pub const fn coalescing(self) -> CoalescingReceiver<T> {
+        CoalescingReceiver::new(self, coalesce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{coalesce, same_key, Coalesce};
+    use crate::message::MessageChannel;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Message {
+        Count(u32),
+        Speed(u32),
+        Click(u32),
+    }
+
+    impl Coalesce for Message {
+        type Key = &'static str;
+
+        fn coalescing_key(&self) -> Option<Self::Key> {
+            match self {
+                Self::Count(_) => Some("count"),
+                Self::Speed(_) => Some("speed"),
+                Self::Click(_) => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_coalesce_keeps_the_latest_per_key() {
+        let messages = vec![
+            Message::Count(1),
+            Message::Speed(1),
+            Message::Click(1),
+            Message::Count(2),
+            Message::Click(2),
+            Message::Count(3),
+        ];
+
+        assert_eq!(
+            coalesce(messages),
+            vec![
+                Message::Speed(1),
+                Message::Click(1),
+                Message::Click(2),
+                Message::Count(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_key() {
+        assert!(same_key(&Message::Count(1), &Message::Count(2)));
+        assert!(!same_key(&Message::Count(1), &Message::Speed(1)));
+        assert!(!same_key(&Message::Click(1), &Message::Click(1)));
+    }
+
+    #[test]
+    fn test_coalescing_receiver() {
+        let (sender, receiver) = MessageChannel::new(10).split();
+        let receiver = receiver.coalescing();
+
+        for count in 0..5 {
+            sender.send(Message::Count(count)).unwrap();
+            sender.send(Message::Click(count)).unwrap();
+        }
+
+        assert_eq!(
+            receiver.drain_limit(4),
+            vec![Message::Click(0), Message::Count(1), Message::Click(1)]
+        );
+        assert_eq!(receiver.inner().pending(), 6);
+        assert_eq!(receiver.drain().len(), 4);
+        assert!(receiver.into_inner().is_empty());
+    }
+}
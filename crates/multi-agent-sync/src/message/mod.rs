@@ -15,9 +15,14 @@
  */
 
 mod channel;
+mod coalesce;
+mod policy;
+mod queue;
 mod receiver;
 mod sender;
 
 pub use channel::MessageChannel;
+pub use coalesce::{coalesce, Coalesce, Coalescing, CoalescingReceiver};
+pub use policy::ChannelPolicy;
 pub use receiver::MessageReceiver;
 pub use sender::MessageSender;
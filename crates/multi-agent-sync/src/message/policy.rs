@@ -0,0 +1,101 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{coalesce, Coalesce};
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::Duration,
+};
+
+/// What a message channel does with a message sent while it is full.
+///
+/// Every message the channel refuses or discards is counted, see
+/// [`MessageSender::dropped`](super::MessageSender::dropped) and
+/// [`MessageReceiver::dropped`](super::MessageReceiver::dropped).
+///
+/// A channel with a capacity of zero is always full: it is not a rendezvous
+/// channel, so every policy but [`Unbounded`](Self::Unbounded) refuses all messages.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::message::{ChannelPolicy, MessageChannel};
+///
+/// let (sender, receiver) = MessageChannel::with_policy(2, ChannelPolicy::DropOldest).split();
+/// for message in 1..=3 {
+///     sender.send(message).unwrap();
+/// }
+///
+/// assert_eq!(receiver.drain(), vec![2, 3]);
+/// assert_eq!(receiver.dropped(), 1);
+/// ```
+pub enum ChannelPolicy<T> {
+    /// Refuse the new message: `send` fails with `Error::MessageChannelFull`.
+    DropNewest,
+    /// Discard the oldest pending message to make room for the new one, like a ring buffer.
+    DropOldest,
+    /// Wait up to the given time for the receiver to make room, then refuse the new message.
+    Block(Duration),
+    /// Grow as needed, ignoring the capacity. Nothing is ever dropped.
+    Unbounded,
+    /// Replace the pending message with the same [`Coalesce`] key as the new one,
+    /// full or not, keeping its place in the queue. A new message without a
+    /// pending one of the same key is refused if the channel is full.
+    ///
+    /// Built by [`coalesce_by_key`](Self::coalesce_by_key), the function tells
+    /// whether two messages have the same key. Messages with a `None` key are never
+    /// replaced. A replaced message is superseded rather than lost, so it
+    /// is not counted as dropped.
+    CoalesceByKey(fn(&T, &T) -> bool),
+}
+
+impl<T: Coalesce> ChannelPolicy<T> {
+    /// Replace pending messages superseded by a new one with the same key,
+    /// see [`CoalesceByKey`](Self::CoalesceByKey).
+    #[inline]
+    #[must_use]
+    pub const fn coalesce_by_key() -> Self {
+        Self::CoalesceByKey(coalesce::same_key::<T>)
+    }
+}
+
+impl<T> Default for ChannelPolicy<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::DropNewest
+    }
+}
+
+impl<T> Clone for ChannelPolicy<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ChannelPolicy<T> {}
+
+impl<T> Debug for ChannelPolicy<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DropNewest => f.write_str("DropNewest"),
+            Self::DropOldest => f.write_str("DropOldest"),
+            Self::Block(timeout) => f.debug_tuple("Block").field(timeout).finish(),
+            Self::Unbounded => f.write_str("Unbounded"),
+            Self::CoalesceByKey(_) => f.debug_tuple("CoalesceByKey").finish_non_exhaustive(),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ChannelPolicy;
+use crate::listeners::Listeners;
+use multi_agent_core::{Error, Result};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Condvar, Mutex, MutexGuard, PoisonError,
+    },
+    time::Duration,
+};
+
+/// Messages in flight between the senders and the receivers of a channel.
+pub struct Queue<T> {
+    state: Mutex<State<T>>,
+    /// Signaled when a message is queued or the last sender is dropped.
+    sent: Condvar,
+    /// Signaled when messages are taken or the last receiver is dropped.
+    received: Condvar,
+    capacity: usize,
+    policy: ChannelPolicy<T>,
+    dropped: AtomicU64,
+    /// Run after a message is queued.
+    listeners: Listeners,
+}
+
+struct State<T> {
+    messages: VecDeque<T>,
+    senders: usize,
+    receivers: usize,
+}
+
+impl<T> Queue<T> {
+    /// Create a queue with one sender and one receiver.
+    pub fn new(capacity: usize, policy: ChannelPolicy<T>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                messages: VecDeque::new(),
+                senders: 1,
+                receivers: 1,
+            }),
+            sent: Condvar::new(),
+            received: Condvar::new(),
+            capacity,
+            policy,
+            dropped: AtomicU64::new(0),
+            listeners: Listeners::default(),
+        }
+    }
+
+    pub const fn capacity(&self) -> Option<usize> {
+        match self.policy {
+            ChannelPolicy::Unbounded => None,
+            _ => Some(self.capacity),
+        }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.lock().messages.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.is_full_with(self.pending())
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn on_message<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.listeners.add(callback);
+    }
+
+    pub fn send(&self, message: T) -> Result<()> {
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+        if state.receivers == 0 {
+            return Err(Error::MessageChannelDisconnected);
+        }
+
+        match self.policy {
+            ChannelPolicy::DropOldest => {
+                while self.is_full_with(state.messages.len())
+                    && state.messages.pop_front().is_some()
+                {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            ChannelPolicy::Block(timeout) => {
+                state = self
+                    .received
+                    .wait_timeout_while(state, timeout, |state| {
+                        state.receivers > 0 && self.is_full_with(state.messages.len())
+                    })
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+                if state.receivers == 0 {
+                    return Err(Error::MessageChannelDisconnected);
+                }
+            }
+            ChannelPolicy::CoalesceByKey(same_key) => {
+                let pending: Option<&mut T> = state
+                    .messages
+                    .iter_mut()
+                    .find(|pending| same_key(pending, &message));
+                if let Some(pending) = pending {
+                    *pending = message;
+                    drop(state);
+                    self.notify_sent();
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+
+        if self.is_full_with(state.messages.len()) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return Err(Error::MessageChannelFull {
+                capacity: self.capacity,
+            });
+        }
+        state.messages.push_back(message);
+        drop(state);
+        self.notify_sent();
+        Ok(())
+    }
+
+    /// Take up to `limit` messages, oldest first.
+    pub fn take(&self, limit: usize) -> Vec<T> {
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+        let count: usize = limit.min(state.messages.len());
+        let messages: Vec<T> = state.messages.drain(..count).collect();
+        drop(state);
+        if !messages.is_empty() {
+            self.received.notify_all();
+        }
+        messages
+    }
+
+    /// Take the oldest message, waiting up to `timeout` for one, or forever if `None`.
+    ///
+    /// Returns `None` on timeout, or once the queue is empty and every sender is dropped.
+    pub fn take_one(&self, timeout: Option<Duration>) -> Option<T> {
+        let waiting = |state: &mut State<T>| state.messages.is_empty() && state.senders > 0;
+        let mut state: MutexGuard<'_, State<T>> = match timeout {
+            Some(timeout) if !timeout.is_zero() => {
+                self.sent
+                    .wait_timeout_while(self.lock(), timeout, waiting)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            Some(_) => self.lock(),
+            None => self
+                .sent
+                .wait_while(self.lock(), waiting)
+                .unwrap_or_else(PoisonError::into_inner),
+        };
+
+        let message: Option<T> = state.messages.pop_front();
+        drop(state);
+        if message.is_some() {
+            self.received.notify_all();
+        }
+        message
+    }
+
+    pub fn add_sender(&self) {
+        self.lock().senders += 1;
+    }
+
+    pub fn remove_sender(&self) {
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.sent.notify_all();
+        }
+    }
+
+    pub fn add_receiver(&self) {
+        self.lock().receivers += 1;
+    }
+
+    pub fn remove_receiver(&self) {
+        let mut state: MutexGuard<'_, State<T>> = self.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            drop(state);
+            self.received.notify_all();
+        }
+    }
+
+    fn notify_sent(&self) {
+        self.sent.notify_all();
+        self.listeners.notify();
+    }
+
+    fn is_full_with(&self, pending: usize) -> bool {
+        self.capacity().is_some_and(|capacity| pending >= capacity)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -14,57 +14,119 @@
  * limitations under the License.
  */
 
-use crossbeam_channel::Receiver;
-use std::fmt::Debug;
+use super::queue::Queue;
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter,
+    sync::Arc,
+    time::Duration,
+};
 
-#[derive(Debug, Clone)]
 pub struct MessageReceiver<T> {
-    inner: Receiver<T>,
+    queue: Arc<Queue<T>>,
 }
 
 impl<T> MessageReceiver<T> {
     #[inline]
-    pub(super) fn new(receiver: Receiver<T>) -> Self {
-        Self { inner: receiver }
+    pub(super) fn new(queue: Arc<Queue<T>>) -> Self {
+        Self { queue }
     }
 
     #[inline]
     pub fn drain(&self) -> Vec<T> {
-        self.inner.try_iter().collect()
+        self.queue.take(usize::MAX)
     }
 
     #[inline]
     pub fn drain_limit(&self, limit: usize) -> Vec<T> {
-        self.inner.try_iter().take(limit).collect()
+        self.queue.take(limit)
     }
 
     #[inline]
     pub fn try_recv(&self) -> Option<T> {
-        self.inner.try_recv().ok()
+        self.queue.take_one(Some(Duration::ZERO))
+    }
+
+    /// Block until a message arrives, or return `None` once every sender is dropped.
+    #[inline]
+    pub fn recv(&self) -> Option<T> {
+        self.queue.take_one(None)
+    }
+
+    /// Block until a message arrives or `timeout` elapses.
+    ///
+    /// Returns `None` on timeout, or right away once every sender is dropped.
+    #[inline]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        self.queue.take_one(Some(timeout))
     }
 
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
-        self.inner.try_iter()
+        iter::from_fn(|| self.try_recv())
     }
 
     #[inline]
     pub fn pending(&self) -> usize {
-        self.inner.len()
+        self.queue.pending()
     }
 
     #[inline]
     pub fn capacity(&self) -> Option<usize> {
-        self.inner.capacity()
+        self.queue.capacity()
+    }
+
+    /// Number of messages refused or discarded by the channel since it was created.
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped()
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.pending() == 0
     }
 
     #[inline]
     pub fn is_full(&self) -> bool {
-        self.inner.is_full()
+        self.queue.is_full()
+    }
+
+    /// Run `callback` on the sending thread after every message queued from now on.
+    ///
+    /// Meant for cheap notifications, such as waking up a thread waiting for
+    /// several sources at once. The callback lives as long as the channel.
+    #[inline]
+    pub fn on_message<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.queue.on_message(callback);
+    }
+}
+
+impl<T> Clone for MessageReceiver<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.queue.add_receiver();
+        Self::new(Arc::clone(&self.queue))
+    }
+}
+
+impl<T> Drop for MessageReceiver<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.queue.remove_receiver();
+    }
+}
+
+impl<T> Debug for MessageReceiver<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageReceiver")
+            .field("pending", &self.pending())
+            .field("capacity", &self.capacity())
+            .field("dropped", &self.dropped())
+            .finish()
     }
 }
@@ -14,29 +14,34 @@
  * limitations under the License.
  */
 
-use crossbeam_channel::{Sender, TrySendError};
-use multi_agent_core::{Error, Result};
-use std::fmt::Debug;
+use super::queue::Queue;
+use multi_agent_core::Result;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
 
-#[derive(Debug, Clone)]
 pub struct MessageSender<T> {
-    inner: Sender<T>,
+    queue: Arc<Queue<T>>,
 }
 
 impl<T> MessageSender<T> {
     #[inline]
-    pub(super) fn new(sender: Sender<T>) -> Self {
-        Self { inner: sender }
+    pub(super) fn new(queue: Arc<Queue<T>>) -> Self {
+        Self { queue }
     }
 
+    /// Send a message, applying the policy of the channel if it is full.
+    ///
+    /// Only blocks with `ChannelPolicy::Block`.
+    ///
+    /// # Errors
+    /// Returns `Error::MessageChannelFull` if the channel is full and its policy
+    /// refuses the message, or `Error::MessageChannelDisconnected` if the receiver
+    /// has been dropped.
     #[inline]
     pub fn send(&self, message: T) -> Result<()> {
-        self.inner.try_send(message).map_err(|err| match err {
-            TrySendError::Full(_) => Error::MessageChannelFull {
-                capacity: self.inner.capacity().unwrap_or(0),
-            },
-            TrySendError::Disconnected(_) => Error::MessageChannelDisconnected,
-        })
+        self.queue.send(message)
     }
 
     #[inline]
@@ -46,21 +51,53 @@ impl<T> MessageSender<T> {
 
     #[inline]
     pub fn pending(&self) -> usize {
-        self.inner.len()
+        self.queue.pending()
     }
 
     #[inline]
     pub fn capacity(&self) -> Option<usize> {
-        self.inner.capacity()
+        self.queue.capacity()
+    }
+
+    /// Number of messages refused or discarded by the channel since it was created.
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped()
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.pending() == 0
     }
 
     #[inline]
     pub fn is_full(&self) -> bool {
-        self.inner.is_full()
+        self.queue.is_full()
+    }
+}
+
+impl<T> Clone for MessageSender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.queue.add_sender();
+        Self::new(Arc::clone(&self.queue))
+    }
+}
+
+impl<T> Drop for MessageSender<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.queue.remove_sender();
+    }
+}
+
+impl<T> Debug for MessageSender<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageSender")
+            .field("pending", &self.pending())
+            .field("capacity", &self.capacity())
+            .field("dropped", &self.dropped())
+            .finish()
     }
 }
@@ -0,0 +1,35 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::DeltaFeed;
+use std::fmt::Debug;
+
+/// Writer publishing a new value to a [`Shared`](crate::Shared) on every tick.
+///
+/// Implemented by [`TripleBuffer`](crate::TripleBuffer) and
+/// [`DiffBuffer`](crate::DiffBuffer), so the runtime can pick one per simulation
+/// without knowing which bounds the data satisfies.
+pub trait Publisher<T>: Debug + Send {
+    /// Make a copy of `value` the current value of the shared container.
+    fn publish(&mut self, value: &T);
+
+    /// Feed of the changes published, for readers keeping their own copy of the
+    /// value, if the publisher records them.
+    #[inline]
+    fn feed(&self) -> Option<DeltaFeed<T>> {
+        None
+    }
+}
@@ -14,9 +14,10 @@
  * limitations under the License.
  */
 
+use crate::{listeners::Listeners, signal::Signal};
 use arc_swap::ArcSwap;
 use multi_agent_core::GuardArc;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 /// A thread-safe, lock-free shared state container using the RCU (Read-Copy-Update) pattern.
 ///
@@ -25,7 +26,9 @@ use std::sync::Arc;
 ///
 /// # Performance Characteristics
 /// - **Read (`load`)**: Lock-free, very fast, O(1)
-/// - **Write (`store`, `update`)**: Requires cloning T, O(n) where n is the size of T
+/// - **Write (`store`, `update`)**: Requires cloning T, O(n) where n is the size of T.
+///   Lock-free unless a thread waits in [`wait_for_change`](Self::wait_for_change),
+///   followed by the callbacks registered with [`on_change`](Self::on_change)
 ///
 /// # Best Practices
 /// - Keep `T` small and cheap to clone
@@ -56,6 +59,14 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct Shared<T> {
     inner: Arc<ArcSwap<T>>,
+    changes: Arc<Changes>,
+}
+
+/// Signal of every write, and the callbacks to run after it.
+#[derive(Debug, Default)]
+struct Changes {
+    signal: Signal,
+    listeners: Listeners,
 }
 
 impl<T> Shared<T> {
@@ -72,6 +83,7 @@ impl<T> Shared<T> {
     pub fn new(data: T) -> Self {
         Self {
             inner: Arc::new(ArcSwap::from_pointee(data)),
+            changes: Arc::new(Changes::default()),
         }
     }
 
@@ -95,6 +107,138 @@ impl<T> Shared<T> {
     pub fn load(&self) -> GuardArc<T> {
         self.inner.load()
     }
+
+    /// Load an owned pointer to the current value.
+    ///
+    /// Slower than [`load`](Self::load), but the returned `Arc` can be kept
+    /// around for as long as needed without holding back other threads.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_sync::Shared;
+    /// use std::sync::Arc;
+    ///
+    /// let shared = Shared::new(42);
+    /// let snapshot: Arc<i32> = shared.load_full();
+    /// shared.store(100);
+    /// assert_eq!(*snapshot, 42);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn load_full(&self) -> Arc<T> {
+        self.inner.load_full()
+    }
+
+    /// Number of writes since the container was created.
+    ///
+    /// Compare two generations to know whether the value changed in between,
+    /// without comparing the values themselves.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_sync::Shared;
+    ///
+    /// let shared = Shared::new(42);
+    /// let generation = shared.generation();
+    /// shared.store(100);
+    /// assert_ne!(shared.generation(), generation);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.changes.signal.generation()
+    }
+
+    /// Block until the value is written after `generation`, or until `timeout` elapses.
+    ///
+    /// # Arguments
+    /// * `generation` - Generation already seen by the caller
+    /// * `timeout` - Maximum time to wait
+    ///
+    /// # Returns
+    /// The current generation, equal to `generation` if the wait timed out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_sync::Shared;
+    /// use std::{thread, time::Duration};
+    ///
+    /// let shared = Shared::new(0);
+    /// let generation = shared.generation();
+    ///
+    /// let writer = shared.clone();
+    /// thread::spawn(move || writer.store(1));
+    ///
+    /// let current = shared.wait_for_change(generation, Duration::from_secs(1));
+    /// assert_ne!(current, generation);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn wait_for_change(&self, generation: u64, timeout: Duration) -> u64 {
+        self.changes.signal.wait_for_change(generation, timeout)
+    }
+
+    /// Run `callback` on the writing thread after every following write.
+    ///
+    /// Meant for cheap notifications, such as waking up a GUI to redraw it. The
+    /// callback must not register other callbacks on the same container, and it
+    /// lives as long as the container.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_sync::Shared;
+    /// use std::sync::{
+    ///     atomic::{AtomicU32, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// let shared = Shared::new(0);
+    /// let writes = Arc::new(AtomicU32::new(0));
+    ///
+    /// let counter = Arc::clone(&writes);
+    /// shared.on_change(move || {
+    ///     counter.fetch_add(1, Ordering::Relaxed);
+    /// });
+    /// shared.store(1);
+    /// shared.update(|value| *value += 1);
+    /// assert_eq!(writes.load(Ordering::Relaxed), 2);
+    /// ```
+    #[inline]
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.changes.listeners.add(callback);
+    }
+
+    /// Replace the current value with one already behind an `Arc`, and return the
+    /// previous one.
+    ///
+    /// Nothing is cloned or allocated. Readers that have already loaded the previous
+    /// value hold their own reference to it, counted in the returned `Arc`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use multi_agent_sync::Shared;
+    /// use std::sync::Arc;
+    ///
+    /// let shared = Shared::new(42);
+    /// let previous: Arc<i32> = shared.swap(Arc::new(100));
+    /// assert_eq!(*previous, 42);
+    /// assert_eq!(**shared.load(), 100);
+    /// ```
+    #[inline]
+    pub fn swap(&self, data: Arc<T>) -> Arc<T> {
+        let previous: Arc<T> = self.inner.swap(data);
+        self.notify_change();
+        previous
+    }
+
+    #[inline]
+    fn notify_change(&self) {
+        self.changes.signal.notify();
+        self.changes.listeners.notify();
+    }
 }
 
 impl<T: Clone> Shared<T> {
@@ -117,6 +261,7 @@ impl<T: Clone> Shared<T> {
     #[inline]
     pub fn store(&self, data: T) {
         self.inner.store(Arc::new(data));
+        self.notify_change();
     }
 
     /// Update the value using a closure (RCU pattern).
@@ -148,6 +293,7 @@ impl<T: Clone> Shared<T> {
             f(&mut new_data);
             new_data
         });
+        self.notify_change();
     }
 }
 
@@ -155,6 +301,10 @@ impl<T: Clone> Shared<T> {
 mod tests {
     use super::Shared;
     use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
         thread,
         time::Duration,
     };
@@ -194,6 +344,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wait_for_change() {
+        let shared: Shared<u8> = Shared::new(0);
+        let generation: u64 = shared.generation();
+
+        assert_eq!(
+            shared.wait_for_change(generation, Duration::from_millis(5)),
+            generation
+        );
+
+        let writer = shared.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            writer.update(|data| *data += 1);
+        });
+
+        let current: u64 = shared.wait_for_change(generation, Duration::from_secs(5));
+        assert_eq!(current, generation + 1);
+        assert_eq!(**shared.load(), 1);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_on_change() {
+        let shared: Shared<u32> = Shared::new(0);
+        let writes = Arc::new(AtomicU64::new(0));
+
+        let counter = Arc::clone(&writes);
+        shared.on_change(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        let _ = shared.load();
+        shared.store(1);
+        let other = shared.clone();
+        other.update(|value| *value += 1);
+        let _ = shared.swap(Arc::new(5));
+
+        assert_eq!(writes.load(Ordering::Relaxed), 3);
+    }
+
     #[test]
     fn test_concurrent_updates() {
         // Test that multiple threads can update without data races
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Condvar, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+/// Generation counter bumped on every event, that threads can block on.
+///
+/// Signalling is lock-free while nobody waits: the condition variable and its
+/// mutex are only used when a thread is blocked in [`wait_for_change`](Self::wait_for_change).
+///
+/// Notify it from the `on_change` and `on_message` callbacks of several sources
+/// to wait for the first event of any of them.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::{message::MessageChannel, Shared, Signal};
+/// use std::{sync::Arc, time::Duration};
+///
+/// let signal = Arc::new(Signal::default());
+/// let shared = Shared::new(0);
+/// let (sender, receiver) = MessageChannel::new(10).split();
+///
+/// let notifier = Arc::clone(&signal);
+/// shared.on_change(move || notifier.notify());
+/// let notifier = Arc::clone(&signal);
+/// receiver.on_message(move || notifier.notify());
+///
+/// let generation = signal.generation();
+/// sender.send(1).unwrap();
+/// assert_ne!(signal.wait_for_change(generation, Duration::from_secs(1)), generation);
+/// ```
+#[derive(Debug, Default)]
+pub struct Signal {
+    generation: AtomicU64,
+    waiters: AtomicUsize,
+    lock: Mutex<()>,
+    changed: Condvar,
+}
+
+impl Signal {
+    /// Number of events signalled so far.
+    #[inline]
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Signal an event and wake up the waiting threads, if any.
+    #[inline]
+    pub fn notify(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            // Taking the lock orders this event after the check of a waiter about to sleep.
+            drop(self.lock.lock().unwrap_or_else(PoisonError::into_inner));
+            self.changed.notify_all();
+        }
+    }
+
+    /// Block until an event is signalled after `generation`, or until `timeout` elapses,
+    /// and return the current generation.
+    #[inline]
+    #[must_use]
+    pub fn wait_for_change(&self, generation: u64, timeout: Duration) -> u64 {
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        drop(
+            self.changed
+                .wait_timeout_while(
+                    self.lock.lock().unwrap_or_else(PoisonError::into_inner),
+                    timeout,
+                    |()| self.generation() == generation,
+                )
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+        self.generation()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Signal;
+    use std::{sync::Arc, thread, time::Duration};
+
+    #[test]
+    fn test_signal_wakes_up_waiters() {
+        let signal = Arc::new(Signal::default());
+
+        for _ in 0..100 {
+            let generation: u64 = signal.generation();
+            let notifier = Arc::clone(&signal);
+            let handle = thread::spawn(move || notifier.notify());
+
+            let current: u64 = signal.wait_for_change(generation, Duration::from_secs(5));
+            assert_eq!(current, generation + 1);
+            handle.join().unwrap();
+        }
+        assert_eq!(signal.wait_for_change(100, Duration::from_millis(5)), 100);
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Fill level of a message channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChannelFill {
+    /// Number of messages waiting in the channel.
+    pub pending: usize,
+    /// Capacity of the channel, `None` if unbounded.
+    pub capacity: Option<usize>,
+    /// Messages refused or discarded by the channel since it was created.
+    pub dropped: u64,
+}
+
+impl ChannelFill {
+    /// Fraction of the capacity in use, between 0 and 1, `None` if unbounded.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> Option<f32> {
+        self.capacity
+            .map(|capacity| self.pending as f32 / capacity.max(1) as f32)
+    }
+}
+
+/// Measurements published by the simulation thread after every tick.
+///
+/// The runtime stores them in a [`Shared`](crate::Shared) read by the GUI,
+/// so only the latest values are available: keep a history on the reading side
+/// to draw graphs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RuntimeStats {
+    /// Index of the last executed tick.
+    pub tick: u64,
+    /// Wall-clock time spent inside `update()` during the last tick.
+    pub update_time: Duration,
+    /// Wall-clock time spent cloning and publishing the simulation data during the last tick.
+    pub publish_time: Duration,
+    /// Number of ticks actually executed per wall-clock second, measured over
+    /// the last half second.
+    pub achieved_hz: f64,
+    /// Number of times the simulation thread woke up later than planned
+    /// because the previous ticks took longer than a period.
+    pub overruns: u64,
+    /// Messages waiting to be delivered to the simulation, before the last tick drained them.
+    pub gui_to_simulation: ChannelFill,
+    /// Messages sent by the simulation and not read by the GUI yet.
+    pub simulation_to_gui: ChannelFill,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_fill_ratio() {
+        let bounded = ChannelFill {
+            pending: 25,
+            capacity: Some(100),
+            dropped: 0,
+        };
+        let unbounded = ChannelFill {
+            pending: 25,
+            capacity: None,
+            dropped: 0,
+        };
+
+        assert_eq!(bounded.ratio(), Some(0.25));
+        assert_eq!(unbounded.ratio(), None);
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Change;
+use std::{
+    collections::VecDeque,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// A bounded history of the data published by the simulation, indexed by tick.
+///
+/// Snapshots are kept as the `Arc<T>` already stored in [`Shared`](crate::Shared),
+/// so recording a frame does not clone the data. Frames recorded with
+/// [`push_change`](Self::push_change) may keep only the changes since the
+/// previous one, as published by a [`DiffBuffer`](crate::DiffBuffer), and are
+/// rebuilt from the nearest full frame when read.
+///
+/// Once the timeline is full, the oldest frame is dropped for every new one,
+/// along with the changes that can no longer be rebuilt without it: a timeline
+/// of changes keeps between half its capacity and its capacity frames. Every
+/// clone refers to the same frames.
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::Timeline;
+/// use std::sync::Arc;
+///
+/// let timeline = Timeline::new(2);
+/// timeline.push(0, Arc::new("a"));
+/// timeline.push(1, Arc::new("b"));
+/// timeline.push(2, Arc::new("c"));
+///
+/// assert_eq!(timeline.ticks(), Some(1..=2));
+/// assert_eq!(timeline.get(0), None);
+/// assert_eq!(*timeline.get(2).unwrap(), "c");
+/// ```
+#[derive(Debug)]
+pub struct Timeline<T> {
+    inner: Arc<TimelineInner<T>>,
+}
+
+#[derive(Debug)]
+struct TimelineInner<T> {
+    capacity: usize,
+    frames: Mutex<VecDeque<(u64, Change<T>)>>,
+}
+
+impl<T> Clone for Timeline<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Timeline<T> {
+    /// Create an empty timeline keeping at most `capacity` frames.
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(TimelineInner {
+                capacity,
+                frames: Mutex::new(VecDeque::with_capacity(capacity)),
+            }),
+        }
+    }
+
+    /// Maximum number of frames kept.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Number of frames currently kept.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no frame is kept.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Record the data published by the given tick, dropping the oldest frame if full.
+    ///
+    /// Ticks are expected to be pushed in increasing order.
+    #[inline]
+    pub fn push(&self, tick: u64, data: Arc<T>) {
+        if self.inner.capacity == 0 {
+            return;
+        }
+
+        let mut frames = self.lock();
+        self.insert(&mut frames, tick, Change::Keyframe(data));
+    }
+
+    /// Record the changes published by the given tick, dropping the oldest frames
+    /// if full.
+    ///
+    /// `change` must apply to the previous frame pushed. The whole data, given by
+    /// `keyframe`, is recorded instead when there is no previous frame, or every
+    /// half capacity, to bound the changes applied when reading a frame.
+    ///
+    /// Ticks are expected to be pushed in increasing order.
+    #[inline]
+    pub fn push_change(&self, tick: u64, change: Change<T>, keyframe: impl FnOnce() -> Arc<T>) {
+        if self.inner.capacity == 0 {
+            return;
+        }
+
+        let mut frames = self.lock();
+        let deltas: usize = frames
+            .iter()
+            .rev()
+            .take_while(|(_, change)| matches!(change, Change::Delta(_)))
+            .count();
+        let change: Change<T> = match change {
+            Change::Delta(_) if frames.is_empty() || deltas + 1 >= self.keyframe_interval() => {
+                Change::Keyframe(keyframe())
+            }
+            change => change,
+        };
+        self.insert(&mut frames, tick, change);
+        drop(frames);
+    }
+
+    /// Append a frame, dropping the oldest ones and the changes that no longer
+    /// follow a full frame if full.
+    #[inline]
+    fn insert(&self, frames: &mut VecDeque<(u64, Change<T>)>, tick: u64, change: Change<T>) {
+        while frames.len() >= self.inner.capacity {
+            frames.pop_front();
+            while frames
+                .front()
+                .is_some_and(|(_, change)| matches!(change, Change::Delta(_)))
+            {
+                frames.pop_front();
+            }
+        }
+        frames.push_back((tick, change));
+    }
+
+    /// Range of the ticks currently kept, if any.
+    #[inline]
+    #[must_use]
+    pub fn ticks(&self) -> Option<RangeInclusive<u64>> {
+        let frames = self.lock();
+        let first: u64 = frames.front()?.0;
+        let last: u64 = frames.back()?.0;
+        drop(frames);
+        Some(first..=last)
+    }
+
+    /// Drop every frame recorded after the given tick.
+    #[inline]
+    pub fn truncate_after(&self, tick: u64) {
+        let mut frames = self.lock();
+        let len: usize = frames.partition_point(|(frame_tick, _)| *frame_tick <= tick);
+        frames.truncate(len);
+    }
+
+    /// Drop every frame.
+    #[inline]
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Frames between two full ones recorded by [`push_change`](Self::push_change).
+    #[inline]
+    fn keyframe_interval(&self) -> usize {
+        (self.inner.capacity / 2).max(1)
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, VecDeque<(u64, Change<T>)>> {
+        self.inner
+            .frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Clone> Timeline<T> {
+    /// Data published by the given tick, if it is still kept.
+    ///
+    /// Copies the nearest full frame and applies the changes recorded since, if
+    /// the frame only kept the changes.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, tick: u64) -> Option<Arc<T>> {
+        let frames = self.lock();
+        let index: usize = frames.binary_search_by_key(&tick, |(tick, _)| *tick).ok()?;
+        let keyframe: usize = frames
+            .range(..=index)
+            .rposition(|(_, change)| matches!(change, Change::Keyframe(_)))?;
+
+        let Some((_, Change::Keyframe(data))) = frames.get(keyframe) else {
+            return None;
+        };
+        if keyframe == index {
+            return Some(Arc::clone(data));
+        }
+        let mut data: T = T::clone(data);
+        frames
+            .range(keyframe + 1..=index)
+            .for_each(|(_, change)| change.apply_to(&mut data));
+        drop(frames);
+        Some(Arc::new(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timeline;
+    use crate::{Change, Patch};
+    use std::{any::Any, ops::Range, sync::Arc};
+
+    struct Add(u64);
+
+    impl Patch<u64> for Add {
+        fn apply_to(&self, value: &mut u64) {
+            *value += self.0;
+        }
+
+        fn delta(&self) -> &dyn Any {
+            &self.0
+        }
+    }
+
+    fn add(amount: u64) -> Change<u64> {
+        Change::Delta(Arc::new(Add(amount)))
+    }
+
+    fn timeline(capacity: usize, ticks: Range<u64>) -> Timeline<u64> {
+        let timeline = Timeline::new(capacity);
+        for tick in ticks {
+            timeline.push(tick, Arc::new(tick * 10));
+        }
+        timeline
+    }
+
+    #[test]
+    fn test_timeline_drops_oldest_frames() {
+        let timeline = timeline(3, 0..5);
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline.ticks(), Some(2..=4));
+        assert_eq!(timeline.get(1), None);
+        assert_eq!(*timeline.get(3).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_timeline_truncate_after() {
+        let timeline = timeline(10, 0..5);
+
+        timeline.truncate_after(2);
+        assert_eq!(timeline.ticks(), Some(0..=2));
+
+        timeline.push(3, Arc::new(0));
+        assert_eq!(*timeline.get(3).unwrap(), 0);
+
+        timeline.clear();
+        assert!(timeline.is_empty());
+        assert_eq!(timeline.ticks(), None);
+    }
+
+    #[test]
+    fn test_timeline_without_capacity() {
+        let timeline = timeline(0, 0..5);
+
+        assert!(timeline.is_empty());
+    }
+
+    #[test]
+    fn test_timeline_clones_share_frames() {
+        let timeline = timeline(4, 0..2);
+        let clone = timeline.clone();
+
+        clone.push(2, Arc::new(20));
+
+        assert_eq!(timeline.len(), 3);
+    }
+
+    #[test]
+    fn test_timeline_rebuilds_changes() {
+        let timeline = Timeline::new(8);
+        timeline.push_change(0, add(1), || Arc::new(10));
+        for tick in 1..4 {
+            timeline.push_change(tick, add(1), || unreachable!());
+        }
+
+        assert_eq!(*timeline.get(0).unwrap(), 10);
+        assert_eq!(*timeline.get(3).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_timeline_drops_changes_without_keyframe() {
+        let timeline = Timeline::new(4);
+        for tick in 0..5 {
+            timeline.push_change(tick, add(1), || Arc::new(tick * 10));
+        }
+
+        assert_eq!(timeline.ticks(), Some(2..=4));
+        assert_eq!(*timeline.get(3).unwrap(), 21);
+        assert_eq!(*timeline.get(4).unwrap(), 40);
+
+        timeline.push_change(5, add(1), || unreachable!());
+        timeline.push_change(6, add(1), || Arc::new(60));
+        assert_eq!(timeline.ticks(), Some(4..=6));
+        assert_eq!(*timeline.get(5).unwrap(), 41);
+    }
+}
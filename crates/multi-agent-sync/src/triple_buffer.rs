@@ -0,0 +1,194 @@
+/*
+ * Copyright 2026 Nicolas Spijkerman
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{Publisher, Shared};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Writer publishing values to a [`Shared`] through recycled buffers.
+///
+/// [`Shared::store`] moves every value into a new allocation, so publishing a
+/// large value on every tick clones it into fresh memory each time. A triple
+/// buffer keeps the buffers it replaced and copies the next values into them with
+/// [`Clone::clone_from`], which reuses the memory of vectors, strings and maps.
+///
+/// At most three buffers exist: the front one, read through the [`Shared`], and
+/// two back ones. Two buffers take turns as long as readers release their frames
+/// before the next publication. A back buffer still referenced by a reader, or by
+/// a [`Timeline`](crate::Timeline), is skipped and the third one is used, or
+/// allocated, instead, so readers never see a value change under them.
+///
+/// Readers are unchanged: they keep calling [`Shared::load`].
+///
+/// # Example
+/// ```rust
+/// use multi_agent_sync::{Shared, TripleBuffer};
+///
+/// let shared = Shared::new(vec![0.0_f32; 1_000]);
+/// let mut buffer = TripleBuffer::new(shared.clone());
+///
+/// let mut trail_map = vec![0.0_f32; 1_000];
+/// for tick in 0..10 {
+///     trail_map[tick] = 1.0;
+///     buffer.publish(&trail_map);
+/// }
+/// assert_eq!(shared.load()[9], 1.0);
+/// ```
+pub struct TripleBuffer<T> {
+    shared: Shared<T>,
+    back: Vec<Arc<T>>,
+}
+
+impl<T> TripleBuffer<T> {
+    /// Number of buffers kept besides the front one.
+    const BACK_BUFFERS: usize = 2;
+
+    /// Create a triple buffer publishing to `shared`.
+    #[inline]
+    #[must_use]
+    pub fn new(shared: Shared<T>) -> Self {
+        Self {
+            shared,
+            back: Vec::with_capacity(Self::BACK_BUFFERS + 1),
+        }
+    }
+
+    /// Shared container the values are published to.
+    #[inline]
+    #[must_use]
+    pub const fn shared(&self) -> &Shared<T> {
+        &self.shared
+    }
+
+    /// Number of back buffers nobody reads anymore, ready to be written.
+    #[inline]
+    #[must_use]
+    pub fn free_buffers(&self) -> usize {
+        self.back
+            .iter()
+            .filter(|buffer| Arc::strong_count(buffer) == 1 && Arc::weak_count(buffer) == 0)
+            .count()
+    }
+}
+
+impl<T: Clone> TripleBuffer<T> {
+    /// Copy `value` into a free back buffer, or a new one if none is free, and
+    /// make it the front buffer.
+    ///
+    /// The replaced front buffer becomes a back buffer, and the oldest back
+    /// buffer is dropped if there are more than two.
+    #[inline]
+    pub fn publish(&mut self, value: &T) {
+        let free: Option<usize> = self
+            .back
+            .iter_mut()
+            .position(|buffer| Arc::get_mut(buffer).is_some());
+
+        let front: Arc<T> = free.map_or_else(
+            || Arc::new(value.clone()),
+            |index| {
+                let mut buffer: Arc<T> = self.back.remove(index);
+                if let Some(data) = Arc::get_mut(&mut buffer) {
+                    data.clone_from(value);
+                }
+                buffer
+            },
+        );
+
+        self.back.push(self.shared.swap(front));
+        if self.back.len() > Self::BACK_BUFFERS {
+            self.back.remove(0);
+        }
+    }
+}
+
+impl<T> Publisher<T> for TripleBuffer<T>
+where
+    T: Clone + Send + Sync,
+{
+    #[inline]
+    fn publish(&mut self, value: &T) {
+        Self::publish(self, value);
+    }
+}
+
+impl<T> Debug for TripleBuffer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TripleBuffer")
+            .field("back_buffers", &self.back.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TripleBuffer;
+    use crate::Shared;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_buffers_are_recycled() {
+        let shared: Shared<Vec<u32>> = Shared::new(vec![0; 64]);
+        let mut buffer = TripleBuffer::new(shared.clone());
+
+        buffer.publish(&vec![1; 64]);
+        buffer.publish(&vec![2; 64]);
+        buffer.publish(&vec![3; 64]);
+        let addresses: Vec<*const u32> = (4..10)
+            .map(|value| {
+                buffer.publish(&vec![value; 64]);
+                shared.load().as_ptr()
+            })
+            .collect();
+
+        assert_eq!(**shared.load(), vec![9; 64]);
+        assert_ne!(addresses[0], addresses[1]);
+        assert_eq!(addresses[0], addresses[2]);
+        assert_eq!(addresses[1], addresses[3]);
+        assert_eq!(buffer.free_buffers(), 1);
+    }
+
+    #[test]
+    fn test_read_buffers_are_not_overwritten() {
+        let shared: Shared<Vec<u32>> = Shared::new(vec![0]);
+        let mut buffer = TripleBuffer::new(shared.clone());
+
+        buffer.publish(&vec![1]);
+        let held: Arc<Vec<u32>> = shared.load_full();
+        for value in 2..10 {
+            buffer.publish(&vec![value]);
+        }
+
+        assert_eq!(*held, vec![1]);
+        assert_eq!(buffer.free_buffers(), 1);
+        assert_eq!(**shared.load(), vec![9]);
+    }
+
+    #[test]
+    fn test_publish_notifies_readers() {
+        let shared: Shared<u32> = Shared::new(0);
+        let mut buffer = TripleBuffer::new(shared.clone());
+        let generation: u64 = shared.generation();
+
+        buffer.publish(&1);
+
+        assert_ne!(shared.generation(), generation);
+        assert_eq!(**buffer.shared().load(), 1);
+    }
+}
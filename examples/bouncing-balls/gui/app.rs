@@ -31,7 +31,6 @@ use multi_agent::{
 pub struct BouncingBallsGui {
     area_config: BouncingAreaConfig,
     area_max_size: Vec2,
-    paused: bool,
 }
 
 impl MultiAgentGui for BouncingBallsGui {
@@ -73,18 +72,6 @@ impl MultiAgentGui for BouncingBallsGui {
         ui.separator();
         ui.heading("Configuration");
 
-        ui.heading(RichText::new("Simulation").size(14.0));
-        ui.horizontal(|ui| {
-            if ui.selectable_label(!self.paused, "Run").clicked() {
-                self.paused = false;
-                send_message_to_simulation(MessageFromGuiToSimulator::Resume);
-            }
-            if ui.selectable_label(self.paused, "Pause").clicked() {
-                self.paused = true;
-                send_message_to_simulation(MessageFromGuiToSimulator::Pause);
-            }
-        });
-
         ui.heading(RichText::new("Balls").size(14.0));
         let old_ball_count: usize = self.area_config.ball_count;
         let ball_count_slider: Slider =
@@ -16,8 +16,6 @@
 
 #[derive(Debug, Clone)]
 pub enum MessageFromGuiToSimulator {
-    Pause,
-    Resume,
     RecalculateArea,
     Shake,
     AddBalls(usize),
@@ -23,5 +23,7 @@ use simulation::BouncingBallsSimulator;
 use multi_agent::AppLauncher;
 
 fn main() -> multi_agent::Result<()> {
-    AppLauncher::run::<BouncingBallsSimulator, BouncingBallsGui>()
+    AppLauncher::builder()
+        .timeline_capacity(600)
+        .run::<BouncingBallsSimulator, BouncingBallsGui>()
 }
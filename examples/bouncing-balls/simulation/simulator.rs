@@ -16,14 +16,14 @@
 
 use super::{BouncingBall, MessageFromSimulatorToGui};
 use crate::gui::{BouncingAreaConfig, MessageFromGuiToSimulator};
-use multi_agent::MultiAgentSimulation;
-use rand::{rngs::ThreadRng, Rng};
+use multi_agent::{CancellationToken, MultiAgentSimulation, SeedFactory};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::time::Duration;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct BouncingBallsSimulator {
     balls: Vec<BouncingBall>,
-    paused: bool,
+    rng: StdRng,
 }
 
 impl BouncingBallsSimulator {
@@ -32,8 +32,15 @@ impl BouncingBallsSimulator {
     const BOUNCE_DAMPING: f32 = 0.90;
     const RADIUS_RANGE: [f32; 2] = [5.0, 10.0];
 
+    fn with_balls(balls: Vec<BouncingBall>, seeds: SeedFactory) -> Self {
+        Self {
+            balls,
+            rng: StdRng::seed_from_u64(seeds.stream(0)),
+        }
+    }
+
     fn add_balls(&mut self, count: usize, max_x: f32, max_y: f32) {
-        let mut rng: ThreadRng = rand::rng();
+        let rng: &mut StdRng = &mut self.rng;
 
         for _ in 0..count {
             let radius: f32 = rng.random_range(Self::RADIUS_RANGE[0]..Self::RADIUS_RANGE[1]);
@@ -62,7 +69,7 @@ impl BouncingBallsSimulator {
     }
 
     fn shake(&mut self) {
-        let mut rng: ThreadRng = rand::rng();
+        let rng: &mut StdRng = &mut self.rng;
         for ball in self.balls.iter_mut() {
             ball.dx = rng.random_range(-50.0..50.0);
             ball.dy = rng.random_range(-50.0..-10.0);
@@ -131,10 +138,10 @@ impl MultiAgentSimulation for BouncingBallsSimulator {
     type MessageFromGui = MessageFromGuiToSimulator;
     type MessageToGui = MessageFromSimulatorToGui;
 
-    fn new(initial_gui_data: Self::GuiData) -> multi_agent::Result<Self> {
+    fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> multi_agent::Result<Self> {
         let (max_x, max_y): (f32, f32) = (initial_gui_data.width, initial_gui_data.height);
 
-        let mut simulator: Self = Self::default();
+        let mut simulator: Self = Self::with_balls(Vec::new(), seeds);
         simulator.add_balls(initial_gui_data.ball_count, max_x, max_y);
 
         Ok(simulator)
@@ -146,6 +153,7 @@ impl MultiAgentSimulation for BouncingBallsSimulator {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         _send_message_to_gui: F,
+        _cancellation: &CancellationToken,
     ) -> multi_agent::Result<&Self::SimulationData>
     where
         F: Fn(Self::MessageToGui),
@@ -154,8 +162,6 @@ impl MultiAgentSimulation for BouncingBallsSimulator {
 
         for message in messages {
             match message {
-                MessageFromGuiToSimulator::Pause => self.paused = true,
-                MessageFromGuiToSimulator::Resume => self.paused = false,
                 MessageFromGuiToSimulator::RecalculateArea => self.bounce_balls(width, height),
                 MessageFromGuiToSimulator::Shake => self.shake(),
                 MessageFromGuiToSimulator::AddBalls(count) => self.add_balls(count, width, height),
@@ -163,13 +169,21 @@ impl MultiAgentSimulation for BouncingBallsSimulator {
             }
         }
 
-        if !self.paused {
-            let dt: f32 = delta_time.as_secs_f32();
-            self.apply_gravity(dt);
-            self.move_balls(dt);
-            self.bounce_balls(width, height);
-        }
+        let dt: f32 = delta_time.as_secs_f32();
+        self.apply_gravity(dt);
+        self.move_balls(dt);
+        self.bounce_balls(width, height);
 
         Ok(&self.balls)
     }
+
+    fn restore(
+        _gui_data: Self::GuiData,
+        simulation_data: Self::SimulationData,
+        seeds: SeedFactory,
+    ) -> multi_agent::Result<Self> {
+        // The generator is not part of the published data, so the restored
+        // simulation draws from a fresh one seeded like at creation.
+        Ok(Self::with_balls(simulation_data, seeds))
+    }
 }
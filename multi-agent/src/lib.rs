@@ -17,6 +17,29 @@
 mod app;
 
 pub use multi_agent_gui as gui;
+pub use multi_agent_sync as sync;
 
 pub use app::AppLauncher;
-pub use multi_agent_core::{Error, GuardArc, MultiAgentGui, MultiAgentSimulation, Result};
+pub use multi_agent_core::{
+    CancellationToken, Error, GuardArc, MultiAgentGui, MultiAgentSimulation, Result, SeedFactory,
+    Session,
+};
+#[cfg(feature = "scripting")]
+pub use multi_agent_runtime::Scenario;
+#[cfg(feature = "cli")]
+pub use multi_agent_runtime::{CommandLine, LaunchOptions, NoOptions};
+pub use multi_agent_runtime::{
+    BatchReport, BatchRun, BatchRunner, Candidate, CatchUpPolicy, GenerationSummary,
+    HeadlessReport, HeadlessRunner, MetricSample, OptimizationReport, Optimizer, PanicPolicy,
+    ParameterBounds, Publication, Recorder, Recording, RuntimeBuilder, Schedule, TickInputs,
+    TickStats,
+};
+#[cfg(feature = "http")]
+pub use multi_agent_runtime::{HttpObserver, HttpServer};
+#[cfg(feature = "remote")]
+pub use multi_agent_runtime::{DeltaCodec, RemoteAddress, RemoteClient, RemoteServer};
+pub use multi_agent_sync::{
+    Change, DeltaFeed, DiffBuffer, Diffable, Patch, Publisher, Recovery, RecoveryAction,
+    ScriptRequest, ScriptStatus, SessionRequest, SessionStatus, SimulationControl,
+    SimulationFailure, Timeline, TripleBuffer,
+};
@@ -41,12 +41,10 @@ impl Default for Boids {
 }
 
 impl Boids {
-    pub fn spawn_random(&mut self, count: usize, max_speed: f32) {
-        let mut rng = Rng::new();
-
+    pub fn spawn_random(&mut self, rng: &mut Rng, count: usize, max_speed: f32) {
         for _ in 0..count {
             self.boids
-                .push(Boid::random(&mut rng, self.width, self.height, max_speed));
+                .push(Boid::random(rng, self.width, self.height, max_speed));
         }
     }
 
@@ -59,11 +57,11 @@ impl Boids {
         self.height = height;
     }
 
-    pub fn set_count(&mut self, target: usize, max_speed: f32) {
+    pub fn set_count(&mut self, rng: &mut Rng, target: usize, max_speed: f32) {
         let current: usize = self.boids.len();
 
         match current.cmp(&target) {
-            Ordering::Less => self.spawn_random(target - current, max_speed),
+            Ordering::Less => self.spawn_random(rng, target - current, max_speed),
             Ordering::Greater => self.boids.truncate(target),
             Ordering::Equal => {}
         }
@@ -1,11 +1,13 @@
 use super::{Boids, MessageFromSimulatorToGui, Vec2};
 use crate::gui::{BoidsConfig, MessageFromGuiToSimulator};
-use multi_agent::{MultiAgentSimulation, Result};
+use fastrand::Rng;
+use multi_agent::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
 use std::time::Duration;
 
 #[derive(Debug)]
 pub struct BoidsSimulator {
     data: Boids,
+    rng: Rng,
 }
 
 impl MultiAgentSimulation for BoidsSimulator {
@@ -15,11 +17,12 @@ impl MultiAgentSimulation for BoidsSimulator {
     type MessageFromGui = MessageFromGuiToSimulator;
     type MessageToGui = MessageFromSimulatorToGui;
 
-    fn new(initial_gui_data: Self::GuiData) -> Result<Self> {
+    fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
+        let mut rng = Rng::with_seed(seeds.stream(0));
         let mut data = Boids::default();
-        data.spawn_random(initial_gui_data.boid_count, initial_gui_data.max_speed);
+        data.spawn_random(&mut rng, initial_gui_data.boid_count, initial_gui_data.max_speed);
 
-        Ok(Self { data })
+        Ok(Self { data, rng })
     }
 
     fn update<F>(
@@ -28,19 +31,20 @@ impl MultiAgentSimulation for BoidsSimulator {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         _send_message_to_gui: F,
+        cancellation: &CancellationToken,
     ) -> Result<&Self::SimulationData> {
         for message in messages {
             match message {
                 MessageFromGuiToSimulator::Reset => {
                     self.data.clear();
                     self.data
-                        .spawn_random(gui_data.boid_count, gui_data.max_speed);
+                        .spawn_random(&mut self.rng, gui_data.boid_count, gui_data.max_speed);
                 }
                 MessageFromGuiToSimulator::SpawnBoids(count) => {
-                    self.data.spawn_random(count, gui_data.max_speed);
+                    self.data.spawn_random(&mut self.rng, count, gui_data.max_speed);
                 }
                 MessageFromGuiToSimulator::SetBoidCount(target) => {
-                    self.data.set_count(target, gui_data.max_speed);
+                    self.data.set_count(&mut self.rng, target, gui_data.max_speed);
                 }
                 MessageFromGuiToSimulator::ResizeWorld(width, height) => {
                     self.data.resize(width, height);
@@ -49,7 +53,7 @@ impl MultiAgentSimulation for BoidsSimulator {
         }
 
         if !gui_data.paused {
-            self.process_tick(&gui_data, delta_time.as_secs_f32());
+            self.process_tick(&gui_data, delta_time.as_secs_f32(), cancellation);
         }
 
         Ok(&self.data)
@@ -57,7 +61,13 @@ impl MultiAgentSimulation for BoidsSimulator {
 }
 
 impl BoidsSimulator {
-    fn process_tick(&mut self, config: &BoidsConfig, dt: f32) {
-        // TODO
+    fn process_tick(&mut self, config: &BoidsConfig, dt: f32, cancellation: &CancellationToken) {
+        for boid in &mut self.data.boids {
+            // Give up the tick when the runtime shuts down
+            if cancellation.is_cancelled() {
+                return;
+            }
+            // TODO
+        }
     }
 }
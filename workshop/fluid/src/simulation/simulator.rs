@@ -1,6 +1,6 @@
 use super::{Fluid, MessageFromSimulatorToGui};
 use crate::gui::{FluidConfig, MessageFromGuiToSimulator};
-use multi_agent::{MultiAgentSimulation, Result};
+use multi_agent::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -15,7 +15,7 @@ impl MultiAgentSimulation for FluidSimulator {
     type MessageFromGui = MessageFromGuiToSimulator;
     type MessageToGui = MessageFromSimulatorToGui;
 
-    fn new(initial_gui_data: Self::GuiData) -> Result<Self> {
+    fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
         Ok(Self {
             data: Fluid::default(),
         })
@@ -27,6 +27,7 @@ impl MultiAgentSimulation for FluidSimulator {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         send_message_to_gui: F,
+        cancellation: &CancellationToken,
     ) -> Result<&Self::SimulationData> {
         Ok(&self.data)
     }
@@ -1,6 +1,6 @@
 use super::{GameOfLife, MessageFromSimulatorToGui};
 use crate::gui::{GameOfLifeConfig, MessageFromGuiToSimulator};
-use multi_agent::{MultiAgentSimulation, Result};
+use multi_agent::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use std::time::Duration;
@@ -18,7 +18,7 @@ impl MultiAgentSimulation for GameOfLifeSimulator {
     type MessageFromGui = MessageFromGuiToSimulator;
     type MessageToGui = MessageFromSimulatorToGui;
 
-    fn new(_initial_gui_data: Self::GuiData) -> Result<Self> {
+    fn new(_initial_gui_data: Self::GuiData, _seeds: SeedFactory) -> Result<Self> {
         Ok(Self {
             data: GameOfLife::default(),
             accumulated_time: Duration::ZERO,
@@ -31,6 +31,7 @@ impl MultiAgentSimulation for GameOfLifeSimulator {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         _send_message_to_gui: F,
+        cancellation: &CancellationToken,
     ) -> Result<&Self::SimulationData> {
         for message in messages {
             match message {
@@ -51,7 +52,11 @@ impl MultiAgentSimulation for GameOfLifeSimulator {
             self.accumulated_time += delta_time;
 
             while self.accumulated_time >= tick_duration {
-                self.process_tick();
+                // Give up the remaining generations when the runtime shuts down
+                if cancellation.is_cancelled() {
+                    break;
+                }
+                self.process_tick(cancellation);
                 self.data.generation += 1;
                 self.accumulated_time -= tick_duration;
             }
@@ -62,7 +67,7 @@ impl MultiAgentSimulation for GameOfLifeSimulator {
 }
 
 impl GameOfLifeSimulator {
-    fn process_tick(&mut self) {
+    fn process_tick(&mut self, cancellation: &CancellationToken) {
         // TODO: read https://rust-guide.com/en/documentation/collections/HashSet
         let cells: &mut FxHashSet<(i64, i64)> = &mut self.data.cells;
 
@@ -71,6 +76,8 @@ impl GameOfLifeSimulator {
         ** 2.        Survival: Any live cell with two or three live neighbors lives on to the next generation.
         ** 3.  Overpopulation: Any live cell with more than three live neighbors dies.
         ** 4.    Reproduction: Any dead cell with exactly three live neighbors becomes a live cell.
+        **
+        ** Return early once `cancellation.is_cancelled()`, checked while going through the cells.
         */
     }
 }
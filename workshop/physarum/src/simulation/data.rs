@@ -1,4 +1,5 @@
 use fastrand::Rng;
+use multi_agent::CancellationToken;
 use std::cmp::Ordering;
 
 /// A single slime agent with position and direction
@@ -97,8 +98,15 @@ impl TrailMap {
         }
     }
 
-    /// Apply diffusion (box blur) and decay to the trail map
-    pub fn diffuse_and_decay(&mut self, diffuse_rate: f32, decay_rate: f32, dt: f32) {
+    /// Apply diffusion (box blur) and decay to the trail map, giving up once
+    /// `cancellation` is cancelled
+    pub fn diffuse_and_decay(
+        &mut self,
+        diffuse_rate: f32,
+        decay_rate: f32,
+        dt: f32,
+        cancellation: &CancellationToken,
+    ) {
         let width = self.width;
         let height = self.height;
 
@@ -106,6 +114,9 @@ impl TrailMap {
         let original = self.data.clone();
 
         for y in 0..height {
+            if cancellation.is_cancelled() {
+                return;
+            }
             for x in 0..width {
                 // 3x3 box blur
                 let mut sum = 0.0;
@@ -196,20 +207,19 @@ impl Physarum {
         }
     }
 
-    pub fn spawn_agents(&mut self, count: usize, mode: SpawnMode) {
-        let mut rng = Rng::new();
+    pub fn spawn_agents(&mut self, rng: &mut Rng, count: usize, mode: SpawnMode) {
         let center_x = self.width as f32 / 2.0;
         let center_y = self.height as f32 / 2.0;
         let radius = self.width.min(self.height) as f32 * 0.4;
 
         for _ in 0..count {
             let agent = match mode {
-                SpawnMode::Random => Agent::random(&mut rng, self.width, self.height),
+                SpawnMode::Random => Agent::random(rng, self.width, self.height),
                 SpawnMode::Center => {
                     let angle = rng.f32() * std::f32::consts::TAU;
                     Agent::new(center_x, center_y, angle)
                 }
-                SpawnMode::Circle => Agent::random_in_circle(&mut rng, center_x, center_y, radius),
+                SpawnMode::Circle => Agent::random_in_circle(rng, center_x, center_y, radius),
             };
             self.agents.push(agent);
         }
@@ -226,11 +236,11 @@ impl Physarum {
         self.trail_map.resize(width, height);
     }
 
-    pub fn set_agent_count(&mut self, target: usize, mode: SpawnMode) {
+    pub fn set_agent_count(&mut self, rng: &mut Rng, target: usize, mode: SpawnMode) {
         let current = self.agents.len();
 
         match current.cmp(&target) {
-            Ordering::Less => self.spawn_agents(target - current, mode),
+            Ordering::Less => self.spawn_agents(rng, target - current, mode),
             Ordering::Greater => self.agents.truncate(target),
             Ordering::Equal => {}
         }
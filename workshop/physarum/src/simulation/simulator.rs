@@ -1,7 +1,7 @@
 use super::{MessageFromSimulatorToGui, Physarum};
 use crate::gui::{MessageFromGuiToSimulator, PhysarumConfig};
 use fastrand::Rng;
-use multi_agent::{MultiAgentSimulation, Result};
+use multi_agent::{CancellationToken, MultiAgentSimulation, Result, SeedFactory};
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -17,17 +17,15 @@ impl MultiAgentSimulation for PhysarumSimulator {
     type MessageFromGui = MessageFromGuiToSimulator;
     type MessageToGui = MessageFromSimulatorToGui;
 
-    fn new(initial_gui_data: Self::GuiData) -> Result<Self> {
+    fn new(initial_gui_data: Self::GuiData, seeds: SeedFactory) -> Result<Self> {
+        let mut rng = Rng::with_seed(seeds.stream(0));
         let mut data = Physarum::new(
             initial_gui_data.width,
             initial_gui_data.height,
         );
-        data.spawn_agents(initial_gui_data.agent_count, initial_gui_data.spawn_mode);
+        data.spawn_agents(&mut rng, initial_gui_data.agent_count, initial_gui_data.spawn_mode);
 
-        Ok(Self {
-            data,
-            rng: Rng::new(),
-        })
+        Ok(Self { data, rng })
     }
 
     fn update<F>(
@@ -36,15 +34,18 @@ impl MultiAgentSimulation for PhysarumSimulator {
         messages: Vec<Self::MessageFromGui>,
         delta_time: Duration,
         _send_message_to_gui: F,
+        cancellation: &CancellationToken,
     ) -> Result<&Self::SimulationData> {
         for message in messages {
             match message {
                 MessageFromGuiToSimulator::Reset => {
                     self.data.clear();
-                    self.data.spawn_agents(gui_data.agent_count, gui_data.spawn_mode);
+                    self.data
+                        .spawn_agents(&mut self.rng, gui_data.agent_count, gui_data.spawn_mode);
                 }
                 MessageFromGuiToSimulator::SetAgentCount(count) => {
-                    self.data.set_agent_count(count, gui_data.spawn_mode);
+                    self.data
+                        .set_agent_count(&mut self.rng, count, gui_data.spawn_mode);
                 }
                 MessageFromGuiToSimulator::ResizeWorld(width, height) => {
                     self.data.resize(width, height);
@@ -59,13 +60,14 @@ impl MultiAgentSimulation for PhysarumSimulator {
             let dt = delta_time.as_secs_f32();
 
             for _ in 0..gui_data.steps_per_frame {
-                self.process_agents(&gui_data, dt / gui_data.steps_per_frame as f32);
+                self.process_agents(&gui_data, dt / gui_data.steps_per_frame as f32, cancellation);
             }
 
             self.data.trail_map.diffuse_and_decay(
                 gui_data.diffuse_rate,
                 gui_data.decay_rate,
                 dt,
+                cancellation,
             );
         }
 
@@ -74,7 +76,13 @@ impl MultiAgentSimulation for PhysarumSimulator {
 }
 
 impl PhysarumSimulator {
-    fn process_agents(&mut self, config: &PhysarumConfig, dt: f32) {
-        // TODO
+    fn process_agents(&mut self, config: &PhysarumConfig, dt: f32, cancellation: &CancellationToken) {
+        for agent in &mut self.data.agents {
+            // Give up the tick when the runtime shuts down
+            if cancellation.is_cancelled() {
+                return;
+            }
+            // TODO
+        }
     }
 }